@@ -64,7 +64,7 @@
 pub use self::{
     cors::AllowOrigin,
     error::{Error, ErrorBody, HttpStatusCode, MovedPermanentlyError},
-    manager::{ApiManager, ApiManagerConfig, UpdateEndpoints, WebServerConfig},
+    manager::{ApiManager, ApiManagerConfig, TlsConfig, UpdateEndpoints, WebServerConfig},
     with::{Actuality, Deprecated, NamedWith, Result, With},
 };
 
@@ -33,6 +33,7 @@ use std::{
     collections::HashMap,
     io,
     net::{SocketAddr, TcpListener},
+    path::PathBuf,
     time::Duration,
 };
 
@@ -48,6 +49,10 @@ pub struct WebServerConfig {
     pub allow_origin: Option<AllowOrigin>,
     /// Json payload size.
     pub json_payload_size: Option<usize>,
+    /// Optional TLS settings. If set, the server accepts HTTPS connections only.
+    ///
+    /// TLS is only supported if the crate is compiled with the `tls` feature.
+    pub tls: Option<TlsConfig>,
 }
 
 impl WebServerConfig {
@@ -57,9 +62,16 @@ impl WebServerConfig {
             listen_address,
             allow_origin: None,
             json_payload_size: None,
+            tls: None,
         }
     }
 
+    /// Enables TLS for the server with the specified configuration.
+    pub fn with_tls(mut self, tls: TlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
     fn json_config(&self) -> JsonConfig {
         let config = JsonConfig::default();
 
@@ -77,6 +89,78 @@ impl WebServerConfig {
     }
 }
 
+/// TLS configuration for a web server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TlsConfig {
+    /// Path to the PEM-encoded certificate chain.
+    pub cert_path: PathBuf,
+    /// Path to the PEM-encoded private key (in the PKCS #8 or RSA format).
+    pub key_path: PathBuf,
+}
+
+impl TlsConfig {
+    /// Creates a TLS configuration with the specified certificate and private key paths.
+    pub fn new(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        Self {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+        }
+    }
+
+    /// Loads the certificate and the private key, returning an error if either of them
+    /// cannot be read or parsed.
+    #[cfg(feature = "tls")]
+    pub fn load(&self) -> io::Result<rustls::ServerConfig> {
+        use rustls::{
+            internal::pemfile::{certs, pkcs8_private_keys, rsa_private_keys},
+            NoClientAuth, ServerConfig,
+        };
+        use std::{fs::File, io::BufReader};
+
+        let invalid_data = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+        let open = |path: &PathBuf| {
+            File::open(path).map(BufReader::new).map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!("Cannot open TLS file `{}`: {}", path.display(), e),
+                )
+            })
+        };
+
+        let cert_chain = certs(&mut open(&self.cert_path)?).map_err(|()| {
+            invalid_data(format!(
+                "Cannot parse TLS certificate from `{}`",
+                self.cert_path.display()
+            ))
+        })?;
+        if cert_chain.is_empty() {
+            return Err(invalid_data(format!(
+                "No TLS certificates found in `{}`",
+                self.cert_path.display()
+            )));
+        }
+
+        let key_error = || {
+            invalid_data(format!(
+                "Cannot parse TLS private key from `{}`",
+                self.key_path.display()
+            ))
+        };
+        let mut keys = pkcs8_private_keys(&mut open(&self.key_path)?).map_err(|()| key_error())?;
+        if keys.is_empty() {
+            keys = rsa_private_keys(&mut open(&self.key_path)?).map_err(|()| key_error())?;
+        }
+        let key = keys.into_iter().next().ok_or_else(key_error)?;
+
+        let mut config = ServerConfig::new(NoClientAuth::new());
+        config
+            .set_single_cert(cert_chain, key)
+            .map_err(|e| invalid_data(format!("Invalid TLS configuration: {}", e)))?;
+        Ok(config)
+    }
+}
+
 /// Configuration parameters for `ApiManager`.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -371,14 +455,24 @@ impl ApiManager {
         #[cfg(windows)]
         let raw_socket = listener.as_raw_socket();
 
-        let mut server_builder = HttpServer::new(move || {
+        let tls = server_config.tls.clone();
+        let server_builder = HttpServer::new(move || {
             App::new()
                 .app_data(server_config.json_config())
                 .wrap(server_config.cors_factory())
                 .wrap(error_handlers())
                 .service(aggregator.extend_backend(access, web::scope("api")))
-        })
-        .listen(listener)?;
+        });
+        let mut server_builder = match tls {
+            None => server_builder.listen(listener)?,
+            #[cfg(feature = "tls")]
+            Some(tls) => server_builder.listen_rustls(listener, tls.load()?)?,
+            #[cfg(not(feature = "tls"))]
+            Some(_) => {
+                let msg = "TLS support requires `exonum-api` to be compiled with the `tls` feature";
+                return Err(io::Error::new(io::ErrorKind::Other, msg));
+            }
+        };
 
         if disable_signals {
             server_builder = server_builder.disable_signals();
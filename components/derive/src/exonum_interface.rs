@@ -378,6 +378,14 @@ impl ExonumInterface {
             .iter()
             .map(impl_match_arm_for_removed_method);
 
+        // Names are only resolved for existing methods; removed methods are treated
+        // as unknown.
+        let name_match_arms = self.methods.iter().map(|descriptor| {
+            let ServiceMethodDescriptor { name, id, .. } = descriptor;
+            let name = name.to_string();
+            quote!(#id => Some(#name),)
+        });
+
         let ctx = quote!(#cr::_reexports::ExecutionContext<'a>);
         let res = quote!(std::result::Result<(), exonum::runtime::ExecutionError>);
         quote! {
@@ -396,6 +404,13 @@ impl ExonumInterface {
                         _ => Err(exonum::runtime::CommonError::NoSuchMethod.into()),
                     }
                 }
+
+                fn method_name(method: exonum::runtime::MethodId) -> Option<&'static str> {
+                    match method {
+                        #( #name_match_arms )*
+                        _ => None,
+                    }
+                }
             }
         }
     }
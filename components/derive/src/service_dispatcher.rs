@@ -112,6 +112,20 @@ impl ToTokens for ServiceDispatcher {
             }
         });
 
+        let name_match_arms = self.implements.0.iter().map(|interface| {
+            let trait_name = &interface.path;
+            let interface_trait = if interface.is_raw {
+                quote!(dyn #trait_name)
+            } else {
+                quote!(dyn #trait_name<#ctx, Output = #res>)
+            };
+            let interface_trait = quote!(<#interface_trait as #cr::Interface>);
+
+            quote! {
+                #interface_trait::INTERFACE_NAME => #interface_trait::method_name(method),
+            }
+        });
+
         let expanded = quote! {
             impl #impl_generics #cr::ServiceDispatcher for #service_name #ty_generics #where_clause  {
                 fn call(
@@ -125,6 +139,17 @@ impl ToTokens for ServiceDispatcher {
                         other => Err(#cr::_reexports::CommonError::NoSuchInterface.into()),
                     }
                 }
+
+                fn method_name(
+                    &self,
+                    interface_name: &str,
+                    method: #cr::_reexports::MethodId,
+                ) -> Option<&'static str> {
+                    match interface_name {
+                        #( #name_match_arms )*
+                        _ => None,
+                    }
+                }
             }
         };
         tokens.extend(expanded);
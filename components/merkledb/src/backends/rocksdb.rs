@@ -24,7 +24,7 @@ use smallvec::SmallVec;
 use std::{fmt, iter::Peekable, mem, path::Path, sync::Arc};
 
 use crate::{
-    db::{check_database, Change},
+    db::{check_database, count_keys_with_prefix, Change},
     Database, DbOptions, Iter, Iterator, Patch, ResolvedAddress, Snapshot,
 };
 
@@ -164,6 +164,9 @@ impl RocksDB {
             if changes.is_cleared() {
                 self.clear_prefix(&mut batch, cf, &resolved);
             }
+            for (from, to) in &changes.removed_ranges {
+                batch.delete_range_cf(cf, resolved.keyed(from), resolved.keyed(to));
+            }
 
             if let Some(id_bytes) = resolved.id_to_bytes() {
                 // Write changes to the column family with each key prefixed by the ID of the
@@ -265,6 +268,39 @@ impl Database for RocksDB {
         w_opts.set_sync(true);
         self.do_merge(patch, &w_opts)
     }
+
+    /// Flushes memtables of all column families to disk and compacts the entire key range
+    /// of each column family.
+    fn flush(&self) -> crate::Result<()> {
+        let db = self.get_lock_guard();
+        let cf_names = rocksdb::DB::list_cf(&RocksDbOptions::default(), db.path())?;
+        for cf_name in &cf_names {
+            if let Some(cf) = db.cf_handle(cf_name) {
+                db.flush_cf(cf)?;
+                db.compact_range_cf(cf, None::<&[u8]>, None::<&[u8]>);
+            }
+        }
+        Ok(())
+    }
+
+    /// Sums the `rocksdb.estimate-num-keys` property over all column families. The estimate
+    /// includes auxiliary keys (e.g., index metadata) and may be inaccurate if keys were
+    /// overwritten or deleted since the last compaction.
+    fn approximate_num_keys(&self) -> usize {
+        let db = self.get_lock_guard();
+        let cf_names = rocksdb::DB::list_cf(&RocksDbOptions::default(), db.path())
+            .unwrap_or_else(|err| panic!("{}", err));
+        cf_names
+            .iter()
+            .filter_map(|cf_name| db.cf_handle(cf_name))
+            .map(|cf| {
+                let count = db
+                    .property_int_value_cf(cf, "rocksdb.estimate-num-keys")
+                    .unwrap_or_else(|err| panic!("{}", err));
+                count.unwrap_or(0) as usize
+            })
+            .sum()
+    }
 }
 
 impl Snapshot for RocksDBSnapshot {
@@ -282,6 +318,30 @@ impl Snapshot for RocksDBSnapshot {
     fn iter(&self, name: &ResolvedAddress, from: &[u8]) -> Iter<'_> {
         Box::new(self.rocksdb_iter(name, from))
     }
+
+    fn count_prefix(&self, name: &ResolvedAddress, prefix: &[u8]) -> usize {
+        if self.get_lock_guard().cf_handle(&name.name).is_none() {
+            return 0;
+        }
+
+        count_keys_with_prefix(Box::new(self.rocksdb_iter(name, prefix)), prefix)
+    }
+
+    fn multi_get(&self, name: &ResolvedAddress, keys: &[&[u8]]) -> Vec<Option<Vec<u8>>> {
+        // Resolve the column family once and look up all keys in a single batch.
+        let lock_guard = self.get_lock_guard();
+        let cf = match lock_guard.cf_handle(&name.name) {
+            Some(cf) => cf,
+            None => return vec![None; keys.len()],
+        };
+
+        let keys = keys.iter().map(|key| (cf, name.keyed(key)));
+        self.snapshot
+            .multi_get_cf(keys)
+            .into_iter()
+            .map(|res| res.unwrap_or_else(|e| panic!("{}", e)))
+            .collect()
+    }
 }
 
 impl<'a> Iterator for RocksDBIterator<'a> {
@@ -14,34 +14,77 @@
 
 //! An implementation of `TemporaryDB` database.
 
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use crossbeam::sync::ShardedLock;
 use smallvec::SmallVec;
 use std::{
+    borrow::Cow,
     collections::{btree_map::Range, BTreeMap, HashMap},
+    fmt,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
     iter::{Iterator, Peekable},
-    sync::Arc,
+    mem,
+    num::NonZeroU64,
+    ops::Bound,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
 };
 
 use crate::{
     backends::rocksdb::{next_id_bytes, ID_SIZE},
-    db::{check_database, Change, Iterator as DbIterator},
-    Database, Iter, Patch, ResolvedAddress, Result, Snapshot,
+    db::{check_database, Change, Iterator as DbIterator, ViewChanges},
+    Database, Error, Iter, Patch, ResolvedAddress, Result, Snapshot,
 };
 
 type MemoryDB = HashMap<ResolvedAddress, BTreeMap<Vec<u8>, Vec<u8>>>;
 
+/// Magic bytes at the start of files produced by `TemporaryDB::dump_to`.
+const DUMP_MAGIC: &[u8; 8] = b"EXTMPDB\0";
+/// Current version of the dump format.
+const DUMP_VERSION: u32 = 1;
+
 /// This in-memory database is only used for testing and experimenting; is not designed to
 /// operate under load in production.
 #[derive(Debug)]
 pub struct TemporaryDB {
     inner: Arc<ShardedLock<MemoryDB>>,
+    /// Are checks ensuring RocksDB-compatible behavior enabled?
+    strict: bool,
+    /// Version of the database contents, which is incremented on each change of the contents.
+    /// The version is only modified while holding the write lock on `inner`.
+    version: AtomicU64,
 }
 
 struct TemporarySnapshot {
     snapshot: MemoryDB,
 }
 
-struct TemporaryDBIterator<'a> {
+/// Snapshot of a `TemporaryDB` tagged with the version of the database contents.
+///
+/// The version is incremented each time the database contents is changed (e.g., when a patch
+/// is merged). Since a snapshot is a copy of the database contents, it is isolated
+/// from the changes made after its creation; the version allows tooling to assert this
+/// by comparing the version of the snapshot with [`TemporaryDB::version`].
+///
+/// [`TemporaryDB::version`]: struct.TemporaryDB.html#method.version
+pub struct VersionedSnapshot {
+    version: u64,
+    inner: TemporarySnapshot,
+}
+
+/// Iterator over a view in `TemporaryDB`. The iterator is fused: once it returns `None`,
+/// it keeps returning `None`. A cloned iterator continues from the same position
+/// independently of the original one.
+///
+/// The iterator can be obtained with [`VersionedSnapshot::cloneable_iter`].
+///
+/// [`VersionedSnapshot::cloneable_iter`]: struct.VersionedSnapshot.html#method.cloneable_iter
+#[derive(Debug, Clone)]
+pub struct TemporaryDBIterator<'a> {
     iter: Peekable<Range<'a, Vec<u8>, Vec<u8>>>,
     prefix: Option<[u8; ID_SIZE]>,
     ended: bool,
@@ -54,11 +97,40 @@ impl TemporaryDB {
 
         db.insert(ResolvedAddress::system("default"), BTreeMap::new());
         let inner = Arc::new(ShardedLock::new(db));
-        let mut db = Self { inner };
+        let mut db = Self {
+            inner,
+            strict: false,
+            version: AtomicU64::new(0),
+        };
         check_database(&mut db).unwrap();
         db
     }
 
+    /// Creates a new, empty database which additionally checks that the written data
+    /// can be handled uniformly by all database backends, in particular, by [`RocksDB`].
+    /// This allows to catch non-portable behavior in tests using the in-memory database.
+    ///
+    /// The following restrictions are enforced in addition to those of [`new`]:
+    ///
+    /// - Zero-length keys are rejected. Keys produced by MerkleDB indexes always contain
+    ///   the index ID, so the full key may only be empty if a view without an ID
+    ///   is accessed directly. [`merge`] returns an error for a patch with such a key,
+    ///   and no changes from the patch are applied.
+    ///
+    /// Other aspects of the database behavior already coincide with `RocksDB` and thus do not
+    /// depend on this option. In particular, empty values are stored and retrieved as-is,
+    /// a deleted and then reinserted key is visible during iteration at its usual position,
+    /// and iteration is performed in the lexicographic order of keys.
+    ///
+    /// [`RocksDB`]: struct.RocksDB.html
+    /// [`new`]: #method.new
+    /// [`merge`]: trait.Database.html#tymethod.merge
+    pub fn with_strict_rocks_semantics() -> Self {
+        let mut db = Self::new();
+        db.strict = true;
+        db
+    }
+
     /// Clears the contents of the database.
     pub fn clear(&self) -> crate::Result<()> {
         let mut rw_lock = self.inner.write().expect("Couldn't get read-write lock");
@@ -66,10 +138,158 @@ impl TemporaryDB {
         for collection in rw_lock.values_mut() {
             collection.clear();
         }
+        self.version.fetch_add(1, Ordering::SeqCst);
 
         Ok(())
     }
 
+    /// Rebuilds the internal storage of the database to release the memory retained after
+    /// removing entries. Empty collections (which may remain, e.g., after clearing indexes)
+    /// are removed as well. The contents of the database is not changed.
+    ///
+    /// This may be useful in long-running tests which repeatedly insert and remove many keys.
+    pub fn shrink_to_fit(&self) {
+        let mut inner = self.inner.write().expect("Couldn't get write lock");
+        let default_address = ResolvedAddress::system("default");
+        inner.retain(|address, collection| !collection.is_empty() || *address == default_address);
+        for collection in inner.values_mut() {
+            *collection = mem::take(collection).into_iter().collect();
+        }
+        inner.shrink_to_fit();
+    }
+
+    /// Replaces the entire contents of the database with `contents`, which maps
+    /// resolved addresses of views to their key-value data. This is much faster than merging
+    /// a patch with the same data, and is thus useful to install large prepared fixtures
+    /// in tests. All data previously stored in the database is discarded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `contents` specify an incompatible database version, or violate
+    /// the restrictions of the [strict mode] if it is enabled. In this case, the database
+    /// is not changed.
+    ///
+    /// [strict mode]: #method.with_strict_rocks_semantics
+    pub fn replace_contents(
+        &self,
+        mut contents: HashMap<ResolvedAddress, BTreeMap<Vec<u8>, Vec<u8>>>,
+    ) -> Result<()> {
+        if self.strict {
+            check_strict_contents(&contents)?;
+        }
+        contents
+            .entry(ResolvedAddress::system("default"))
+            .or_default();
+
+        let mut staging = Self {
+            inner: Arc::new(ShardedLock::new(contents)),
+            strict: self.strict,
+            version: AtomicU64::new(0),
+        };
+        check_database(&mut staging)?;
+        let contents = mem::take(&mut *staging.inner.write().expect("Couldn't get write lock"));
+        let mut inner = self.inner.write().expect("Couldn't get write lock");
+        *inner = contents;
+        self.version.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Writes the contents of the database to the file at `path`, creating or truncating it.
+    ///
+    /// The file can be loaded with [`load_from`]. This is useful to store database fixtures
+    /// for tests. The file format is an implementation detail; it starts with a magic header
+    /// and a format version, so that the incompatible files are rejected during loading.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exonum_merkledb::{access::CopyAccessExt, Database, TemporaryDB};
+    /// # use tempfile::TempDir;
+    /// let db = TemporaryDB::new();
+    /// let fork = db.fork();
+    /// fork.get_list("list").extend(vec![1_u32, 2, 3]);
+    /// db.merge(fork.into_patch()).unwrap();
+    ///
+    /// let dir = TempDir::new().unwrap();
+    /// let path = dir.path().join("db.dump");
+    /// db.dump_to(&path).unwrap();
+    ///
+    /// let loaded_db = TemporaryDB::load_from(&path).unwrap();
+    /// let snapshot = loaded_db.snapshot();
+    /// let list = snapshot.get_list::<_, u32>("list");
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    ///
+    /// [`load_from`]: #method.load_from
+    pub fn dump_to(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let inner = self.inner.read().expect("Couldn't get read lock");
+        let file = File::create(path).map_err(|e| dump_error(path, &e))?;
+        let mut writer = BufWriter::new(file);
+        write_dump(&mut writer, &inner)
+            .and_then(|()| writer.flush())
+            .map_err(|e| dump_error(path, &e))
+    }
+
+    /// Loads a database from the file at `path` previously created with [`dump_to`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, or if it is not a database dump
+    /// of the supported version.
+    ///
+    /// [`dump_to`]: #method.dump_to
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| load_error(path, &e))?;
+        let db = read_dump(&mut BufReader::new(file)).map_err(|e| load_error(path, &e))?;
+        let inner = Arc::new(ShardedLock::new(db));
+        let mut db = Self {
+            inner,
+            strict: false,
+            version: AtomicU64::new(0),
+        };
+        check_database(&mut db)?;
+        Ok(db)
+    }
+
+    /// Returns the current version of the database contents. The version is incremented
+    /// each time the contents is changed, e.g., when a patch is merged or the database
+    /// is cleared.
+    pub fn version(&self) -> u64 {
+        let _guard = self.inner.read().expect("Couldn't get read lock");
+        self.version.load(Ordering::SeqCst)
+    }
+
+    /// Creates a snapshot of the database tagged with the current version
+    /// of the database contents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exonum_merkledb::{access::CopyAccessExt, Database, Snapshot, TemporaryDB};
+    /// let db = TemporaryDB::new();
+    /// let snapshot = db.snapshot_versioned();
+    ///
+    /// let fork = db.fork();
+    /// fork.get_list("list").push(1_u32);
+    /// db.merge(fork.into_patch()).unwrap();
+    ///
+    /// // The snapshot does not see the changes made after its creation.
+    /// assert!(db.version() > snapshot.version());
+    /// let snapshot: &dyn Snapshot = &snapshot;
+    /// assert!(snapshot.get_list::<_, u32>("list").is_empty());
+    /// ```
+    pub fn snapshot_versioned(&self) -> VersionedSnapshot {
+        let inner = self.inner.read().expect("Couldn't get read lock");
+        VersionedSnapshot {
+            version: self.version.load(Ordering::SeqCst),
+            inner: TemporarySnapshot {
+                snapshot: inner.clone(),
+            },
+        }
+    }
+
     fn temporary_snapshot(&self) -> TemporarySnapshot {
         TemporarySnapshot {
             snapshot: self.inner.read().expect("Couldn't get read lock").clone(),
@@ -77,14 +297,143 @@ impl TemporaryDB {
     }
 }
 
+fn dump_error(path: &Path, err: &io::Error) -> Error {
+    Error::new(format!(
+        "Cannot dump database to `{}`: {}",
+        path.display(),
+        err
+    ))
+}
+
+fn load_error(path: &Path, err: &io::Error) -> Error {
+    Error::new(format!(
+        "Cannot load database from `{}`: {}",
+        path.display(),
+        err
+    ))
+}
+
+fn write_bytes(writer: &mut impl Write, bytes: &[u8]) -> io::Result<()> {
+    writer.write_u64::<LittleEndian>(bytes.len() as u64)?;
+    writer.write_all(bytes)
+}
+
+fn read_bytes(reader: &mut impl Read) -> io::Result<Vec<u8>> {
+    let len = reader.read_u64::<LittleEndian>()?;
+    let mut bytes = Vec::new();
+    // Use `take` so that a corrupted length does not lead to a huge allocation.
+    reader.by_ref().take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(bytes)
+}
+
+/// Writes the database contents. Column families are sorted by their addresses
+/// so that dumps of equal databases are equal.
+fn write_dump(writer: &mut impl Write, db: &MemoryDB) -> io::Result<()> {
+    writer.write_all(DUMP_MAGIC)?;
+    writer.write_u32::<LittleEndian>(DUMP_VERSION)?;
+
+    let mut collections: Vec<_> = db.iter().collect();
+    collections.sort_unstable_by(|(x, _), (y, _)| (&x.name, x.id).cmp(&(&y.name, y.id)));
+    writer.write_u64::<LittleEndian>(collections.len() as u64)?;
+
+    for (address, collection) in collections {
+        write_bytes(writer, address.name.as_bytes())?;
+        // `0` is never a valid ID, so it is used to encode the absence of an ID.
+        writer.write_u64::<LittleEndian>(address.id.map_or(0, NonZeroU64::get))?;
+        writer.write_u64::<LittleEndian>(collection.len() as u64)?;
+        for (key, value) in collection {
+            write_bytes(writer, key)?;
+            write_bytes(writer, value)?;
+        }
+    }
+    Ok(())
+}
+
+fn read_dump(reader: &mut impl Read) -> io::Result<MemoryDB> {
+    let mut magic = [0_u8; 8];
+    reader.read_exact(&mut magic)?;
+    if &magic != DUMP_MAGIC {
+        let msg = "file is not a `TemporaryDB` dump";
+        return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+    }
+    let version = reader.read_u32::<LittleEndian>()?;
+    if version != DUMP_VERSION {
+        let msg = format!(
+            "unsupported dump version {} (expected {})",
+            version, DUMP_VERSION
+        );
+        return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+    }
+
+    let collections_count = reader.read_u64::<LittleEndian>()?;
+    let mut db = HashMap::new();
+    for _ in 0..collections_count {
+        let name = String::from_utf8(read_bytes(reader)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let id = NonZeroU64::new(reader.read_u64::<LittleEndian>()?);
+        let entries_count = reader.read_u64::<LittleEndian>()?;
+
+        let mut collection = BTreeMap::new();
+        for _ in 0..entries_count {
+            let key = read_bytes(reader)?;
+            let value = read_bytes(reader)?;
+            collection.insert(key, value);
+        }
+        db.insert(ResolvedAddress::new(name, id), collection);
+    }
+    Ok(db)
+}
+
+/// Checks changes in a patch against the restrictions of the strict mode.
+fn check_strict_changes(changes: &HashMap<ResolvedAddress, ViewChanges>) -> Result<()> {
+    for (address, view_changes) in changes {
+        if address.id.is_none() && view_changes.data.keys().any(Vec::is_empty) {
+            return Err(Error::new(format!(
+                "Zero-length key in view `{}` is not allowed with strict RocksDB semantics",
+                address.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Checks contents installed via `TemporaryDB::replace_contents` against the restrictions
+/// of the strict mode.
+fn check_strict_contents(contents: &MemoryDB) -> Result<()> {
+    for (address, collection) in contents {
+        if address.id.is_none() && collection.keys().any(Vec::is_empty) {
+            return Err(Error::new(format!(
+                "Zero-length key in view `{}` is not allowed with strict RocksDB semantics",
+                address.name
+            )));
+        }
+    }
+    Ok(())
+}
+
 impl Database for TemporaryDB {
     fn snapshot(&self) -> Box<dyn Snapshot> {
         Box::new(self.temporary_snapshot())
     }
 
+    /// Returns the exact number of keys stored in the database, including auxiliary keys
+    /// (e.g., index metadata).
+    fn approximate_num_keys(&self) -> usize {
+        let inner = self.inner.read().expect("Couldn't get read lock");
+        inner.values().map(BTreeMap::len).sum()
+    }
+
     fn merge(&self, patch: Patch) -> Result<()> {
+        let patch_changes = patch.into_changes();
+        if self.strict {
+            check_strict_changes(&patch_changes)?;
+        }
+
         let mut inner = self.inner.write().expect("Couldn't get write lock");
-        for (resolved, changes) in patch.into_changes() {
+        for (resolved, changes) in patch_changes {
             if !inner.contains_key(&resolved) {
                 inner.insert(resolved.clone(), BTreeMap::new());
             }
@@ -101,6 +450,11 @@ impl Database for TemporaryDB {
                     collection.clear();
                 }
             }
+            for (from, to) in &changes.removed_ranges {
+                let mut middle_and_tail = collection.split_off(resolved.keyed(from).as_ref());
+                let mut tail = middle_and_tail.split_off(resolved.keyed(to).as_ref());
+                collection.append(&mut tail);
+            }
 
             if let Some(id_bytes) = resolved.id_to_bytes() {
                 // Write changes to the column family with each key prefixed by the ID of the
@@ -130,6 +484,7 @@ impl Database for TemporaryDB {
                 }
             }
         }
+        self.version.fetch_add(1, Ordering::SeqCst);
         Ok(())
     }
 
@@ -144,7 +499,13 @@ impl<'a> DbIterator for TemporaryDBIterator<'a> {
             return None;
         }
 
-        let (key, value) = self.iter.next()?;
+        let (key, value) = match self.iter.next() {
+            Some(entry) => entry,
+            None => {
+                self.ended = true;
+                return None;
+            }
+        };
 
         if let Some(ref prefix) = self.prefix {
             if &key[..ID_SIZE] != prefix {
@@ -182,13 +543,8 @@ impl<'a> DbIterator for TemporaryDBIterator<'a> {
     }
 }
 
-impl Snapshot for TemporarySnapshot {
-    fn get(&self, name: &ResolvedAddress, key: &[u8]) -> Option<Vec<u8>> {
-        let collection = self.snapshot.get(name)?;
-        collection.get(name.keyed(key).as_ref()).cloned()
-    }
-
-    fn iter(&self, name: &ResolvedAddress, from: &[u8]) -> Iter<'_> {
+impl TemporarySnapshot {
+    fn temporary_iter(&self, name: &ResolvedAddress, from: &[u8]) -> TemporaryDBIterator<'_> {
         let collection = self
             .snapshot
             .get(name)
@@ -197,14 +553,145 @@ impl Snapshot for TemporarySnapshot {
         let from = name.keyed(from).into_owned();
         let iter = collection.range::<Vec<u8>, _>(&from..);
 
-        Box::new(TemporaryDBIterator {
+        TemporaryDBIterator {
             iter: iter.peekable(),
             prefix: name.id_to_bytes(),
             ended: false,
-        })
+        }
+    }
+
+    fn range_iter<'a>(
+        &'a self,
+        name: &ResolvedAddress,
+        from: &[u8],
+        to: Option<&[u8]>,
+    ) -> impl Iterator<Item = (&'a [u8], &'a [u8])> + 'a {
+        let from = name.keyed(from).into_owned();
+        let until = match to {
+            Some(to) => Bound::Excluded(name.keyed(to).into_owned()),
+            None => name
+                .id_to_bytes()
+                .and_then(|id_bytes| prefix_upper_bound(&id_bytes))
+                .map_or(Bound::Unbounded, Bound::Excluded),
+        };
+        let prefix_len = if name.id.is_some() { ID_SIZE } else { 0 };
+
+        self.snapshot
+            .get(name)
+            .into_iter()
+            .flat_map(move |collection| {
+                collection.range::<Vec<u8>, _>((Bound::Included(from.clone()), until.clone()))
+            })
+            .map(move |(key, value)| (&key[prefix_len..], value.as_slice()))
+    }
+}
+
+impl Snapshot for TemporarySnapshot {
+    fn get(&self, name: &ResolvedAddress, key: &[u8]) -> Option<Vec<u8>> {
+        let collection = self.snapshot.get(name)?;
+        collection.get(name.keyed(key).as_ref()).cloned()
+    }
+
+    fn get_pinned(&self, name: &ResolvedAddress, key: &[u8]) -> Option<Cow<'_, [u8]>> {
+        let collection = self.snapshot.get(name)?;
+        collection
+            .get(name.keyed(key).as_ref())
+            .map(|value| Cow::Borrowed(value.as_slice()))
+    }
+
+    fn iter(&self, name: &ResolvedAddress, from: &[u8]) -> Iter<'_> {
+        Box::new(self.temporary_iter(name, from))
+    }
+
+    fn count_prefix(&self, name: &ResolvedAddress, prefix: &[u8]) -> usize {
+        let until = prefix_upper_bound(prefix);
+        self.range_iter(name, prefix, until.as_deref()).count()
+    }
+
+    fn multi_get(&self, name: &ResolvedAddress, keys: &[&[u8]]) -> Vec<Option<Vec<u8>>> {
+        let collection = match self.snapshot.get(name) {
+            Some(collection) => collection,
+            None => return vec![None; keys.len()],
+        };
+        keys.iter()
+            .map(|key| collection.get(name.keyed(key).as_ref()).cloned())
+            .collect()
     }
 }
 
+impl VersionedSnapshot {
+    /// Returns the version of the database contents captured by this snapshot.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Iterates over entries of the view starting from the `from` key. Unlike
+    /// [`Snapshot::iter`], the returned iterator has a concrete type, which can be cloned
+    /// to continue iteration from the same position independently, e.g., for a lookahead.
+    ///
+    /// [`Snapshot::iter`]: trait.Snapshot.html#tymethod.iter
+    pub fn cloneable_iter(&self, name: &ResolvedAddress, from: &[u8]) -> TemporaryDBIterator<'_> {
+        self.inner.temporary_iter(name, from)
+    }
+
+    /// Iterates over entries of the view with keys in the range `from..to`, or starting from
+    /// `from` until the end of the view if `to` is not specified.
+    ///
+    /// Unlike [`Snapshot::iter`], the returned iterator has a concrete type and does not
+    /// require boxing, so it can be used in performance-sensitive code operating
+    /// on a `TemporaryDB` snapshot.
+    ///
+    /// [`Snapshot::iter`]: trait.Snapshot.html#tymethod.iter
+    pub fn range_iter<'a>(
+        &'a self,
+        name: &ResolvedAddress,
+        from: &[u8],
+        to: Option<&[u8]>,
+    ) -> impl Iterator<Item = (&'a [u8], &'a [u8])> + 'a {
+        self.inner.range_iter(name, from, to)
+    }
+}
+
+impl fmt::Debug for VersionedSnapshot {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("VersionedSnapshot")
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+impl Snapshot for VersionedSnapshot {
+    fn get(&self, name: &ResolvedAddress, key: &[u8]) -> Option<Vec<u8>> {
+        self.inner.get(name, key)
+    }
+
+    fn get_pinned(&self, name: &ResolvedAddress, key: &[u8]) -> Option<Cow<'_, [u8]>> {
+        self.inner.get_pinned(name, key)
+    }
+
+    fn iter(&self, name: &ResolvedAddress, from: &[u8]) -> Iter<'_> {
+        self.inner.iter(name, from)
+    }
+
+    fn count_prefix(&self, name: &ResolvedAddress, prefix: &[u8]) -> usize {
+        self.inner.count_prefix(name, prefix)
+    }
+
+    fn multi_get(&self, name: &ResolvedAddress, keys: &[&[u8]]) -> Vec<Option<Vec<u8>>> {
+        self.inner.multi_get(name, keys)
+    }
+}
+
+/// Returns the smallest key greater than all keys starting with `prefix`, or `None`
+/// if there is no such key (i.e., `prefix` consists of `0xff` bytes only).
+fn prefix_upper_bound(prefix: &[u8]) -> Option<Vec<u8>> {
+    let last_incrementable = prefix.iter().rposition(|&byte| byte != u8::max_value())?;
+    let mut bound = prefix[..=last_incrementable].to_vec();
+    bound[last_incrementable] += 1;
+    Some(bound)
+}
+
 impl Default for TemporaryDB {
     fn default() -> Self {
         Self::new()
@@ -245,3 +732,389 @@ fn clearing_database() {
     assert_eq!(list.len(), 3);
     assert_eq!(list.iter().collect::<Vec<_>>(), vec![4, 5, 6]);
 }
+
+#[test]
+fn dumping_and_loading_database() {
+    use crate::{access::CopyAccessExt, ObjectHash};
+
+    let db = TemporaryDB::new();
+    let fork = db.fork();
+    fork.get_list("foo").extend(vec![1_u32, 2, 3]);
+    fork.get_proof_entry(("bar", &0_u8)).set("!".to_owned());
+    fork.get_proof_map("baz").put(&5_u64, "five".to_owned());
+    fork.get_entry("empty_value").set(Vec::<u8>::new());
+    db.merge(fork.into_patch()).unwrap();
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("db.dump");
+    db.dump_to(&path).unwrap();
+    let loaded_db = TemporaryDB::load_from(&path).unwrap();
+
+    let snapshot = db.snapshot();
+    let loaded_snapshot = loaded_db.snapshot();
+    let inner = db.inner.read().unwrap();
+    for address in inner.keys() {
+        let entries: Vec<_> = snapshot
+            .iter(address, &[])
+            .map(|(key, value)| (key.to_vec(), value.to_vec()))
+            .collect();
+        let loaded_entries: Vec<_> = loaded_snapshot
+            .iter(address, &[])
+            .map(|(key, value)| (key.to_vec(), value.to_vec()))
+            .collect();
+        assert_eq!(entries, loaded_entries);
+    }
+    assert_eq!(*inner, *loaded_db.inner.read().unwrap());
+
+    let list = loaded_snapshot.get_list::<_, u32>("foo");
+    assert_eq!(list.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    let map = loaded_snapshot.get_proof_map::<_, u64, String>("baz");
+    assert_eq!(map.get(&5), Some("five".to_owned()));
+    assert_eq!(
+        snapshot
+            .get_proof_map::<_, u64, String>("baz")
+            .object_hash(),
+        map.object_hash()
+    );
+
+    // The loaded database is fully functional.
+    let fork = loaded_db.fork();
+    fork.get_list("foo").push(4_u32);
+    loaded_db.merge(fork.into_patch()).unwrap();
+    let snapshot = loaded_db.snapshot();
+    let list = snapshot.get_list::<_, u32>("foo");
+    assert_eq!(list.iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn loading_invalid_dump() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("db.dump");
+    std::fs::write(&path, b"definitely not a database").unwrap();
+    let err = TemporaryDB::load_from(&path).unwrap_err();
+    assert!(
+        err.to_string().contains("not a `TemporaryDB` dump"),
+        "{}",
+        err
+    );
+
+    let mut contents = DUMP_MAGIC.to_vec();
+    contents.extend_from_slice(&(DUMP_VERSION + 1).to_le_bytes());
+    std::fs::write(&path, contents).unwrap();
+    let err = TemporaryDB::load_from(&path).unwrap_err();
+    assert!(
+        err.to_string().contains("unsupported dump version"),
+        "{}",
+        err
+    );
+
+    let err = TemporaryDB::load_from(dir.path().join("missing.dump")).unwrap_err();
+    assert!(err.to_string().contains("Cannot load database"), "{}", err);
+}
+
+#[test]
+fn prefix_upper_bounds() {
+    assert_eq!(prefix_upper_bound(&[]), None);
+    assert_eq!(prefix_upper_bound(&[0xff, 0xff]), None);
+    assert_eq!(prefix_upper_bound(&[1, 2]), Some(vec![1, 3]));
+    assert_eq!(prefix_upper_bound(&[1, 0xff]), Some(vec![2]));
+}
+
+#[test]
+fn cloning_iterator() {
+    use crate::views::View;
+
+    let db = TemporaryDB::new();
+    let fork = db.fork();
+    let address = ResolvedAddress::new("foo", NonZeroU64::new(1));
+    {
+        let mut view = View::new(&fork, address.clone());
+        for i in 1_u8..=3 {
+            view.put(&vec![i], vec![i * 10]);
+        }
+        // Use a view in the same column family to check that iteration stops at its boundary.
+        let mut view = View::new(&fork, ResolvedAddress::new("foo", NonZeroU64::new(2)));
+        view.put(&vec![0_u8], vec![0]);
+    }
+    db.merge(fork.into_patch()).unwrap();
+
+    let snapshot = db.snapshot_versioned();
+    let mut iter = snapshot.cloneable_iter(&address, &[]);
+    assert_eq!(iter.next(), Some((&[1_u8][..], &[10_u8][..])));
+    let mut cloned_iter = iter.clone();
+    assert_eq!(iter.peek(), Some((&[2_u8][..], &[20_u8][..])));
+    assert_eq!(iter.next(), Some((&[2_u8][..], &[20_u8][..])));
+    assert_eq!(iter.next(), Some((&[3_u8][..], &[30_u8][..])));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+
+    // The cloned iterator continues from the position at which it was cloned.
+    assert_eq!(cloned_iter.peek(), Some((&[2_u8][..], &[20_u8][..])));
+    assert_eq!(cloned_iter.next(), Some((&[2_u8][..], &[20_u8][..])));
+    let mut lookahead = cloned_iter.clone();
+    assert_eq!(lookahead.next(), Some((&[3_u8][..], &[30_u8][..])));
+    assert_eq!(lookahead.next(), None);
+    assert_eq!(cloned_iter.next(), Some((&[3_u8][..], &[30_u8][..])));
+    assert_eq!(cloned_iter.peek(), None);
+    assert_eq!(cloned_iter.next(), None);
+}
+
+#[test]
+fn strict_mode_rejects_empty_keys() {
+    use crate::views::View;
+
+    let db = TemporaryDB::with_strict_rocks_semantics();
+    let fork = db.fork();
+    {
+        let mut view = View::new(&fork, ResolvedAddress::system("bar"));
+        view.put(&vec![1_u8], vec![1_u8]);
+        let mut view = View::new(&fork, ResolvedAddress::system("foo"));
+        view.put(&Vec::<u8>::new(), vec![1_u8]);
+    }
+    let err = db.merge(fork.into_patch()).unwrap_err();
+    assert!(err.to_string().contains("Zero-length key in view `foo`"));
+
+    // No changes from the patch should be applied.
+    let snapshot = db.snapshot();
+    assert_eq!(snapshot.get(&ResolvedAddress::system("bar"), &[1]), None);
+
+    // Empty keys within views with an ID are fine, since the full key contains the ID.
+    let fork = db.fork();
+    let address = ResolvedAddress::new("foo", NonZeroU64::new(1));
+    View::new(&fork, address.clone()).put(&Vec::<u8>::new(), vec![1_u8]);
+    db.merge(fork.into_patch()).unwrap();
+    assert_eq!(db.snapshot().get(&address, &[]), Some(vec![1]));
+
+    // The non-strict database accepts empty keys.
+    let db = TemporaryDB::new();
+    let fork = db.fork();
+    View::new(&fork, ResolvedAddress::system("foo")).put(&Vec::<u8>::new(), vec![1_u8]);
+    db.merge(fork.into_patch()).unwrap();
+    assert_eq!(
+        db.snapshot().get(&ResolvedAddress::system("foo"), &[]),
+        Some(vec![1])
+    );
+}
+
+#[test]
+fn strict_mode_empty_values_round_trip() {
+    use crate::views::View;
+
+    let db = TemporaryDB::with_strict_rocks_semantics();
+    let fork = db.fork();
+    let address = ResolvedAddress::new("foo", NonZeroU64::new(1));
+    View::new(&fork, address.clone()).put(&vec![1_u8], Vec::<u8>::new());
+    db.merge(fork.into_patch()).unwrap();
+
+    let snapshot = db.snapshot();
+    assert_eq!(snapshot.get(&address, &[1]), Some(vec![]));
+    assert!(snapshot.contains(&address, &[1]));
+    let mut iter = snapshot.iter(&address, &[]);
+    assert_eq!(iter.next(), Some((&[1_u8][..], &[][..])));
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn approximate_num_keys() {
+    use crate::access::CopyAccessExt;
+
+    let db = TemporaryDB::new();
+    let fork = db.fork();
+    fork.get_map("map").put(&0_u32, 0_u32);
+    db.merge(fork.into_patch()).unwrap();
+    // The database contains auxiliary keys, such as index metadata.
+    let initial_count = db.approximate_num_keys();
+    assert!(initial_count > 1);
+
+    let fork = db.fork();
+    {
+        let mut map = fork.get_map("map");
+        for i in 1..10_u32 {
+            map.put(&i, i);
+        }
+        map.remove(&0);
+        map.remove(&5);
+    }
+    db.merge(fork.into_patch()).unwrap();
+    assert_eq!(db.approximate_num_keys(), initial_count + 7);
+
+    let fork = db.fork();
+    fork.get_map::<_, u32, u32>("map").remove(&1);
+    db.merge(fork.into_patch()).unwrap();
+    assert_eq!(db.approximate_num_keys(), initial_count + 6);
+}
+
+#[test]
+fn shrink_to_fit() {
+    use crate::access::CopyAccessExt;
+
+    let db = TemporaryDB::new();
+    let fork = db.fork();
+    {
+        let mut map = fork.get_map("map");
+        for i in 0..1_000_u32 {
+            map.put(&i, i);
+        }
+        fork.get_list("list").extend(0_u32..100);
+    }
+    db.merge(fork.into_patch()).unwrap();
+    let initial_count = db.approximate_num_keys();
+
+    let fork = db.fork();
+    {
+        let mut map = fork.get_map::<_, u32, u32>("map");
+        for i in 10..1_000_u32 {
+            map.remove(&i);
+        }
+        fork.get_list::<_, u32>("list").clear();
+    }
+    db.merge(fork.into_patch()).unwrap();
+    let count = db.approximate_num_keys();
+    // 990 map entries and all 100 list entries have been removed. The list length
+    // is stored in the index metadata and is thus removed as well.
+    assert!(count < initial_count - 1_000);
+
+    db.shrink_to_fit();
+    assert_eq!(db.approximate_num_keys(), count);
+    let snapshot = db.snapshot();
+    let map = snapshot.get_map::<_, u32, u32>("map");
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        (0..10).map(|i| (i, i)).collect::<Vec<_>>()
+    );
+    assert!(snapshot.get_list::<_, u32>("list").is_empty());
+
+    // The database remains usable after shrinking.
+    let fork = db.fork();
+    fork.get_map("map").put(&10_u32, 10_u32);
+    fork.get_list("list").push(1_u32);
+    db.merge(fork.into_patch()).unwrap();
+    let snapshot = db.snapshot();
+    assert_eq!(snapshot.get_map::<_, u32, u32>("map").get(&10), Some(10));
+    assert_eq!(snapshot.get_list::<_, u32>("list").len(), 1);
+}
+
+#[test]
+fn replacing_contents() {
+    use crate::access::CopyAccessExt;
+
+    let db = TemporaryDB::new();
+    let fork = db.fork();
+    fork.get_list("list").extend(vec![1_u32, 2, 3]);
+    db.merge(fork.into_patch()).unwrap();
+
+    let address = ResolvedAddress::new("fixture", None);
+    let data: BTreeMap<_, _> = (0_u8..100).map(|i| (vec![i], vec![i, i])).collect();
+    let mut contents = HashMap::new();
+    contents.insert(address.clone(), data.clone());
+    db.replace_contents(contents).unwrap();
+
+    let snapshot = db.snapshot();
+    assert_eq!(snapshot.get(&address, &[5]), Some(vec![5, 5]));
+    assert_eq!(snapshot.get(&address, &[100]), None);
+    let mut iter = snapshot.iter(&address, &[]);
+    let mut items = vec![];
+    while let Some((key, value)) = iter.next() {
+        items.push((key.to_vec(), value.to_vec()));
+    }
+    assert_eq!(items, data.into_iter().collect::<Vec<_>>());
+    // The prior state is cleared.
+    assert!(snapshot.index_type("list").is_none());
+
+    // The database remains usable after replacing contents.
+    let fork = db.fork();
+    fork.get_list("list").push(4_u32);
+    db.merge(fork.into_patch()).unwrap();
+    assert_eq!(db.snapshot().get_list::<_, u32>("list").len(), 1);
+
+    // Zero-length keys are rejected in the strict mode, and the database is not changed.
+    let strict_db = TemporaryDB::with_strict_rocks_semantics();
+    let mut contents = HashMap::new();
+    contents.insert(
+        address.clone(),
+        vec![(vec![], vec![1])].into_iter().collect(),
+    );
+    assert!(strict_db.replace_contents(contents).is_err());
+    assert_eq!(strict_db.snapshot().get(&address, &[]), None);
+}
+
+#[test]
+fn range_iter_matches_boxed_iter() {
+    use crate::views::View;
+
+    let db = TemporaryDB::new();
+    let fork = db.fork();
+    let addresses = vec![
+        ResolvedAddress::new("foo", None),
+        ResolvedAddress::new("bar", NonZeroU64::new(1)),
+        ResolvedAddress::new("bar", NonZeroU64::new(2)),
+    ];
+    for address in &addresses {
+        let mut view = View::new(&fork, address.clone());
+        for i in 0_u16..1_000 {
+            view.put(&i.to_be_bytes(), vec![i as u8]);
+        }
+    }
+    db.merge(fork.into_patch()).unwrap();
+
+    let snapshot = db.snapshot_versioned();
+    for address in &addresses {
+        let mut boxed_items = vec![];
+        let mut iter = snapshot.iter(address, &[]);
+        while let Some((key, value)) = iter.next() {
+            boxed_items.push((key.to_vec(), value.to_vec()));
+        }
+        let items: Vec<_> = snapshot
+            .range_iter(address, &[], None)
+            .map(|(key, value)| (key.to_vec(), value.to_vec()))
+            .collect();
+        assert_eq!(items.len(), 1_000);
+        assert_eq!(items, boxed_items);
+
+        let from = 100_u16.to_be_bytes();
+        let to = 200_u16.to_be_bytes();
+        let items: Vec<_> = snapshot
+            .range_iter(address, &from, Some(&to[..]))
+            .map(|(key, value)| (key.to_vec(), value.to_vec()))
+            .collect();
+        assert_eq!(items, boxed_items[100..200].to_vec());
+    }
+
+    // Iteration over a non-existing view yields nothing.
+    let address = ResolvedAddress::new("baz", None);
+    assert_eq!(snapshot.range_iter(&address, &[], None).count(), 0);
+}
+
+#[test]
+fn versioned_snapshot_is_isolated() {
+    use crate::access::CopyAccessExt;
+
+    let db = TemporaryDB::new();
+    let fork = db.fork();
+    fork.get_list("list").extend(vec![1_u32, 2, 3]);
+    db.merge(fork.into_patch()).unwrap();
+
+    let snapshot = db.snapshot_versioned();
+    let version = snapshot.version();
+    assert_eq!(version, db.version());
+
+    let fork = db.fork();
+    fork.get_list("list").push(4_u32);
+    fork.get_entry("entry").set("foo".to_owned());
+    db.merge(fork.into_patch()).unwrap();
+    assert!(db.version() > version);
+
+    // The snapshot sees neither the new data, nor the changes to the existing data.
+    assert_eq!(snapshot.version(), version);
+    let snapshot_ref: &dyn Snapshot = &snapshot;
+    let list = snapshot_ref.get_list::<_, u32>("list");
+    assert_eq!(list.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert!(!snapshot_ref.get_entry::<_, String>("entry").exists());
+
+    // Clearing the database changes the version as well.
+    let version = db.version();
+    db.clear().unwrap();
+    assert!(db.version() > version);
+    let snapshot_ref: &dyn Snapshot = &snapshot;
+    assert_eq!(snapshot_ref.get_list::<_, u32>("list").len(), 3);
+}
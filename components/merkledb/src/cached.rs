@@ -0,0 +1,196 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Read-through cache over a database snapshot.
+
+use std::{borrow::Cow, collections::HashMap, fmt, sync::Mutex};
+
+use crate::{Iter, ResolvedAddress, Snapshot};
+
+type Cache = HashMap<ResolvedAddress, HashMap<Vec<u8>, Option<Vec<u8>>>>;
+
+/// Snapshot wrapper memoizing results of point reads.
+///
+/// `CachedSnapshot` delegates to the wrapped snapshot only the first time a certain key
+/// is read; subsequent `get`, `contains` and `multi_get` calls for the key are served
+/// from memory. Absent values are cached as well. Iteration and `get_pinned` calls
+/// are not cached and are always delegated to the wrapped snapshot, so that the latter
+/// can avoid copying values.
+///
+/// Since snapshots are immutable, the cache never becomes stale. However, it is never
+/// cleared either, so the wrapper should be short-lived. For example, a service performing
+/// many repeated reads when processing a block should create a new `CachedSnapshot`
+/// for each block and drop it afterwards.
+///
+/// # Examples
+///
+/// ```
+/// # use exonum_merkledb::{access::CopyAccessExt, CachedSnapshot, Database, Snapshot, TemporaryDB};
+/// let db = TemporaryDB::new();
+/// let fork = db.fork();
+/// fork.get_entry("entry").set(1_u64);
+/// db.merge(fork.into_patch()).unwrap();
+///
+/// let cached = CachedSnapshot::new(db.snapshot());
+/// let snapshot: &dyn Snapshot = &cached;
+/// assert_eq!(snapshot.get_entry::<_, u64>("entry").get(), Some(1));
+/// // The second read is served from the cache.
+/// assert_eq!(snapshot.get_entry::<_, u64>("entry").get(), Some(1));
+/// ```
+pub struct CachedSnapshot<S> {
+    inner: S,
+    cache: Mutex<Cache>,
+}
+
+impl<S: Snapshot> CachedSnapshot<S> {
+    /// Wraps the snapshot with an empty cache.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            cache: Mutex::default(),
+        }
+    }
+
+    /// Returns a reference to the wrapped snapshot.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Drops the cache and returns the wrapped snapshot.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S> fmt::Debug for CachedSnapshot<S> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cached_keys: usize = self.cache.lock().unwrap().values().map(HashMap::len).sum();
+        formatter
+            .debug_struct("CachedSnapshot")
+            .field("cached_keys", &cached_keys)
+            .finish()
+    }
+}
+
+impl<S: Snapshot> Snapshot for CachedSnapshot<S> {
+    fn get(&self, name: &ResolvedAddress, key: &[u8]) -> Option<Vec<u8>> {
+        let mut cache = self.cache.lock().unwrap();
+        if let Some(value) = cache.get(name).and_then(|values| values.get(key)) {
+            return value.clone();
+        }
+
+        let value = self.inner.get(name, key);
+        cache
+            .entry(name.to_owned())
+            .or_default()
+            .insert(key.to_vec(), value.clone());
+        value
+    }
+
+    fn get_pinned(&self, name: &ResolvedAddress, key: &[u8]) -> Option<Cow<'_, [u8]>> {
+        self.inner.get_pinned(name, key)
+    }
+
+    fn contains(&self, name: &ResolvedAddress, key: &[u8]) -> bool {
+        self.get(name, key).is_some()
+    }
+
+    fn iter(&self, name: &ResolvedAddress, from: &[u8]) -> Iter<'_> {
+        self.inner.iter(name, from)
+    }
+
+    fn count_prefix(&self, name: &ResolvedAddress, prefix: &[u8]) -> usize {
+        self.inner.count_prefix(name, prefix)
+    }
+
+    fn multi_get(&self, name: &ResolvedAddress, keys: &[&[u8]]) -> Vec<Option<Vec<u8>>> {
+        let mut cache = self.cache.lock().unwrap();
+        let values = cache.entry(name.to_owned()).or_default();
+
+        // Only the keys missing from the cache are requested from the wrapped snapshot.
+        let missing_keys: Vec<&[u8]> = keys
+            .iter()
+            .copied()
+            .filter(|&key| !values.contains_key(key))
+            .collect();
+        if !missing_keys.is_empty() {
+            let missing_values = self.inner.multi_get(name, &missing_keys);
+            for (key, value) in missing_keys.into_iter().zip(missing_values) {
+                values.insert(key.to_vec(), value);
+            }
+        }
+        keys.iter().map(|&key| values[key].clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::CachedSnapshot;
+    use crate::{access::CopyAccessExt, Database, Iter, ResolvedAddress, Snapshot, TemporaryDB};
+
+    /// Snapshot counting point reads from the wrapped snapshot.
+    struct CountingSnapshot {
+        inner: Box<dyn Snapshot>,
+        reads: AtomicUsize,
+    }
+
+    impl Snapshot for CountingSnapshot {
+        fn get(&self, name: &ResolvedAddress, key: &[u8]) -> Option<Vec<u8>> {
+            self.reads.fetch_add(1, Ordering::SeqCst);
+            self.inner.get(name, key)
+        }
+
+        fn iter(&self, name: &ResolvedAddress, from: &[u8]) -> Iter<'_> {
+            self.inner.iter(name, from)
+        }
+    }
+
+    #[test]
+    fn repeated_reads_hit_backend_once() {
+        let db = TemporaryDB::new();
+        let fork = db.fork();
+        fork.get_map("map").put(&1_u8, "foo".to_owned());
+        db.merge(fork.into_patch()).unwrap();
+
+        let cached = CachedSnapshot::new(CountingSnapshot {
+            inner: db.snapshot(),
+            reads: AtomicUsize::new(0),
+        });
+        let backend_reads = || cached.inner().reads.load(Ordering::SeqCst);
+        let snapshot: &dyn Snapshot = &cached;
+
+        let map = snapshot.get_map::<_, u8, String>("map");
+        assert_eq!(map.get(&1), Some("foo".to_owned()));
+        let reads_after_first_get = backend_reads();
+        assert!(reads_after_first_get > 0);
+
+        // Neither repeated reads, nor reads via a new index instance reach the backend.
+        assert_eq!(map.get(&1), Some("foo".to_owned()));
+        let map = snapshot.get_map::<_, u8, String>("map");
+        assert_eq!(map.get(&1), Some("foo".to_owned()));
+        assert!(map.contains(&1));
+        assert_eq!(backend_reads(), reads_after_first_get);
+
+        // Absent values are cached as well.
+        assert_eq!(map.get(&2), None);
+        assert_eq!(backend_reads(), reads_after_first_get + 1);
+        assert_eq!(map.get(&2), None);
+        assert_eq!(backend_reads(), reads_after_first_get + 1);
+
+        // Iteration is not affected by the cache.
+        assert_eq!(map.keys().collect::<Vec<_>>(), vec![1]);
+    }
+}
@@ -0,0 +1,347 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Consistency checks for Merkelized indexes in a database snapshot.
+
+use exonum_crypto::Hash;
+
+use crate::{
+    indexes::proof_map::{Raw, ToProofPath},
+    views::{IndexAddress, IndexType, IndexesPool, ViewWithMetadata},
+    BinaryKey, HashTag, ObjectHash, ProofListIndex, ProofMapIndex, Snapshot, SystemSchema,
+};
+
+use std::borrow::Borrow;
+
+/// Inconsistency in a Merkelized index detected by [`verify_consistency`].
+///
+/// [`verify_consistency`]: fn.verify_consistency.html
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum Inconsistency {
+    /// The hash of the index calculated from the stored Merkle tree does not match the hash
+    /// recomputed from the index contents.
+    IndexHash {
+        /// Address of the index.
+        index: IndexAddress,
+        /// Hash calculated from the stored Merkle tree.
+        stored: Hash,
+        /// Hash recomputed from the index contents.
+        recomputed: Hash,
+    },
+    /// The number of leaves of the Merkle tree of a `ProofMapIndex` does not match
+    /// the number of map values.
+    MapLeaves {
+        /// Address of the index.
+        index: IndexAddress,
+        /// Number of leaves of the Merkle tree.
+        leaves: usize,
+        /// Number of values in the map.
+        values: usize,
+    },
+    /// The Merkle tree of a `ProofMapIndex` has no leaf for the key, or the hash stored
+    /// in the leaf does not match the hash of the value of the key.
+    MapEntry {
+        /// Address of the index.
+        index: IndexAddress,
+        /// Serialized key.
+        key: Vec<u8>,
+    },
+    /// The hash of the index recorded in the state aggregator does not match the hash
+    /// calculated from the stored Merkle tree of the index.
+    AggregatedHash {
+        /// Address of the index.
+        index: IndexAddress,
+        /// Hash recorded in the state aggregator.
+        aggregated: Hash,
+        /// Hash calculated from the stored Merkle tree.
+        stored: Hash,
+    },
+}
+
+/// Checks that Merkle trees of all `ProofListIndex` and `ProofMapIndex` instances
+/// in the snapshot correspond to the index contents, and that the hashes of aggregated
+/// indexes recorded in the [state aggregator] are up to date.
+///
+/// Other index types, including `ProofEntry`, are not checked since their hashes
+/// cannot be recomputed without knowing the type of the stored values. Likewise,
+/// the map values are not matched with the leaves of the Merkle tree, since the leaf
+/// corresponding to a key is determined by the key type; use [`verify_map_consistency`]
+/// for this check.
+///
+/// This function reads all entries of Merkelized indexes, so it is intended for debugging
+/// and testing rather than for production use; e.g., it may be run when a test finishes.
+///
+/// # Examples
+///
+/// ```
+/// # use exonum_merkledb::{access::CopyAccessExt, verify_consistency, Database, TemporaryDB};
+/// let db = TemporaryDB::new();
+/// let fork = db.fork();
+/// fork.get_proof_list("list").extend(vec![1_u32, 2, 3]);
+/// fork.get_proof_map("map").put(&1_u8, "foo".to_owned());
+/// db.merge(fork.into_patch()).unwrap();
+///
+/// assert_eq!(verify_consistency(&db.snapshot()), Ok(()));
+/// ```
+///
+/// [state aggregator]: index.html#state-aggregation
+/// [`verify_map_consistency`]: fn.verify_map_consistency.html
+pub fn verify_consistency(snapshot: &dyn Snapshot) -> Result<(), Vec<Inconsistency>> {
+    let aggregator = SystemSchema::new(snapshot).state_aggregator();
+    let mut inconsistencies = vec![];
+
+    for (index, _) in IndexesPool::new(snapshot).indexes() {
+        let index_type = ViewWithMetadata::get_metadata_unchecked(snapshot, &index)
+            .expect("Index from the pool has no metadata")
+            .index_type();
+        let (stored, recomputed) = match index_type {
+            IndexType::ProofList => list_hashes(snapshot, &index),
+            IndexType::ProofMap => map_hashes(snapshot, &index, &mut inconsistencies),
+            _ => continue,
+        };
+
+        if stored != recomputed {
+            inconsistencies.push(Inconsistency::IndexHash {
+                index: index.clone(),
+                stored,
+                recomputed,
+            });
+        }
+
+        if index.id_in_group().is_none() {
+            if let Some(aggregated) = aggregator.get(index.name()) {
+                if aggregated != stored {
+                    inconsistencies.push(Inconsistency::AggregatedHash {
+                        index,
+                        aggregated,
+                        stored,
+                    });
+                }
+            }
+        }
+    }
+
+    if inconsistencies.is_empty() {
+        Ok(())
+    } else {
+        Err(inconsistencies)
+    }
+}
+
+/// Returns the stored and recomputed hashes of a `ProofListIndex`.
+fn list_hashes(snapshot: &dyn Snapshot, index: &IndexAddress) -> (Hash, Hash) {
+    let view = ViewWithMetadata::get_or_create_unchecked(snapshot, index, IndexType::ProofList)
+        .expect("Cannot access list");
+    let list = ProofListIndex::<_, Vec<u8>>::new(view);
+    let values: Vec<_> = list.iter().collect();
+    (list.object_hash(), HashTag::hash_list(&values))
+}
+
+/// Returns the stored and recomputed hashes of a `ProofMapIndex`.
+fn map_hashes(
+    snapshot: &dyn Snapshot,
+    index: &IndexAddress,
+    inconsistencies: &mut Vec<Inconsistency>,
+) -> (Hash, Hash) {
+    let view = ViewWithMetadata::get_or_create_unchecked(snapshot, index, IndexType::ProofMap)
+        .expect("Cannot access map");
+    // The key type and mode are irrelevant, since map keys are never decoded by the checks.
+    let map = ProofMapIndex::<_, Hash, Vec<u8>, Raw>::new(view);
+    let (leaves, values) = map.leaf_and_value_counts();
+    if leaves != values {
+        inconsistencies.push(Inconsistency::MapLeaves {
+            index: index.clone(),
+            leaves,
+            values,
+        });
+    }
+    let recomputed = HashTag::hash_map_node(map.recompute_merkle_root());
+    (map.object_hash(), recomputed)
+}
+
+/// Checks that each value of the `ProofMapIndex` with the specified address is stored
+/// in the Merkle tree under the leaf corresponding to its key, in addition to the checks
+/// performed for maps by [`verify_consistency`]. The key type `K` and the key mode
+/// (i.e., [`Hashed`] or [`Raw`]) must match the ones used to create the map.
+///
+/// # Examples
+///
+/// ```
+/// # use exonum_merkledb::{
+/// #     access::CopyAccessExt, proof_map::Hashed, verify_map_consistency, Database, TemporaryDB,
+/// # };
+/// let db = TemporaryDB::new();
+/// let fork = db.fork();
+/// fork.get_proof_map("map").put(&1_u8, "foo".to_owned());
+/// db.merge(fork.into_patch()).unwrap();
+///
+/// assert_eq!(verify_map_consistency::<u8, Hashed>(&db.snapshot(), "map"), Ok(()));
+/// ```
+///
+/// [`verify_consistency`]: fn.verify_consistency.html
+/// [`Hashed`]: proof_map/struct.Hashed.html
+/// [`Raw`]: proof_map/struct.Raw.html
+pub fn verify_map_consistency<K, KeyMode>(
+    snapshot: &dyn Snapshot,
+    address: impl Into<IndexAddress>,
+) -> Result<(), Vec<Inconsistency>>
+where
+    K: BinaryKey + ?Sized,
+    KeyMode: ToProofPath<K>,
+{
+    let index = address.into();
+    let mut inconsistencies = vec![];
+    let (stored, recomputed) = map_hashes(snapshot, &index, &mut inconsistencies);
+    if stored != recomputed {
+        inconsistencies.push(Inconsistency::IndexHash {
+            index: index.clone(),
+            stored,
+            recomputed,
+        });
+    }
+
+    let view = ViewWithMetadata::get_or_create_unchecked(snapshot, &index, IndexType::ProofMap)
+        .expect("Cannot access map");
+    let map = ProofMapIndex::<_, K, Vec<u8>, KeyMode>::new(view);
+    for key in map.mismatched_keys() {
+        let key: &K = key.borrow();
+        let mut key_bytes = vec![0_u8; key.size()];
+        key.write(&mut key_bytes);
+        inconsistencies.push(Inconsistency::MapEntry {
+            index: index.clone(),
+            key: key_bytes,
+        });
+    }
+
+    if inconsistencies.is_empty() {
+        Ok(())
+    } else {
+        Err(inconsistencies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{verify_consistency, verify_map_consistency, Inconsistency};
+    use crate::{
+        access::CopyAccessExt,
+        proof_map::Hashed,
+        views::{IndexesPool, View},
+        Database, IndexAddress, TemporaryDB,
+    };
+
+    #[test]
+    fn corrupted_list_value_is_detected() {
+        let db = TemporaryDB::new();
+        let fork = db.fork();
+        fork.get_proof_list("list").extend(vec![1_u32, 2, 3]);
+        fork.get_proof_map("map").put(&1_u8, "foo".to_owned());
+        fork.get_proof_list(("group", &1_u8)).push(5_u64);
+        db.merge(fork.into_patch()).unwrap();
+        assert_eq!(verify_consistency(&db.snapshot()), Ok(()));
+
+        // Overwrite the second list element bypassing the index, so that the Merkle tree
+        // is not updated. List values are stored with keys `height << 56 | index`,
+        // where the height of leaves is 0.
+        let fork = db.fork();
+        let address = IndexesPool::new(&fork)
+            .indexes()
+            .find_map(|(index, address)| {
+                if index.name() == "list" {
+                    Some(address)
+                } else {
+                    None
+                }
+            })
+            .unwrap();
+        View::new(&fork, address).put(&1_u64, 42_u32);
+        db.merge(fork.into_patch()).unwrap();
+
+        let inconsistencies = verify_consistency(&db.snapshot()).unwrap_err();
+        assert_eq!(inconsistencies.len(), 1);
+        assert!(matches!(
+            &inconsistencies[0],
+            Inconsistency::IndexHash { index, .. } if *index == IndexAddress::from_root("list")
+        ));
+    }
+
+    #[test]
+    fn swapped_map_values_are_detected() {
+        let db = TemporaryDB::new();
+        let fork = db.fork();
+        {
+            let mut map = fork.get_proof_map("map");
+            map.put(&1_u8, "foo".to_owned());
+            map.put(&2_u8, "bar".to_owned());
+        }
+        db.merge(fork.into_patch()).unwrap();
+        assert_eq!(
+            verify_map_consistency::<u8, Hashed>(&db.snapshot(), "map"),
+            Ok(())
+        );
+
+        // Swap the values bypassing the index, so that the hashes of the values
+        // still match the leaves as a whole, but not for each key.
+        let fork = db.fork();
+        let address = IndexesPool::new(&fork).indexes().next().unwrap().1;
+        // Values are stored with the `VALUE_KEY_PREFIX` prefix before the key.
+        let mut view = View::new(&fork, address);
+        view.put(&[2_u8, 1][..], "bar".to_owned());
+        view.put(&[2_u8, 2][..], "foo".to_owned());
+        drop(view);
+        db.merge(fork.into_patch()).unwrap();
+
+        let index = IndexAddress::from_root("map");
+        assert_eq!(
+            verify_map_consistency::<u8, Hashed>(&db.snapshot(), "map"),
+            Err(vec![
+                Inconsistency::MapEntry {
+                    index: index.clone(),
+                    key: vec![1],
+                },
+                Inconsistency::MapEntry {
+                    index,
+                    key: vec![2]
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn missing_map_value_is_detected() {
+        let db = TemporaryDB::new();
+        let fork = db.fork();
+        {
+            let mut map = fork.get_proof_map("map");
+            map.put(&1_u8, "foo".to_owned());
+            map.put(&2_u8, "bar".to_owned());
+        }
+        db.merge(fork.into_patch()).unwrap();
+
+        let fork = db.fork();
+        let address = IndexesPool::new(&fork).indexes().next().unwrap().1;
+        View::new(&fork, address).remove(&[2_u8, 1][..]);
+        db.merge(fork.into_patch()).unwrap();
+
+        assert_eq!(
+            verify_consistency(&db.snapshot()),
+            Err(vec![Inconsistency::MapLeaves {
+                index: IndexAddress::from_root("map"),
+                leaves: 2,
+                values: 1,
+            }])
+        );
+    }
+}
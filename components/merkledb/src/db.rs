@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::{
+    borrow::Cow,
     cell::RefCell,
     collections::{BTreeMap, HashMap, HashSet},
     fmt,
@@ -27,7 +28,8 @@ use std::{
 use crate::{
     validation::assert_valid_name_component,
     views::{
-        get_object_hash, AsReadonly, ChangesIter, IndexesPool, RawAccess, ResolvedAddress, View,
+        get_object_hash, AsReadonly, ChangesIter, IndexAddress, IndexesPool, RawAccess,
+        ResolvedAddress, View,
     },
     Error, Result, SystemSchema,
 };
@@ -39,6 +41,9 @@ pub struct ViewChanges {
     pub(super) data: BTreeMap<Vec<u8>, Change>,
     /// Was the view cleared as a part of changes?
     is_cleared: bool,
+    /// Half-open ranges of keys removed from the view as a part of changes. Changes in `data`
+    /// are made after the removal and take precedence over the ranges.
+    pub(super) removed_ranges: Vec<(Vec<u8>, Vec<u8>)>,
     /// Is the view aggregated into `state_hash` of the database?
     /// Storing this information directly in the changes allows to avoid relatively expensive
     /// metadata lookups during state aggregator update in `Fork::into_patch()`.
@@ -61,9 +66,45 @@ impl ViewChanges {
 
     pub fn clear(&mut self) {
         self.data.clear();
+        self.removed_ranges.clear();
         self.is_cleared = true;
     }
 
+    /// Removes keys in the half-open range `[from, to)`. The range is recorded as a whole,
+    /// so the keys from the underlying snapshot are not enumerated.
+    pub fn remove_range(&mut self, from: &[u8], to: &[u8]) {
+        if from >= to {
+            return;
+        }
+
+        let mut middle_and_tail = self.data.split_off(from);
+        let mut tail = middle_and_tail.split_off(to);
+        self.data.append(&mut tail);
+        // The snapshot is ignored for a cleared view, so the range need not be recorded.
+        if !self.is_cleared {
+            self.removed_ranges.push((from.to_vec(), to.to_vec()));
+        }
+    }
+
+    /// Checks whether the key belongs to one of the removed ranges.
+    fn is_removed(&self, key: &[u8]) -> bool {
+        self.removed_ranges
+            .iter()
+            .any(|(from, to)| from.as_slice() <= key && key < to.as_slice())
+    }
+
+    /// Wraps an iterator over the underlying snapshot so that it skips removed keys.
+    pub(crate) fn skip_removed<'a>(&'a self, iter: Iter<'a>) -> Iter<'a> {
+        if self.removed_ranges.is_empty() {
+            iter
+        } else {
+            Box::new(SkipRemovedIter {
+                inner: iter,
+                changes: self,
+            })
+        }
+    }
+
     pub fn set_aggregation(&mut self, namespace: Option<String>) {
         self.namespace = namespace;
     }
@@ -81,7 +122,7 @@ impl ViewChanges {
                 Change::Delete => None,
             });
         }
-        if self.is_cleared() {
+        if self.is_cleared() || self.is_removed(key) {
             return Ok(None);
         }
         Err(())
@@ -97,13 +138,46 @@ impl ViewChanges {
             });
         }
 
-        if self.is_cleared() {
+        if self.is_cleared() || self.is_removed(key) {
             return Ok(false);
         }
         Err(())
     }
 }
 
+/// Iterator over the underlying snapshot skipping keys in the ranges removed by `ViewChanges`.
+struct SkipRemovedIter<'a> {
+    inner: Iter<'a>,
+    changes: &'a ViewChanges,
+}
+
+impl SkipRemovedIter<'_> {
+    fn skip_removed(&mut self) {
+        loop {
+            let is_removed = match self.inner.peek() {
+                Some((key, _)) => self.changes.is_removed(key),
+                None => false,
+            };
+            if !is_removed {
+                break;
+            }
+            self.inner.next();
+        }
+    }
+}
+
+impl Iterator for SkipRemovedIter<'_> {
+    fn next(&mut self) -> Option<(&[u8], &[u8])> {
+        self.skip_removed();
+        self.inner.next()
+    }
+
+    fn peek(&mut self) -> Option<(&[u8], &[u8])> {
+        self.skip_removed();
+        self.inner.peek()
+    }
+}
+
 /// Cell holding changes for a specific view. Mutable view borrows take changes out
 /// of the `Option` and unwraps `Rc` into inner data, while immutable borrows clone inner `Rc`.
 type ChangesCell = Option<Rc<ViewChanges>>;
@@ -288,6 +362,10 @@ impl WorkingPatch {
             if changes.is_cleared() {
                 *patch_changes = changes;
             } else {
+                // Removed ranges precede the new changes in `data`.
+                for (from, to) in &changes.removed_ranges {
+                    patch_changes.remove_range(from, to);
+                }
                 patch_changes.data.extend(changes.data);
             }
         }
@@ -583,6 +661,39 @@ pub trait Database: Send + Sync + 'static {
     /// will be returned. In case of an error, the method guarantees no changes are applied to
     /// the database.
     fn merge_sync(&self, patch: Patch) -> Result<()>;
+
+    /// Flushes the merged changes to the persistent storage and compacts the storage,
+    /// if applicable to the backend. This method does not change the logical database state;
+    /// it may be used, e.g., in benchmarks to exclude background maintenance from measurements.
+    ///
+    /// The default implementation does nothing, which is appropriate for in-memory backends.
+    ///
+    /// # Errors
+    ///
+    /// If this method encounters any form of I/O or other error, an error variant
+    /// will be returned.
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Returns an estimate of the total number of keys in the database. The estimate
+    /// is intended for diagnostics and capacity planning, and may be much cheaper to compute
+    /// than the exact number of keys.
+    ///
+    /// The default implementation counts entries in all indexes of the database with
+    /// [`Snapshot::count_prefix`], which requires a full scan. It does not account for
+    /// auxiliary data, such as index metadata. Backends may override it with a more
+    /// efficient implementation.
+    ///
+    /// [`Snapshot::count_prefix`]: trait.Snapshot.html#method.count_prefix
+    fn approximate_num_keys(&self) -> usize {
+        let snapshot = self.snapshot();
+        let snapshot = snapshot.as_ref();
+        IndexesPool::new(snapshot)
+            .indexes()
+            .map(|(_, address)| snapshot.count_prefix(&address, &[]))
+            .sum()
+    }
 }
 
 /// Extension trait for `Database`.
@@ -657,12 +768,22 @@ pub trait DatabaseExt: Database {
                     view_changes.insert(key.to_vec(), Change::Put(value.to_vec()));
                 }
             }
+            for (from, to) in &changes.removed_ranges {
+                let mut iter = snapshot.iter(name, from);
+                while let Some((key, value)) = iter.next() {
+                    if key >= to.as_slice() {
+                        break;
+                    }
+                    view_changes.insert(key.to_vec(), Change::Put(value.to_vec()));
+                }
+            }
 
             rev_changes.insert(
                 name.to_owned(),
                 ViewChanges {
                     data: view_changes,
                     is_cleared: false,
+                    removed_ranges: vec![],
                     namespace: changes.namespace.clone(),
                 },
             );
@@ -680,6 +801,18 @@ pub trait DatabaseExt: Database {
 
 impl<T: Database> DatabaseExt for T {}
 
+/// Counts keys starting with `prefix` in an iterator positioned at the `prefix`.
+pub(crate) fn count_keys_with_prefix(mut iter: Iter<'_>, prefix: &[u8]) -> usize {
+    let mut count = 0;
+    while let Some((key, _)) = iter.next() {
+        if !key.starts_with(prefix) {
+            break;
+        }
+        count += 1;
+    }
+    count
+}
+
 /// A read-only snapshot of a storage backend.
 ///
 /// A `Snapshot` instance is an immutable representation of a certain storage state.
@@ -690,6 +823,15 @@ pub trait Snapshot: Send + Sync + 'static {
     /// or `None` if it does not exist.
     fn get(&self, name: &ResolvedAddress, key: &[u8]) -> Option<Vec<u8>>;
 
+    /// Returns a value corresponding to the specified address and key, borrowing it
+    /// from the snapshot if possible, or `None` if the value does not exist.
+    ///
+    /// The default implementation copies the value using [`get`](#tymethod.get).
+    /// Backends keeping values in memory may override it to avoid the copy.
+    fn get_pinned(&self, name: &ResolvedAddress, key: &[u8]) -> Option<Cow<'_, [u8]>> {
+        self.get(name, key).map(Cow::Owned)
+    }
+
     /// Returns `true` if the snapshot contains a value for the specified address and key.
     ///
     /// The default implementation checks existence of the value using [`get`](#tymethod.get).
@@ -700,6 +842,23 @@ pub trait Snapshot: Send + Sync + 'static {
     /// Returns an iterator over the entries of the snapshot in ascending order starting from
     /// the specified key. The iterator element type is `(&[u8], &[u8])`.
     fn iter(&self, name: &ResolvedAddress, from: &[u8]) -> Iter<'_>;
+
+    /// Returns the number of keys at the specified address starting with `prefix`.
+    ///
+    /// The default implementation iterates over the matching entries using
+    /// [`iter`](#tymethod.iter). Backends may override it with a more efficient implementation.
+    fn count_prefix(&self, name: &ResolvedAddress, prefix: &[u8]) -> usize {
+        count_keys_with_prefix(self.iter(name, prefix), prefix)
+    }
+
+    /// Returns values corresponding to the specified address and keys. The returned vector
+    /// has the same length as `keys`; its `i`th element is the value for `keys[i]`.
+    ///
+    /// The default implementation calls [`get`](#tymethod.get) for each key. Backends may
+    /// override it with a more efficient implementation.
+    fn multi_get(&self, name: &ResolvedAddress, keys: &[&[u8]]) -> Vec<Option<Vec<u8>>> {
+        keys.iter().map(|key| self.get(name, key)).collect()
+    }
 }
 
 /// A trait that defines a streaming iterator over storage view entries. Unlike
@@ -729,6 +888,21 @@ impl Snapshot for Patch {
             .unwrap_or_else(|()| self.snapshot.get(name, key))
     }
 
+    fn get_pinned(&self, name: &ResolvedAddress, key: &[u8]) -> Option<Cow<'_, [u8]>> {
+        if let Some(changes) = self.changes.get(name) {
+            if let Some(change) = changes.data.get(key) {
+                return match change {
+                    Change::Put(value) => Some(Cow::Borrowed(value.as_slice())),
+                    Change::Delete => None,
+                };
+            }
+            if changes.is_cleared() || changes.is_removed(key) {
+                return None;
+            }
+        }
+        self.snapshot.get_pinned(name, key)
+    }
+
     fn contains(&self, name: &ResolvedAddress, key: &[u8]) -> bool {
         self.changes
             .get(name)
@@ -750,7 +924,29 @@ impl Snapshot for Patch {
             // Ignore all changes from the snapshot.
             Box::new(ChangesIter::new(changes_iter.unwrap()))
         } else {
-            Box::new(ForkIter::new(self.snapshot.iter(name, from), changes_iter))
+            let snapshot_iter = self.snapshot.iter(name, from);
+            let snapshot_iter = match maybe_changes {
+                Some(changes) => changes.skip_removed(snapshot_iter),
+                None => snapshot_iter,
+            };
+            Box::new(ForkIter::new(snapshot_iter, changes_iter))
+        }
+    }
+
+    fn count_prefix(&self, name: &ResolvedAddress, prefix: &[u8]) -> usize {
+        if self.changes.contains_key(name) {
+            // Changes need to be merged with the snapshot entries, so we have to iterate.
+            count_keys_with_prefix(self.iter(name, prefix), prefix)
+        } else {
+            self.snapshot.count_prefix(name, prefix)
+        }
+    }
+
+    fn multi_get(&self, name: &ResolvedAddress, keys: &[&[u8]]) -> Vec<Option<Vec<u8>>> {
+        if self.changes.contains_key(name) {
+            keys.iter().map(|key| self.get(name, key)).collect()
+        } else {
+            self.snapshot.multi_get(name, keys)
         }
     }
 }
@@ -854,12 +1050,146 @@ impl Fork {
         self.patch
     }
 
+    /// Puts `new` value for the `key` into a map at the specified address, but only if
+    /// the current value in the fork is equal to `expected` (`None` means that the key
+    /// should be absent). Returns `true` if the value was put, and `false` otherwise.
+    ///
+    /// The map has `[u8]` keys and `Vec<u8>` values, that is, keys and values are
+    /// stored as-is. The check and the update are performed on the same view of the fork,
+    /// so no changes can be made to the map between them.
+    ///
+    /// # Panics
+    ///
+    /// - If the index at `address` exists, but is not a map.
+    /// - If the map is currently borrowed from the fork.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exonum_merkledb::{access::CopyAccessExt, Database, TemporaryDB};
+    /// let db = TemporaryDB::new();
+    /// let fork = db.fork();
+    /// assert!(fork.compare_and_set("map", b"key", None, b"first".to_vec()));
+    /// // The key is already present, so the value is not updated.
+    /// assert!(!fork.compare_and_set("map", b"key", None, b"second".to_vec()));
+    /// assert!(fork.compare_and_set("map", b"key", Some(&b"first"[..]), b"second".to_vec()));
+    ///
+    /// let map = fork.get_map::<_, [u8], Vec<u8>>("map");
+    /// assert_eq!(map.get(b"key"), Some(b"second".to_vec()));
+    /// ```
+    pub fn compare_and_set<I>(
+        &self,
+        address: I,
+        key: &[u8],
+        expected: Option<&[u8]>,
+        new: Vec<u8>,
+    ) -> bool
+    where
+        I: Into<IndexAddress>,
+    {
+        use crate::access::CopyAccessExt;
+
+        let mut map = self.get_map::<_, [u8], Vec<u8>>(address);
+        if map.get(key).as_deref() == expected {
+            map.put(key, new);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Puts `value` for the `key` into a map at the specified address and returns
+    /// the previous value visible in the fork, or `None` if the key was absent.
+    ///
+    /// The map has `[u8]` keys and `Vec<u8>` values, that is, keys and values are
+    /// stored as-is. Similar to [`compare_and_set`], the read and the update are performed
+    /// on the same view of the fork.
+    ///
+    /// [`compare_and_set`]: #method.compare_and_set
+    ///
+    /// # Panics
+    ///
+    /// - If the index at `address` exists, but is not a map.
+    /// - If the map is currently borrowed from the fork.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exonum_merkledb::{Database, TemporaryDB};
+    /// let db = TemporaryDB::new();
+    /// let fork = db.fork();
+    /// assert_eq!(fork.replace("map", b"key", b"first".to_vec()), None);
+    /// assert_eq!(
+    ///     fork.replace("map", b"key", b"second".to_vec()),
+    ///     Some(b"first".to_vec())
+    /// );
+    /// ```
+    pub fn replace<I>(&self, address: I, key: &[u8], value: Vec<u8>) -> Option<Vec<u8>>
+    where
+        I: Into<IndexAddress>,
+    {
+        use crate::access::CopyAccessExt;
+
+        let mut map = self.get_map::<_, [u8], Vec<u8>>(address);
+        let old_value = map.get(key);
+        map.put(key, value);
+        old_value
+    }
+
+    /// Removes keys in the half-open range `[from, to)` from a map at the specified address.
+    /// Keys are compared lexicographically as byte strings.
+    ///
+    /// The map has `[u8]` keys and `Vec<u8>` values, that is, keys and values are
+    /// stored as-is. The range is recorded in the fork as a whole rather than key by key,
+    /// and is applied by the database backend when the patch is merged (e.g., RocksDB
+    /// uses a range deletion).
+    ///
+    /// # Panics
+    ///
+    /// - If the index at `address` exists, but is not a map.
+    /// - If the map is currently borrowed from the fork.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exonum_merkledb::{access::CopyAccessExt, Database, TemporaryDB};
+    /// let db = TemporaryDB::new();
+    /// let fork = db.fork();
+    /// let mut map = fork.get_map::<_, [u8], Vec<u8>>("map");
+    /// for i in 0_u8..5 {
+    ///     map.put(&[i], vec![i]);
+    /// }
+    /// drop(map);
+    ///
+    /// fork.remove_range("map", &[1], &[3]);
+    /// let map = fork.get_map::<_, [u8], Vec<u8>>("map");
+    /// let keys: Vec<_> = map.keys().collect();
+    /// assert_eq!(keys, vec![vec![0], vec![3], vec![4]]);
+    /// ```
+    pub fn remove_range<I>(&self, address: I, from: &[u8], to: &[u8])
+    where
+        I: Into<IndexAddress>,
+    {
+        use crate::access::CopyAccessExt;
+
+        self.get_map::<_, [u8], Vec<u8>>(address)
+            .remove_range(from, to);
+    }
+
     /// Returns a readonly wrapper around the fork. Indexes created based on the readonly
     /// version cannot be modified; on the other hand, it is possible to have multiple
     /// copies of an index at the same time.
     pub fn readonly(&self) -> ReadonlyFork<'_> {
         ReadonlyFork(self)
     }
+
+    /// Returns the snapshot the fork is based on. Unlike [`readonly`], the snapshot
+    /// does not reflect any changes made in the fork, including the flushed ones.
+    ///
+    /// [`readonly`]: #method.readonly
+    pub fn base_snapshot(&self) -> &dyn Snapshot {
+        self.patch.snapshot.as_ref()
+    }
 }
 
 impl From<Patch> for Fork {
@@ -1051,6 +1381,10 @@ impl Snapshot for Box<dyn Snapshot> {
         self.as_ref().get(name, key)
     }
 
+    fn get_pinned(&self, name: &ResolvedAddress, key: &[u8]) -> Option<Cow<'_, [u8]>> {
+        self.as_ref().get_pinned(name, key)
+    }
+
     fn contains(&self, name: &ResolvedAddress, key: &[u8]) -> bool {
         self.as_ref().contains(name, key)
     }
@@ -1058,6 +1392,14 @@ impl Snapshot for Box<dyn Snapshot> {
     fn iter(&self, name: &ResolvedAddress, from: &[u8]) -> Iter<'_> {
         self.as_ref().iter(name, from)
     }
+
+    fn count_prefix(&self, name: &ResolvedAddress, prefix: &[u8]) -> usize {
+        self.as_ref().count_prefix(name, prefix)
+    }
+
+    fn multi_get(&self, name: &ResolvedAddress, keys: &[&[u8]]) -> Vec<Option<Vec<u8>>> {
+        self.as_ref().multi_get(name, keys)
+    }
 }
 
 impl<'a, T> ForkIter<'a, T>
@@ -1236,6 +1578,382 @@ mod tests {
 
     use std::{collections::HashSet, iter::FromIterator};
 
+    #[test]
+    fn compare_and_set() {
+        let db = TemporaryDB::new();
+        let fork = db.fork();
+
+        // Absent value with the absent expectation.
+        assert!(fork.compare_and_set("map", b"key", None, vec![1]));
+        // Absent value with a present expectation.
+        assert!(!fork.compare_and_set("map", b"other", Some(&[1][..]), vec![2]));
+        // Present value with the absent expectation.
+        assert!(!fork.compare_and_set("map", b"key", None, vec![3]));
+        // Present value with a mismatching expectation.
+        assert!(!fork.compare_and_set("map", b"key", Some(&[2][..]), vec![4]));
+        // Present value with the matching expectation.
+        assert!(fork.compare_and_set("map", b"key", Some(&[1][..]), vec![5]));
+
+        {
+            let map = fork.get_map::<_, [u8], Vec<u8>>("map");
+            assert_eq!(map.get(b"key"), Some(vec![5]));
+            assert_eq!(map.get(b"other"), None);
+        }
+
+        // Values merged into the database are checked as well.
+        db.merge(fork.into_patch()).unwrap();
+        let fork = db.fork();
+        assert!(!fork.compare_and_set("map", b"key", Some(&[1][..]), vec![6]));
+        assert!(fork.compare_and_set("map", b"key", Some(&[5][..]), vec![7]));
+        assert!(fork.compare_and_set("map", b"other", None, vec![8]));
+        db.merge(fork.into_patch()).unwrap();
+
+        let snapshot = db.snapshot();
+        let map = snapshot.get_map::<_, [u8], Vec<u8>>("map");
+        assert_eq!(map.get(b"key"), Some(vec![7]));
+        assert_eq!(map.get(b"other"), Some(vec![8]));
+    }
+
+    #[test]
+    fn replace() {
+        let db = TemporaryDB::new();
+        let fork = db.fork();
+        {
+            let mut map = fork.get_map::<_, [u8], Vec<u8>>("map");
+            map.put(b"existing", vec![1]);
+            map.put(b"removed", vec![2]);
+        }
+        db.merge(fork.into_patch()).unwrap();
+
+        let fork = db.fork();
+        fork.get_map::<_, [u8], Vec<u8>>("map").remove(b"removed");
+        // Existing key.
+        assert_eq!(fork.replace("map", b"existing", vec![3]), Some(vec![1]));
+        assert_eq!(fork.replace("map", b"existing", vec![4]), Some(vec![3]));
+        // New key.
+        assert_eq!(fork.replace("map", b"new", vec![5]), None);
+        // Key removed earlier in the same fork.
+        assert_eq!(fork.replace("map", b"removed", vec![6]), None);
+        db.merge(fork.into_patch()).unwrap();
+
+        let snapshot = db.snapshot();
+        let map = snapshot.get_map::<_, [u8], Vec<u8>>("map");
+        assert_eq!(map.get(b"existing"), Some(vec![4]));
+        assert_eq!(map.get(b"new"), Some(vec![5]));
+        assert_eq!(map.get(b"removed"), Some(vec![6]));
+    }
+
+    fn check_remove_range(db: &dyn Database) {
+        fn map_keys(fork: &Fork) -> Vec<Vec<u8>> {
+            fork.get_map::<_, [u8], Vec<u8>>("map").keys().collect()
+        }
+
+        let fork = db.fork();
+        {
+            let mut map = fork.get_map::<_, [u8], Vec<u8>>("map");
+            for key in &[&[1][..], &[2], &[2, 0], &[3], &[4]] {
+                map.put(key, key.to_vec());
+            }
+            // Another index must not be affected.
+            fork.get_map::<_, [u8], Vec<u8>>("other").put(&[2], vec![]);
+        }
+        db.merge(fork.into_patch()).unwrap();
+
+        let mut fork = db.fork();
+        // `from` is inclusive, and `to` is exclusive.
+        fork.remove_range("map", &[2], &[3]);
+        // An empty range.
+        fork.remove_range("map", &[4], &[4]);
+        assert_eq!(map_keys(&fork), vec![vec![1], vec![3], vec![4]]);
+        {
+            let map = fork.get_map::<_, [u8], Vec<u8>>("map");
+            assert_eq!(map.get(&[2, 0]), None);
+            assert!(!map.contains(&[2]));
+            assert_eq!(map.get(&[3]), Some(vec![3]));
+        }
+
+        // Keys put after the removal are retained.
+        fork.get_map::<_, [u8], Vec<u8>>("map").put(&[2, 1], vec![]);
+        assert_eq!(map_keys(&fork), vec![vec![1], vec![2, 1], vec![3], vec![4]]);
+        fork.flush();
+
+        // Ranges and keys put into the fork are removed as well.
+        fork.get_map::<_, [u8], Vec<u8>>("map").put(&[3, 1], vec![]);
+        fork.remove_range("map", &[0], &[3, 2]);
+        assert_eq!(map_keys(&fork), vec![vec![4]]);
+        fork.rollback();
+        assert_eq!(map_keys(&fork), vec![vec![1], vec![2, 1], vec![3], vec![4]]);
+
+        fork.remove_range("map", &[0], &[2, 0]);
+        let patch = fork.into_patch();
+        {
+            let map = patch.get_map::<_, [u8], Vec<u8>>("map");
+            let keys: Vec<_> = map.keys().collect();
+            assert_eq!(keys, vec![vec![2, 1], vec![3], vec![4]]);
+            assert_eq!(map.get(&[1]), None);
+        }
+        db.merge(patch).unwrap();
+
+        let snapshot = db.snapshot();
+        let map = snapshot.get_map::<_, [u8], Vec<u8>>("map");
+        let keys: Vec<_> = map.keys().collect();
+        assert_eq!(keys, vec![vec![2, 1], vec![3], vec![4]]);
+        let other = snapshot.get_map::<_, [u8], Vec<u8>>("other");
+        assert_eq!(other.get(&[2]), Some(vec![]));
+    }
+
+    #[test]
+    fn remove_range() {
+        check_remove_range(&TemporaryDB::new());
+    }
+
+    #[test]
+    fn remove_range_in_rocksdb() {
+        use crate::{DbOptions, RocksDB};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksDB::open(&dir, &DbOptions::default()).unwrap();
+        check_remove_range(&db);
+    }
+
+    fn check_prefix_counts(db: &dyn Database) {
+        let fork = db.fork();
+        {
+            let mut view = View::new(&fork, "foo");
+            for key in &[
+                &[1_u8, 0] as &[u8],
+                &[1, 1],
+                &[1, 1, 0],
+                &[1, 0xff],
+                &[2],
+                &[0xff],
+            ] {
+                view.put(&key.to_vec(), vec![0]);
+            }
+            let mut view = View::new(&fork, "bar");
+            view.put(&vec![1_u8, 0], vec![0]);
+        }
+
+        // Check counts for a patch with changes.
+        let patch = fork.into_patch();
+        let address: ResolvedAddress = "foo".into();
+        assert_eq!(patch.count_prefix(&address, &[]), 6);
+        assert_eq!(patch.count_prefix(&address, &[1]), 4);
+        assert_eq!(patch.count_prefix(&address, &[1, 1]), 2);
+        assert_eq!(patch.count_prefix(&address, &[0xff]), 1);
+        assert_eq!(patch.count_prefix(&address, &[3]), 0);
+        assert_eq!(patch.count_prefix(&"baz".into(), &[1]), 0);
+
+        // Check counts for the database snapshot.
+        db.merge(patch).unwrap();
+        let snapshot = db.snapshot();
+        assert_eq!(snapshot.count_prefix(&address, &[]), 6);
+        assert_eq!(snapshot.count_prefix(&address, &[1]), 4);
+        assert_eq!(snapshot.count_prefix(&address, &[1, 1]), 2);
+        assert_eq!(snapshot.count_prefix(&address, &[1, 0xff]), 1);
+        assert_eq!(snapshot.count_prefix(&address, &[0xff]), 1);
+        assert_eq!(snapshot.count_prefix(&address, &[3]), 0);
+        assert_eq!(snapshot.count_prefix(&"bar".into(), &[1]), 1);
+        assert_eq!(snapshot.count_prefix(&"baz".into(), &[1]), 0);
+
+        // Check counts for a patch which removes some keys.
+        let fork = db.fork();
+        {
+            let mut view = View::new(&fork, "foo");
+            view.remove(&vec![1_u8, 1]);
+            view.put(&vec![1_u8, 2], vec![0]);
+        }
+        let patch = fork.into_patch();
+        assert_eq!(patch.count_prefix(&address, &[1]), 4);
+        assert_eq!(patch.count_prefix(&address, &[1, 1]), 1);
+    }
+
+    #[test]
+    fn count_prefix_in_temporary_db() {
+        check_prefix_counts(&TemporaryDB::new());
+    }
+
+    #[test]
+    fn count_prefix_in_rocksdb() {
+        use crate::{DbOptions, RocksDB};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksDB::open(&dir, &DbOptions::default()).unwrap();
+        check_prefix_counts(&db);
+    }
+
+    fn check_multi_get(db: &dyn Database) {
+        let keyed_address = ResolvedAddress::new("qux", std::num::NonZeroU64::new(1));
+        let fork = db.fork();
+        {
+            let mut view = View::new(&fork, "foo");
+            view.put(&vec![1_u8], vec![1]);
+            view.put(&vec![3_u8], vec![3]);
+            view.put(&vec![5_u8], vec![]);
+            let mut view = View::new(&fork, keyed_address.clone());
+            view.put(&vec![2_u8], vec![2]);
+        }
+
+        let address: ResolvedAddress = "foo".into();
+        let keys: &[&[u8]] = &[&[5], &[2], &[1], &[4], &[3], &[1]];
+        let expected = vec![
+            Some(vec![]),
+            None,
+            Some(vec![1]),
+            None,
+            Some(vec![3]),
+            Some(vec![1]),
+        ];
+
+        // Check values for a patch with changes.
+        let patch = fork.into_patch();
+        assert_eq!(patch.multi_get(&address, keys), expected);
+        assert_eq!(
+            patch.multi_get(&keyed_address, &[&[1], &[2]]),
+            vec![None, Some(vec![2])]
+        );
+        assert_eq!(patch.multi_get(&"baz".into(), keys), vec![None; keys.len()]);
+        assert!(patch.multi_get(&address, &[]).is_empty());
+
+        // Check values for the database snapshot.
+        db.merge(patch).unwrap();
+        let snapshot = db.snapshot();
+        assert_eq!(snapshot.multi_get(&address, keys), expected);
+        assert_eq!(
+            snapshot.multi_get(&keyed_address, &[&[1], &[2]]),
+            vec![None, Some(vec![2])]
+        );
+        assert_eq!(
+            snapshot.multi_get(&"baz".into(), keys),
+            vec![None; keys.len()]
+        );
+        assert!(snapshot.multi_get(&address, &[]).is_empty());
+
+        // Check values for a patch which removes and adds some keys.
+        let fork = db.fork();
+        {
+            let mut view = View::new(&fork, "foo");
+            view.remove(&vec![1_u8]);
+            view.put(&vec![2_u8], vec![2]);
+        }
+        let patch = fork.into_patch();
+        assert_eq!(
+            patch.multi_get(&address, &[&[1], &[2], &[3]]),
+            vec![None, Some(vec![2]), Some(vec![3])]
+        );
+    }
+
+    #[test]
+    fn get_pinned_borrows_values_in_temporary_db() {
+        use std::borrow::Cow;
+
+        let db = TemporaryDB::new();
+        let fork = db.fork();
+        {
+            let mut view = View::new(&fork, "foo");
+            view.put(&vec![1_u8], vec![1, 2, 3]);
+            view.put(&vec![2_u8], vec![4]);
+        }
+        db.merge(fork.into_patch()).unwrap();
+
+        let address: ResolvedAddress = "foo".into();
+        let snapshot = db.snapshot();
+        for key in &[&[1_u8][..], &[2], &[3]] {
+            let pinned = snapshot.get_pinned(&address, key);
+            assert_eq!(pinned.as_deref(), snapshot.get(&address, key).as_deref());
+            assert!(!matches!(pinned, Some(Cow::Owned(_))));
+        }
+        assert_eq!(snapshot.get_pinned(&"bar".into(), &[1]), None);
+
+        // Values changed in a patch are borrowed from the patch.
+        let fork = db.fork();
+        {
+            let mut view = View::new(&fork, "foo");
+            view.remove(&vec![1_u8]);
+            view.put(&vec![3_u8], vec![5]);
+        }
+        let patch = fork.into_patch();
+        assert_eq!(patch.get_pinned(&address, &[1]), None);
+        assert_eq!(
+            patch.get_pinned(&address, &[2]).as_deref(),
+            Some(&[4_u8][..])
+        );
+        let pinned = patch.get_pinned(&address, &[3]);
+        assert_eq!(pinned.as_deref(), Some(&[5_u8][..]));
+        assert!(matches!(pinned, Some(Cow::Borrowed(_))));
+    }
+
+    #[test]
+    fn multi_get_in_temporary_db() {
+        check_multi_get(&TemporaryDB::new());
+    }
+
+    #[test]
+    fn multi_get_in_rocksdb() {
+        use crate::{DbOptions, RocksDB};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksDB::open(&dir, &DbOptions::default()).unwrap();
+        check_multi_get(&db);
+    }
+
+    fn check_flush(db: &dyn Database) {
+        let fork = db.fork();
+        fork.get_list("list").extend(vec![1_u32, 2, 3]);
+        fork.get_entry("entry").set("foo".to_owned());
+        db.merge(fork.into_patch()).unwrap();
+        let state_hash = SystemSchema::new(&db.snapshot()).state_hash();
+
+        db.flush().unwrap();
+        let snapshot = db.snapshot();
+        let list = snapshot.get_list::<_, u32>("list");
+        assert_eq!(list.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(
+            snapshot.get_entry::<_, String>("entry").get(),
+            Some("foo".to_owned())
+        );
+        assert_eq!(SystemSchema::new(&snapshot).state_hash(), state_hash);
+
+        // The database remains writable after flushing.
+        let fork = db.fork();
+        fork.get_list("list").push(4_u32);
+        db.merge(fork.into_patch()).unwrap();
+        db.flush().unwrap();
+        assert_eq!(db.snapshot().get_list::<_, u32>("list").len(), 4);
+    }
+
+    #[test]
+    fn flush_temporary_db() {
+        check_flush(&TemporaryDB::new());
+    }
+
+    #[test]
+    fn flush_rocksdb() {
+        use crate::{DbOptions, RocksDB};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = RocksDB::open(&dir, &DbOptions::default()).unwrap();
+        check_flush(&db);
+    }
+
+    #[test]
+    fn base_snapshot_does_not_reflect_fork_changes() {
+        let db = TemporaryDB::new();
+        let fork = db.fork();
+        fork.get_entry("entry").set(1_u64);
+        db.merge(fork.into_patch()).unwrap();
+
+        let mut fork = db.fork();
+        fork.get_entry("entry").set(2_u64);
+        fork.flush();
+        fork.get_entry("entry").set(3_u64);
+        assert_eq!(
+            fork.base_snapshot().get_entry::<_, u64>("entry").get(),
+            Some(1)
+        );
+        assert_eq!(fork.readonly().get_entry::<_, u64>("entry").get(), Some(3));
+    }
+
     #[test]
     fn readonly_indexes_are_timely_dropped() {
         let db = TemporaryDB::new();
@@ -0,0 +1,261 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Comparison of database snapshots.
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+    iter,
+};
+
+use crate::{
+    views::{IndexAddress, IndexesPool, ResolvedAddress},
+    Snapshot,
+};
+
+/// Difference in a single key of an index between two snapshots.
+///
+/// Keys and values are represented as raw bytes, i.e., in the form they are stored
+/// in the index.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyDiff {
+    /// The key is present only in the second snapshot.
+    Added {
+        /// Address of the index.
+        index: IndexAddress,
+        /// Key in the index.
+        key: Vec<u8>,
+        /// Value in the second snapshot.
+        value: Vec<u8>,
+    },
+    /// The key is present only in the first snapshot.
+    Removed {
+        /// Address of the index.
+        index: IndexAddress,
+        /// Key in the index.
+        key: Vec<u8>,
+        /// Value in the first snapshot.
+        value: Vec<u8>,
+    },
+    /// The key is present in both snapshots, but with different values.
+    Changed {
+        /// Address of the index.
+        index: IndexAddress,
+        /// Key in the index.
+        key: Vec<u8>,
+        /// Value in the first snapshot.
+        old: Vec<u8>,
+        /// Value in the second snapshot.
+        new: Vec<u8>,
+    },
+}
+
+/// Compares contents of indexes in two snapshots. The snapshots may belong to different
+/// databases.
+///
+/// Indexes are matched by their addresses. An index missing in one of the snapshots
+/// is treated as empty. Differences are ordered by the fully qualified index name and then
+/// by the key. System views (e.g., index metadata or the state aggregator) are not compared.
+///
+/// This function reads all entries of both snapshots, so it is intended for debugging
+/// and testing rather than for production use.
+///
+/// # Examples
+///
+/// ```
+/// # use exonum_merkledb::{access::CopyAccessExt, diff, Database, KeyDiff, TemporaryDB};
+/// let first_db = TemporaryDB::new();
+/// let fork = first_db.fork();
+/// fork.get_entry("entry").set(1_u8);
+/// first_db.merge(fork.into_patch()).unwrap();
+///
+/// let second_db = TemporaryDB::new();
+/// let fork = second_db.fork();
+/// fork.get_entry("entry").set(2_u8);
+/// second_db.merge(fork.into_patch()).unwrap();
+///
+/// let changes = diff(&first_db.snapshot(), &second_db.snapshot());
+/// assert_eq!(changes.len(), 1);
+/// assert!(matches!(
+///     &changes[0],
+///     KeyDiff::Changed { old, new, .. } if *old == [1] && *new == [2]
+/// ));
+/// ```
+pub fn diff(a: &dyn Snapshot, b: &dyn Snapshot) -> Vec<KeyDiff> {
+    let old_indexes = resolve_indexes(a);
+    let new_indexes = resolve_indexes(b);
+    let full_names: BTreeSet<_> = old_indexes.keys().chain(new_indexes.keys()).collect();
+
+    let mut diffs = vec![];
+    for full_name in full_names {
+        let (index, old_address) = match old_indexes.get(full_name) {
+            Some((index, address)) => (index, Some(address)),
+            None => (&new_indexes[full_name].0, None),
+        };
+        let new_address = new_indexes.get(full_name).map(|(_, address)| address);
+        diff_index(
+            index,
+            entries(a, old_address),
+            entries(b, new_address),
+            &mut diffs,
+        );
+    }
+    diffs
+}
+
+/// Returns addresses of all indexes in the snapshot keyed by the fully qualified name.
+fn resolve_indexes(snapshot: &dyn Snapshot) -> BTreeMap<Vec<u8>, (IndexAddress, ResolvedAddress)> {
+    IndexesPool::new(snapshot)
+        .indexes()
+        .map(|(index, address)| (index.fully_qualified_name(), (index, address)))
+        .collect()
+}
+
+/// Returns an iterator over entries of the view with the specified address. If the address
+/// is not specified, the iterator is empty.
+fn entries<'a>(
+    snapshot: &'a dyn Snapshot,
+    address: Option<&ResolvedAddress>,
+) -> impl iter::Iterator<Item = (Vec<u8>, Vec<u8>)> + 'a {
+    let mut iter = address.map(|address| snapshot.iter(address, &[]));
+    iter::from_fn(move || {
+        let (key, value) = iter.as_mut()?.next()?;
+        Some((key.to_vec(), value.to_vec()))
+    })
+}
+
+/// Merge-walks entries of an index in two snapshots, which are ordered by keys.
+fn diff_index(
+    index: &IndexAddress,
+    old_entries: impl iter::Iterator<Item = (Vec<u8>, Vec<u8>)>,
+    new_entries: impl iter::Iterator<Item = (Vec<u8>, Vec<u8>)>,
+    diffs: &mut Vec<KeyDiff>,
+) {
+    let mut old_entries = old_entries.peekable();
+    let mut new_entries = new_entries.peekable();
+    loop {
+        let ordering = match (old_entries.peek(), new_entries.peek()) {
+            (None, None) => break,
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some((old_key, _)), Some((new_key, _))) => old_key.cmp(new_key),
+        };
+
+        match ordering {
+            Ordering::Less => {
+                let (key, value) = old_entries.next().unwrap();
+                diffs.push(KeyDiff::Removed {
+                    index: index.clone(),
+                    key,
+                    value,
+                });
+            }
+            Ordering::Greater => {
+                let (key, value) = new_entries.next().unwrap();
+                diffs.push(KeyDiff::Added {
+                    index: index.clone(),
+                    key,
+                    value,
+                });
+            }
+            Ordering::Equal => {
+                let (key, old) = old_entries.next().unwrap();
+                let (_, new) = new_entries.next().unwrap();
+                if old != new {
+                    diffs.push(KeyDiff::Changed {
+                        index: index.clone(),
+                        key,
+                        old,
+                        new,
+                    });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, KeyDiff};
+    use crate::{access::CopyAccessExt, BinaryValue, Database, IndexAddress, TemporaryDB};
+
+    #[test]
+    fn diff_of_snapshots() {
+        let first_db = TemporaryDB::new();
+        let fork = first_db.fork();
+        {
+            let mut map = fork.get_map("map");
+            map.put(&1_u8, "a".to_owned());
+            map.put(&2_u8, "b".to_owned());
+            map.put(&3_u8, "c".to_owned());
+            fork.get_entry("removed").set(1_u64);
+            fork.get_entry("unchanged").set(2_u64);
+        }
+        first_db.merge(fork.into_patch()).unwrap();
+
+        let second_db = TemporaryDB::new();
+        let fork = second_db.fork();
+        {
+            // Indexes are created in a different order, so they have different identifiers
+            // in the two databases.
+            fork.get_list(("added", &1_u8)).push(5_u32);
+            fork.get_entry("unchanged").set(2_u64);
+            let mut map = fork.get_map("map");
+            map.put(&2_u8, "b".to_owned());
+            map.put(&3_u8, "x".to_owned());
+            map.put(&4_u8, "d".to_owned());
+        }
+        second_db.merge(fork.into_patch()).unwrap();
+
+        let first_snapshot = first_db.snapshot();
+        let second_snapshot = second_db.snapshot();
+        let changes = diff(&first_snapshot, &second_snapshot);
+        let map_address = IndexAddress::from_root("map");
+        assert_eq!(
+            changes,
+            vec![
+                KeyDiff::Added {
+                    index: IndexAddress::from_root("added").append_key(&1_u8),
+                    // List indexes are keyed by big-endian `u64`.
+                    key: 0_u64.to_be_bytes().to_vec(),
+                    value: 5_u32.to_bytes(),
+                },
+                KeyDiff::Removed {
+                    index: map_address.clone(),
+                    key: vec![1],
+                    value: b"a".to_vec(),
+                },
+                KeyDiff::Changed {
+                    index: map_address.clone(),
+                    key: vec![3],
+                    old: b"c".to_vec(),
+                    new: b"x".to_vec(),
+                },
+                KeyDiff::Added {
+                    index: map_address,
+                    key: vec![4],
+                    value: b"d".to_vec(),
+                },
+                KeyDiff::Removed {
+                    index: IndexAddress::from_root("removed"),
+                    key: vec![],
+                    value: 1_u64.to_bytes(),
+                },
+            ]
+        );
+
+        assert!(diff(&first_snapshot, &first_db.snapshot()).is_empty());
+    }
+}
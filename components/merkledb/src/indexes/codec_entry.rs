@@ -0,0 +1,225 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An entry index with the value encoding selected by the index rather than by the value type.
+
+use anyhow::Error;
+#[cfg(feature = "with-protobuf")]
+use exonum_proto::ProtobufConvert;
+#[cfg(feature = "with-bincode")]
+use serde::{de::DeserializeOwned, Serialize};
+
+use std::marker::PhantomData;
+
+use crate::{
+    access::{Access, AccessError, FromAccess},
+    indexes::Entry,
+    views::{IndexAddress, RawAccess, RawAccessMut},
+};
+
+/// Serialization format of values in a [`CodecEntry`].
+///
+/// [`CodecEntry`]: struct.CodecEntry.html
+pub trait Codec<V> {
+    /// Serializes the value.
+    fn encode(value: &V) -> Vec<u8>;
+    /// Deserializes the value.
+    fn decode(bytes: &[u8]) -> Result<V, Error>;
+}
+
+/// Codec serializing values with `bincode`.
+#[cfg(feature = "with-bincode")]
+#[derive(Debug, Clone, Copy)]
+pub struct BincodeCodec;
+
+#[cfg(feature = "with-bincode")]
+impl<V> Codec<V> for BincodeCodec
+where
+    V: Serialize + DeserializeOwned,
+{
+    fn encode(value: &V) -> Vec<u8> {
+        bincode::serialize(value).expect("Failed to serialize value with bincode")
+    }
+
+    fn decode(bytes: &[u8]) -> Result<V, Error> {
+        bincode::deserialize(bytes).map_err(From::from)
+    }
+}
+
+/// Codec serializing values as Protobuf messages.
+#[cfg(feature = "with-protobuf")]
+#[derive(Debug, Clone, Copy)]
+pub struct ProtobufCodec;
+
+#[cfg(feature = "with-protobuf")]
+impl<V> Codec<V> for ProtobufCodec
+where
+    V: ProtobufConvert,
+    V::ProtoStruct: protobuf::Message,
+{
+    fn encode(value: &V) -> Vec<u8> {
+        protobuf::Message::write_to_bytes(&value.to_pb())
+            .expect("Failed to serialize value to Protobuf")
+    }
+
+    fn decode(bytes: &[u8]) -> Result<V, Error> {
+        let pb = protobuf::Message::parse_from_bytes(bytes)?;
+        V::from_pb(pb)
+    }
+}
+
+/// An index that may only contain one element, which is serialized with the codec `C`.
+///
+/// Unlike [`Entry`], which relies on the [`BinaryValue`] implementation of the value type,
+/// `CodecEntry` allows selecting the serialization format per index. This is useful,
+/// for example, if the stored value should be readable by external tools expecting
+/// a certain format. In the storage, `CodecEntry` is indistinguishable from an `Entry`
+/// with the encoded bytes.
+///
+/// [`Entry`]: struct.Entry.html
+/// [`BinaryValue`]: ../../trait.BinaryValue.html
+///
+/// # Examples
+///
+/// ```
+/// # use exonum_merkledb::{
+/// #     access::FromAccess, indexes::codec_entry::{Codec, CodecEntry},
+/// #     Database, TemporaryDB,
+/// # };
+/// /// Codec storing strings as raw UTF-8 bytes.
+/// struct Utf8Codec;
+///
+/// impl Codec<String> for Utf8Codec {
+///     fn encode(value: &String) -> Vec<u8> {
+///         value.as_bytes().to_vec()
+///     }
+///
+///     fn decode(bytes: &[u8]) -> Result<String, anyhow::Error> {
+///         String::from_utf8(bytes.to_vec()).map_err(From::from)
+///     }
+/// }
+///
+/// let db = TemporaryDB::new();
+/// let fork = db.fork();
+/// let mut entry: CodecEntry<_, String, Utf8Codec> =
+///     CodecEntry::from_access(&fork, "entry".into()).unwrap();
+/// entry.set("foo".to_owned());
+/// assert_eq!(entry.get(), Some("foo".to_owned()));
+/// ```
+#[derive(Debug)]
+pub struct CodecEntry<T: RawAccess, V, C> {
+    inner: Entry<T, Vec<u8>>,
+    _v: PhantomData<(V, C)>,
+}
+
+impl<T, V, C> FromAccess<T> for CodecEntry<T::Base, V, C>
+where
+    T: Access,
+    C: Codec<V>,
+{
+    fn from_access(access: T, addr: IndexAddress) -> Result<Self, AccessError> {
+        Entry::from_access(access, addr).map(|inner| Self {
+            inner,
+            _v: PhantomData,
+        })
+    }
+}
+
+impl<T, V, C> CodecEntry<T, V, C>
+where
+    T: RawAccess,
+    C: Codec<V>,
+{
+    /// Returns a value of the entry or `None` if does not exist.
+    ///
+    /// # Panics
+    ///
+    /// If the stored value cannot be decoded with the codec.
+    pub fn get(&self) -> Option<V> {
+        self.inner
+            .get()
+            .map(|bytes| C::decode(&bytes).expect("Error while deserializing value"))
+    }
+
+    /// Returns `true` if a value of the entry exists.
+    pub fn exists(&self) -> bool {
+        self.inner.exists()
+    }
+}
+
+impl<T, V, C> CodecEntry<T, V, C>
+where
+    T: RawAccessMut,
+    C: Codec<V>,
+{
+    /// Changes a value of the entry.
+    pub fn set(&mut self, value: V) {
+        self.inner.set(C::encode(&value));
+    }
+
+    /// Removes a value of the entry.
+    pub fn remove(&mut self) {
+        self.inner.remove();
+    }
+}
+
+#[cfg(all(test, feature = "with-bincode"))]
+mod tests {
+    use serde_derive::{Deserialize, Serialize};
+
+    use super::{BincodeCodec, CodecEntry};
+    use crate::{
+        access::{CopyAccessExt, FromAccess},
+        Database, TemporaryDB,
+    };
+
+    #[test]
+    fn bincode_codec() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Record {
+            id: u32,
+            name: String,
+        }
+
+        let db = TemporaryDB::new();
+        let fork = db.fork();
+        let record = Record {
+            id: 1,
+            name: "a".to_owned(),
+        };
+        {
+            let mut entry: CodecEntry<_, Record, BincodeCodec> =
+                CodecEntry::from_access(&fork, "entry".into()).unwrap();
+            entry.set(record);
+        }
+        db.merge(fork.into_patch()).unwrap();
+
+        let snapshot = db.snapshot();
+        let entry: CodecEntry<_, Record, BincodeCodec> =
+            CodecEntry::from_access(&snapshot, "entry".into()).unwrap();
+        assert_eq!(
+            entry.get(),
+            Some(Record {
+                id: 1,
+                name: "a".to_owned(),
+            })
+        );
+        // `u32` is encoded in 4 bytes, the string length in 8 bytes (both little-endian).
+        let raw_bytes = snapshot.get_entry::<_, Vec<u8>>("entry").get();
+        assert_eq!(
+            raw_bytes,
+            Some(vec![1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, b'a'])
+        );
+    }
+}
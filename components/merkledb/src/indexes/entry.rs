@@ -95,6 +95,28 @@ where
     pub fn exists(&self) -> bool {
         self.base.contains(&())
     }
+
+    /// Returns a value of the entry or the default value if the entry is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exonum_merkledb::{access::CopyAccessExt, TemporaryDB, Database, Entry};
+    ///
+    /// let db = TemporaryDB::new();
+    /// let fork = db.fork();
+    /// let mut index = fork.get_entry::<_, u64>("name");
+    /// assert_eq!(0, index.get_or_default());
+    ///
+    /// index.set(10);
+    /// assert_eq!(10, index.get_or_default());
+    /// ```
+    pub fn get_or_default(&self) -> V
+    where
+        V: Default,
+    {
+        self.get().unwrap_or_default()
+    }
 }
 
 impl<T, V> Entry<T, V>
@@ -190,4 +212,31 @@ where
         self.set(value);
         previous
     }
+
+    /// Modifies the value in the entry with the provided closure and saves the result.
+    /// If the entry is empty, the closure is applied to the default value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exonum_merkledb::{access::CopyAccessExt, TemporaryDB, Database, Entry};
+    ///
+    /// let db = TemporaryDB::new();
+    /// let fork = db.fork();
+    /// let mut index = fork.get_entry::<_, u64>("name");
+    ///
+    /// index.modify(|value| *value += 5);
+    /// assert_eq!(Some(5), index.get());
+    /// index.modify(|value| *value *= 2);
+    /// assert_eq!(Some(10), index.get());
+    /// ```
+    pub fn modify<F>(&mut self, f: F)
+    where
+        V: Default,
+        F: FnOnce(&mut V),
+    {
+        let mut value = self.get_or_default();
+        f(&mut value);
+        self.set(value);
+    }
 }
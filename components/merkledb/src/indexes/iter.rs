@@ -15,8 +15,8 @@
 //! Generic iterator types used by all indexes.
 
 use crate::{
-    views::{Iter, RawAccess, View},
-    BinaryKey, BinaryValue,
+    views::{IndexAddress, Iter, RawAccess, View},
+    BinaryKey, BinaryValue, Snapshot,
 };
 
 /// Iterator over key-value pairs of an index.
@@ -94,6 +94,49 @@ where
     }
 }
 
+/// Returns an iterator over entries of the index at `address` with keys starting with `prefix`.
+/// The prefix is removed from the keys, after which keys and values are decoded as `K`
+/// and `V` respectively. The iterator stops as soon as keys leave the prefix. If the index
+/// does not exist, the iterator is empty.
+///
+/// Unlike iterators of indexes, this function does not check the index type, so it can be
+/// used to read indexes with a custom key layout, e.g., a map with composite keys.
+///
+/// # Panics
+///
+/// The iterator panics if a key or a value cannot be decoded.
+///
+/// # Examples
+///
+/// ```
+/// # use exonum_merkledb::{access::CopyAccessExt, typed_iter, Database, TemporaryDB};
+/// let db = TemporaryDB::new();
+/// let fork = db.fork();
+/// let mut map = fork.get_map::<_, [u8], String>("map");
+/// map.put(&[1, 0, 1], "foo".to_owned());
+/// map.put(&[1, 0, 2], "bar".to_owned());
+/// map.put(&[2, 0, 1], "baz".to_owned());
+/// drop(map);
+/// db.merge(fork.into_patch()).unwrap();
+///
+/// let snapshot = db.snapshot();
+/// let entries: Vec<(u16, String)> = typed_iter::<u16, _>(&*snapshot, "map", &[1]).collect();
+/// assert_eq!(entries, vec![(1, "foo".to_owned()), (2, "bar".to_owned())]);
+/// ```
+pub fn typed_iter<'a, K, V>(
+    snapshot: &'a dyn Snapshot,
+    address: impl Into<IndexAddress>,
+    prefix: &[u8],
+) -> Entries<'a, K, V>
+where
+    K: BinaryKey + ?Sized,
+    V: BinaryValue,
+{
+    Entries {
+        base_iter: Iter::from_snapshot(snapshot, &address.into(), prefix),
+    }
+}
+
 /// Iterator over keys of an index.
 ///
 /// This structure is returned by [`Entries::skip_values`] , and by inherent methods
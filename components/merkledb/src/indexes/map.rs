@@ -299,6 +299,17 @@ where
     }
 }
 
+impl<T, V> MapIndex<T, [u8], V>
+where
+    T: RawAccessMut,
+    V: BinaryValue,
+{
+    /// Removes keys in the half-open range `[from, to)` without enumerating them.
+    pub(crate) fn remove_range(&mut self, from: &[u8], to: &[u8]) {
+        self.base.remove_range(from, to);
+    }
+}
+
 impl<'a, T, K, V> IntoIterator for &'a MapIndex<T, K, V>
 where
     T: RawAccess,
@@ -15,9 +15,10 @@
 //! All available `MerkleDB` indexes.
 
 pub use self::{
+    codec_entry::CodecEntry,
     entry::Entry,
     group::Group,
-    iter::{Entries, IndexIterator, Keys, Values},
+    iter::{typed_iter, Entries, IndexIterator, Keys, Values},
     key_set::KeySetIndex,
     list::ListIndex,
     map::MapIndex,
@@ -26,6 +27,7 @@ pub use self::{
     value_set::ValueSetIndex,
 };
 
+pub mod codec_entry;
 mod entry;
 mod group;
 mod iter;
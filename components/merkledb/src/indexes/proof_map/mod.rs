@@ -22,10 +22,10 @@ pub use self::{
 
 use exonum_crypto::Hash;
 
-use std::{fmt, io, marker::PhantomData};
+use std::{borrow::Borrow, fmt, io, marker::PhantomData};
 
 use self::{
-    key::{ChildKind, VALUE_KEY_PREFIX},
+    key::{ChildKind, LEAF_KEY_PREFIX, VALUE_KEY_PREFIX},
     node::{BranchNode, Node},
     proof_builder::{BuildProof, MerklePatriciaTree},
 };
@@ -202,6 +202,52 @@ where
         }
     }
 
+    /// Recomputes the Merkle root from the hashes stored in the leaves of the tree,
+    /// disregarding child hashes stored in the branch nodes.
+    pub(crate) fn recompute_merkle_root(&self) -> Hash {
+        match self.get_root_node() {
+            Some((path, Node::Leaf(hash))) => HashTag::hash_single_entry_map(&path, &hash),
+            Some((_, Node::Branch(branch))) => self.recompute_branch_hash(branch),
+            None => Hash::zero(),
+        }
+    }
+
+    fn recompute_branch_hash(&self, mut branch: BranchNode) -> Hash {
+        for &kind in &[ChildKind::Left, ChildKind::Right] {
+            let child_hash = match self.get_node_unchecked(&branch.child_path(kind)) {
+                Node::Leaf(hash) => hash,
+                Node::Branch(child) => self.recompute_branch_hash(child),
+            };
+            branch.set_child_hash(kind, &child_hash);
+        }
+        branch.object_hash()
+    }
+
+    /// Returns the number of leaves in the tree and the number of values in the map.
+    pub(crate) fn leaf_and_value_counts(&self) -> (usize, usize) {
+        let leaf_count = self.base.iter::<_, Vec<u8>, Hash>(&LEAF_KEY_PREFIX).count();
+        let value_count = self
+            .base
+            .iter::<_, Vec<u8>, Vec<u8>>(&VALUE_KEY_PREFIX)
+            .count();
+        (leaf_count, value_count)
+    }
+
+    /// Returns the keys for which the tree has no leaf or the leaf hash does not correspond
+    /// to the value of the key. The stored value bytes are hashed, so that the check does not
+    /// depend on the value serialization being canonical.
+    pub(crate) fn mismatched_keys(&self) -> Vec<K::Owned> {
+        let entries =
+            Entries::<K, Vec<u8>>::with_detached_prefix(&self.base, &VALUE_KEY_PREFIX, None);
+        entries
+            .filter(|(key, value)| {
+                let path = KeyMode::transform_key(key.borrow());
+                self.base.get::<_, Hash>(&path) != Some(HashTag::hash_leaf(value))
+            })
+            .map(|(key, _)| key)
+            .collect()
+    }
+
     /// Returns a value corresponding to the key.
     ///
     /// # Examples
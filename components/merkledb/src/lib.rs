@@ -173,16 +173,23 @@ pub mod _reexports {
 }
 
 pub use self::{
-    backends::{rocksdb::RocksDB, temporarydb::TemporaryDB},
+    backends::{
+        rocksdb::RocksDB,
+        temporarydb::{TemporaryDB, TemporaryDBIterator, VersionedSnapshot},
+    },
+    cached::CachedSnapshot,
+    consistency::{verify_consistency, verify_map_consistency, Inconsistency},
     db::{
         Database, DatabaseExt, Fork, Iter, Iterator, OwnedReadonlyFork, Patch, ReadonlyFork,
         Snapshot,
     },
+    diff::{diff, KeyDiff},
     error::Error,
     hash::{root_hash, HashTag, ObjectHash, ValidationError},
     keys::BinaryKey,
     lazy::Lazy,
     options::DbOptions,
+    overlay::overlay_iter,
     values::BinaryValue,
     views::{AsReadonly, IndexAddress, IndexType, ResolvedAddress, SystemSchema},
 };
@@ -192,14 +199,18 @@ pub use self::{
 pub use self::indexes::{
     proof_list::{self, ListProof, ProofListIndex},
     proof_map::{self, MapProof, ProofMapIndex, RawProofMapIndex},
-    Entry, Group, KeySetIndex, ListIndex, MapIndex, ProofEntry, SparseListIndex, ValueSetIndex,
+    typed_iter, CodecEntry, Entry, Group, KeySetIndex, ListIndex, MapIndex, ProofEntry,
+    SparseListIndex, ValueSetIndex,
 };
 
 #[macro_use]
 mod macros;
 pub mod access;
 mod backends;
+mod cached;
+mod consistency;
 mod db;
+mod diff;
 mod error;
 pub mod generic;
 mod hash;
@@ -208,6 +219,7 @@ mod keys;
 mod lazy;
 pub mod migration;
 mod options;
+mod overlay;
 pub mod validation;
 mod values;
 mod views;
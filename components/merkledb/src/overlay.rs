@@ -0,0 +1,198 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Iteration over a snapshot overlaid with another snapshot.
+
+use std::{cmp::Ordering, collections::HashSet};
+
+use crate::{Iter, Iterator, ResolvedAddress, Snapshot};
+
+/// Returns an iterator over the entries of the view at `address` in the `base` snapshot
+/// overlaid with the `overlay` snapshot, starting from the key `from`.
+///
+/// If a key is present in both snapshots, the value from `overlay` is yielded. Keys from
+/// `deleted` are hidden in `base`, but not in `overlay`, i.e., a key which is both deleted
+/// and present in `overlay` is yielded with the overlay value. Entries are yielded
+/// in the ascending order of keys.
+///
+/// This emulates the iteration over a `Fork`, in which the uncommitted changes are laid
+/// on top of the committed state. The function is intended for tooling and testing,
+/// e.g., to simulate pending changes stored in a separate database.
+///
+/// # Examples
+///
+/// ```
+/// # use exonum_merkledb::{overlay_iter, ResolvedAddress, Snapshot};
+/// # use std::collections::HashSet;
+/// # fn iterate(base: &dyn Snapshot, overlay: &dyn Snapshot, address: &ResolvedAddress) {
+/// let mut deleted = HashSet::new();
+/// deleted.insert(b"removed".to_vec());
+/// let mut iter = overlay_iter(base, overlay, &deleted, address, &[]);
+/// while let Some((key, _value)) = iter.next() {
+///     assert_ne!(key, b"removed");
+///     // Process the entry...
+/// }
+/// # }
+/// ```
+pub fn overlay_iter<'a>(
+    base: &'a dyn Snapshot,
+    overlay: &'a dyn Snapshot,
+    deleted: &'a HashSet<Vec<u8>>,
+    address: &ResolvedAddress,
+    from: &[u8],
+) -> Iter<'a> {
+    Box::new(OverlayIter {
+        base: base.iter(address, from),
+        overlay: overlay.iter(address, from),
+        deleted,
+    })
+}
+
+struct OverlayIter<'a> {
+    base: Iter<'a>,
+    overlay: Iter<'a>,
+    deleted: &'a HashSet<Vec<u8>>,
+}
+
+/// Source of the next entry yielded by `OverlayIter`.
+#[derive(Debug, PartialEq, Eq)]
+enum NextEntry {
+    Base,
+    Overlay,
+    Replaced,
+    Finished,
+}
+
+impl OverlayIter<'_> {
+    fn step(&mut self) -> NextEntry {
+        // Skip deleted keys in the base snapshot.
+        while let Some((key, _)) = self.base.peek() {
+            if !self.deleted.contains(key) {
+                break;
+            }
+            self.base.next();
+        }
+
+        match (self.base.peek(), self.overlay.peek()) {
+            (None, None) => NextEntry::Finished,
+            (Some(_), None) => NextEntry::Base,
+            (None, Some(_)) => NextEntry::Overlay,
+            (Some((base_key, _)), Some((overlay_key, _))) => match base_key.cmp(overlay_key) {
+                Ordering::Less => NextEntry::Base,
+                Ordering::Greater => NextEntry::Overlay,
+                Ordering::Equal => NextEntry::Replaced,
+            },
+        }
+    }
+}
+
+impl Iterator for OverlayIter<'_> {
+    fn next(&mut self) -> Option<(&[u8], &[u8])> {
+        match self.step() {
+            NextEntry::Base => self.base.next(),
+            NextEntry::Overlay => self.overlay.next(),
+            NextEntry::Replaced => {
+                self.base.next();
+                self.overlay.next()
+            }
+            NextEntry::Finished => None,
+        }
+    }
+
+    fn peek(&mut self) -> Option<(&[u8], &[u8])> {
+        match self.step() {
+            NextEntry::Base => self.base.peek(),
+            NextEntry::Overlay | NextEntry::Replaced => self.overlay.peek(),
+            NextEntry::Finished => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::overlay_iter;
+    use crate::{
+        access::CopyAccessExt, views::IndexesPool, Database, ResolvedAddress, Snapshot, TemporaryDB,
+    };
+
+    fn map_address(snapshot: &dyn Snapshot) -> ResolvedAddress {
+        IndexesPool::new(snapshot)
+            .indexes()
+            .find_map(|(index, address)| {
+                if index.name() == "map" {
+                    Some(address)
+                } else {
+                    None
+                }
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn overlay_precedence() {
+        let base_db = TemporaryDB::new();
+        let fork = base_db.fork();
+        {
+            let mut map = fork.get_map("map");
+            map.put(&1_u8, "a".to_owned());
+            map.put(&2_u8, "b".to_owned());
+            map.put(&3_u8, "c".to_owned());
+            map.put(&5_u8, "e".to_owned());
+        }
+        base_db.merge(fork.into_patch()).unwrap();
+
+        let overlay_db = TemporaryDB::new();
+        let fork = overlay_db.fork();
+        {
+            let mut map = fork.get_map("map");
+            map.put(&2_u8, "x".to_owned());
+            map.put(&4_u8, "d".to_owned());
+            map.put(&5_u8, "y".to_owned());
+        }
+        overlay_db.merge(fork.into_patch()).unwrap();
+
+        let base = base_db.snapshot();
+        let overlay = overlay_db.snapshot();
+        // The map is the only index in both databases, so its address is the same.
+        let address = map_address(&base);
+        assert_eq!(address, map_address(&overlay));
+
+        // Key 3 is hidden in the base snapshot; key 5 is deleted, but then
+        // re-inserted in the overlay.
+        let deleted: HashSet<_> = vec![vec![3], vec![5]].into_iter().collect();
+        let mut iter = overlay_iter(&base, &overlay, &deleted, &address, &[]);
+        let mut entries = vec![];
+        while let Some((key, value)) = iter.next() {
+            entries.push((key.to_vec(), value.to_vec()));
+        }
+        assert_eq!(
+            entries,
+            vec![
+                (vec![1], b"a".to_vec()),
+                (vec![2], b"x".to_vec()),
+                (vec![4], b"d".to_vec()),
+                (vec![5], b"y".to_vec()),
+            ]
+        );
+
+        // Iteration may start from an arbitrary key.
+        let mut iter = overlay_iter(&base, &overlay, &deleted, &address, &[3]);
+        assert_eq!(iter.peek(), Some((&[4_u8][..], &b"d"[..])));
+        assert_eq!(iter.next(), Some((&[4_u8][..], &b"d"[..])));
+        assert_eq!(iter.next(), Some((&[5_u8][..], &b"y"[..])));
+        assert_eq!(iter.next(), None);
+    }
+}
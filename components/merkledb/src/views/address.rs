@@ -229,6 +229,25 @@ impl IndexAddress {
         (name, is_in_group)
     }
 
+    /// Restores an address from its fully qualified name obtained with `fully_qualified_name`.
+    pub(super) fn from_fully_qualified_name(qualified_name: &[u8]) -> Self {
+        let in_migration = qualified_name[0] == MIGRATION_CHAR;
+        let (name, is_in_group) = Self::parse_fully_qualified_name(qualified_name, 0);
+        let id_in_group = if is_in_group {
+            // Skip the migration char (if any), the name part and the separator.
+            let key_start = usize::from(in_migration) + name.len() + 1;
+            Some(qualified_name[key_start..].to_vec())
+        } else {
+            None
+        };
+
+        Self {
+            name,
+            id_in_group,
+            in_migration,
+        }
+    }
+
     /// Converts a migration namespace into the form that all indexes in the namespace
     /// begin with.
     #[inline]
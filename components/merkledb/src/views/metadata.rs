@@ -328,6 +328,34 @@ impl<T: RawAccess> IndexesPool<T> {
         self.0.get(index_name)
     }
 
+    /// Returns addresses of all indexes in the pool together with their resolved addresses.
+    pub(crate) fn indexes(&self) -> impl Iterator<Item = (IndexAddress, ResolvedAddress)> + '_ {
+        self.0
+            .iter::<_, Vec<u8>, Vec<u8>>(&())
+            // The entry with the empty key stores the pool size.
+            .filter(|(full_name, _)| !full_name.is_empty())
+            .map(|(full_name, metadata)| {
+                let metadata = IndexMetadata::<Vec<u8>>::from_bytes(metadata.into())
+                    .expect("Cannot deserialize index metadata");
+                let address = IndexAddress::from_fully_qualified_name(&full_name);
+                let resolved = ResolvedAddress::new(address.name(), Some(metadata.identifier));
+                (address, resolved)
+            })
+    }
+
+    /// Returns resolved addresses of all indexes in the specified namespace, i.e., indexes
+    /// with the name starting with `{namespace}.`.
+    pub(crate) fn namespace_indexes(&self, namespace: &str) -> Vec<ResolvedAddress> {
+        let prefix = format!("{}.", namespace).into_bytes();
+        self.0
+            .iter::<_, Vec<u8>, IndexMetadata>(&prefix)
+            .map(|(full_name, metadata)| {
+                let (name, _) = IndexAddress::parse_fully_qualified_name(&full_name, prefix.len());
+                ResolvedAddress::new(name, Some(metadata.identifier))
+            })
+            .collect()
+    }
+
     fn set_len(&mut self, len: u64) {
         self.0.put_or_forget(&(), len);
     }
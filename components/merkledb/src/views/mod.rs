@@ -214,10 +214,12 @@ impl<T: RawAccess> ViewInner<T> {
             // Ignore all changes from the snapshot.
             Box::new(ChangesIter::new(changes_iter.unwrap()))
         } else {
-            Box::new(ForkIter::new(
-                self.snapshot().iter(&self.address, from),
-                changes_iter,
-            ))
+            let snapshot_iter = self.snapshot().iter(&self.address, from);
+            let snapshot_iter = match self.changes.as_ref() {
+                Some(changes) => changes.skip_removed(snapshot_iter),
+                None => snapshot_iter,
+            };
+            Box::new(ForkIter::new(snapshot_iter, changes_iter))
         }
     }
 }
@@ -428,6 +430,11 @@ impl<T: RawAccessMut> View<T> {
     pub fn clear(&mut self) {
         self.changes_mut().clear();
     }
+
+    /// Removes keys in the half-open range `[from, to)` from the view.
+    pub(crate) fn remove_range(&mut self, from: &[u8], to: &[u8]) {
+        self.changes_mut().remove_range(from, to);
+    }
 }
 
 /// A bytes iterator implementation that has no items.
@@ -526,6 +533,31 @@ where
     K: BinaryKey + ?Sized,
     V: BinaryValue,
 {
+    /// Creates an iterator over entries of the index at `address` in the snapshot with keys
+    /// starting with `prefix`. The prefix is detached from the keys before parsing them.
+    /// If the index does not exist, the iterator is empty.
+    pub(crate) fn from_snapshot(
+        snapshot: &'a dyn Snapshot,
+        address: &IndexAddress,
+        prefix: &[u8],
+    ) -> Self {
+        let base_iter = match ViewWithMetadata::get_metadata_unchecked(snapshot, address) {
+            Some(metadata) => {
+                let resolved = ResolvedAddress::new(address.name(), Some(metadata.identifier()));
+                snapshot.iter(&resolved, prefix)
+            }
+            None => Box::new(EmptyIterator),
+        };
+        Self {
+            base_iter,
+            prefix: prefix.to_vec(),
+            detach_prefix: true,
+            ended: false,
+            _k: PhantomData,
+            _v: PhantomData,
+        }
+    }
+
     /// Drops the keys returned by the underlying iterator without parsing them.
     pub(crate) fn drop_key_type(self) -> Iter<'a, (), V> {
         Iter {
@@ -15,7 +15,7 @@
 use exonum_crypto::Hash;
 
 use crate::{
-    views::{AsReadonly, IndexType, RawAccess, ViewWithMetadata},
+    views::{AsReadonly, IndexType, IndexesPool, RawAccess, View, ViewWithMetadata},
     Fork, ObjectHash, ProofMapIndex,
 };
 
@@ -86,6 +86,28 @@ impl<T: RawAccess> SystemSchema<T> {
     pub fn state_hash(&self) -> Hash {
         get_state_aggregator(self.0.clone(), "").object_hash()
     }
+
+    /// Returns the total byte length of keys and values stored in the indexes
+    /// of the specified namespace, i.e., indexes with the name starting with `{namespace}.`
+    /// (such as indexes accessed via [`Prefixed`] access with this namespace).
+    ///
+    /// Only the entries of indexes are taken into account; auxiliary data (e.g., index metadata)
+    /// is not. This method iterates over all entries in the namespace, so it may be slow
+    /// for large namespaces.
+    ///
+    /// [`Prefixed`]: access/struct.Prefixed.html
+    pub fn namespace_size(&self, namespace: &str) -> u64 {
+        IndexesPool::new(self.0.clone())
+            .namespace_indexes(namespace)
+            .into_iter()
+            .map(|address| {
+                View::new(self.0.clone(), address)
+                    .iter::<_, Vec<u8>, Vec<u8>>(&())
+                    .map(|(key, value)| (key.len() + value.len()) as u64)
+                    .sum::<u64>()
+            })
+            .sum()
+    }
 }
 
 impl<T: RawAccess + AsReadonly> SystemSchema<T> {
@@ -275,4 +297,30 @@ mod tests {
         let system_schema = SystemSchema::new(&snapshot);
         assert_eq!(system_schema.state_hash(), HashTag::empty_map_hash());
     }
+
+    #[test]
+    fn namespace_size() {
+        let db = TemporaryDB::new();
+        let fork = db.fork();
+        {
+            let mut map = fork.get_map("test.map");
+            // Keys are 8 bytes long; each value is 3 bytes long.
+            map.put(&1_u64, "foo".to_owned());
+            map.put(&2_u64, "bar".to_owned());
+            // An entry has an empty key; the value is 4 bytes long.
+            fork.get_entry(("test.group", &1_u8)).set(1_u32);
+            // Indexes outside the namespace are not taken into account.
+            fork.get_entry("other.entry").set(1_u64);
+            fork.get_entry("tests.entry").set(1_u64);
+            // Indexes in a migration are not taken into account either.
+            Migration::new("test", &fork).get_entry("entry").set(1_u64);
+        }
+        db.merge(fork.into_patch()).unwrap();
+
+        let snapshot = db.snapshot();
+        let system_schema = SystemSchema::new(&snapshot);
+        assert_eq!(system_schema.namespace_size("test"), 2 * (8 + 3) + 4);
+        assert_eq!(system_schema.namespace_size("other"), 8);
+        assert_eq!(system_schema.namespace_size("unknown"), 0);
+    }
 }
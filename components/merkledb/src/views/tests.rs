@@ -19,7 +19,7 @@ use std::{num::NonZeroU64, panic, rc::Rc};
 
 use crate::{
     access::CopyAccessExt,
-    db,
+    db, typed_iter,
     validation::is_valid_identifier,
     views::{IndexAddress, IndexType, RawAccess, View, ViewWithMetadata},
     Database, DbOptions, Fork, ListIndex, MapIndex, ResolvedAddress, RocksDB, TemporaryDB,
@@ -400,6 +400,42 @@ fn fork_iter_prefixed() {
     test_fork_iter(&TemporaryDB::new(), PREFIXED_IDX);
 }
 
+#[test]
+fn typed_iter_over_prefix() {
+    let db = TemporaryDB::new();
+    let fork = db.fork();
+    {
+        // Emulate a map keyed by `(u8, u16)` tuples.
+        let mut map: MapIndex<_, [u8], String> = fork.get_map(("map", &1_u8));
+        map.put(&[0, 0, 1], "zero".to_owned());
+        map.put(&[1, 0, 2], "bar".to_owned());
+        map.put(&[1, 0, 1], "foo".to_owned());
+        map.put(&[1, 1, 0], "baz".to_owned());
+        map.put(&[2, 0, 1], "other".to_owned());
+        // Another index in the same family should not be touched.
+        fork.get_map::<_, [u8], String>(("map", &2_u8))
+            .put(&[1, 0, 3], "unrelated".to_owned());
+    }
+    db.merge(fork.into_patch()).unwrap();
+
+    let snapshot = db.snapshot();
+    let address = IndexAddress::from_root("map").append_key(&1_u8);
+    let entries: Vec<(u16, String)> = typed_iter(&snapshot, address.clone(), &[1]).collect();
+    assert_eq!(
+        entries,
+        vec![
+            (1, "foo".to_owned()),
+            (2, "bar".to_owned()),
+            (256, "baz".to_owned()),
+        ]
+    );
+
+    let entries: Vec<(u16, String)> = typed_iter(&snapshot, address, &[3]).collect();
+    assert!(entries.is_empty());
+    let entries: Vec<(u16, String)> = typed_iter(&snapshot, "missing", &[1]).collect();
+    assert!(entries.is_empty());
+}
+
 #[test]
 fn changelog() {
     test_changelog(&TemporaryDB::new(), IDX_NAME);
@@ -22,7 +22,7 @@ pub use self::{
     },
     builder::BlockchainBuilder,
     config::{ConsensusConfig, ConsensusConfigBuilder, ValidatorKeys},
-    schema::{CallErrorsIter, CallInBlock, CallRecords, Schema, TxLocation},
+    schema::{BlockExecutionSummary, CallErrorsIter, CallInBlock, CallRecords, Schema, TxLocation},
 };
 pub use crate::runtime::TxCheckCache;
 
@@ -40,10 +40,10 @@ use exonum_merkledb::{
 use std::{borrow::Cow, collections::BTreeMap, iter, sync::Arc};
 
 use crate::{
-    blockchain::config::GenesisConfig,
+    blockchain::config::{GenesisConfig, InstanceInitParams},
     helpers::{Height, ValidateInput, ValidatorId},
     messages::{AnyTx, Precommit, Verified},
-    runtime::Dispatcher,
+    runtime::{ArtifactId, CallInfo, Dispatcher, InstanceId, InstanceSpec, MethodId},
 };
 
 mod api_sender;
@@ -471,6 +471,43 @@ impl BlockchainMut {
         self.inner.snapshot()
     }
 
+    /// Returns artifacts deployed both in the blockchain and in the corresponding runtimes,
+    /// ordered by the artifact identifier.
+    pub fn artifacts(&self) -> Vec<ArtifactId> {
+        self.dispatcher.artifacts(&self.snapshot())
+    }
+
+    /// Returns specifications of the active service instances, ordered by the instance ID.
+    pub fn instances(&self) -> Vec<InstanceSpec> {
+        self.dispatcher.instances(&self.snapshot())
+    }
+
+    /// Limits the number of latest blocks for which execution errors of transactions
+    /// and other calls are stored. Errors in the earlier blocks are pruned when a new block
+    /// is committed, which prevents unbounded storage growth, e.g., in long test runs.
+    /// By default, errors are retained forever.
+    ///
+    /// Pruning is local to the node and does not affect block hashes. However, the calls
+    /// in pruned blocks appear successful, and proofs of their errors cannot be built.
+    /// [`Schema::call_records_retained_from`] returns the first height with retained errors.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `blocks` is zero.
+    ///
+    /// [`Schema::call_records_retained_from`]: struct.Schema.html#method.call_records_retained_from
+    pub fn set_status_retention(&mut self, blocks: u64) {
+        self.dispatcher.set_status_retention(blocks);
+    }
+
+    /// Returns the name of the method with the specified ID in the default interface
+    /// of an active service instance, or `None` if the name cannot be resolved.
+    /// Resolving names requires support from the service runtime; it is intended
+    /// for diagnostics, such as logs and test assertions.
+    pub fn method_name(&self, instance_id: InstanceId, method_id: MethodId) -> Option<&str> {
+        self.dispatcher.method_name(instance_id, method_id)
+    }
+
     /// Creates a snapshot of the current storage state that can be later committed into the storage
     /// via the `merge` method.
     pub fn fork(&self) -> Fork {
@@ -578,6 +615,69 @@ impl BlockchainMut {
         }
     }
 
+    /// Version of `create_patch` that calls a method of the service `call_info.instance_id`
+    /// on behalf of the active service `caller_id` before executing the block. Changes
+    /// made by the call are included into the block if the call is successful.
+    ///
+    /// Blocks created by this method cannot be reproduced by other nodes, since the call
+    /// is not recorded in the block.
+    #[doc(hidden)] // used by testkit, should not be used anywhere else
+    pub fn create_patch_with_service_call<C>(
+        &self,
+        block_params: BlockParams<'_>,
+        tx_cache: &C,
+        caller_id: InstanceId,
+        call_info: &CallInfo,
+        arguments: &[u8],
+    ) -> (BlockPatch, Result<(), ExecutionError>)
+    where
+        C: TransactionCache + ?Sized,
+    {
+        let tx_hashes = match block_params.contents {
+            BlockContents::Transactions(tx_hashes) => tx_hashes,
+            BlockContents::Skip => panic!("Service calls cannot be made in block skips"),
+        };
+
+        let mut fork = self.fork();
+        let res = self
+            .dispatcher
+            .call_on_behalf_of(&mut fork, caller_id, call_info, arguments);
+        let patch = self.create_patch_inner(fork, &block_params, tx_hashes, tx_cache);
+        (patch, res)
+    }
+
+    /// Version of `create_patch` that initiates adding the service `instance` before executing
+    /// the block, similar to the supervisor starting a service. The instance becomes active
+    /// after the block is committed. Changes made by the instance initialization are included
+    /// into the block if the initialization is successful.
+    ///
+    /// Blocks created by this method cannot be reproduced by other nodes, since adding
+    /// the instance is not recorded in the block.
+    #[doc(hidden)] // used by testkit, should not be used anywhere else
+    pub fn create_patch_with_new_service<C>(
+        &self,
+        block_params: BlockParams<'_>,
+        tx_cache: &C,
+        instance: InstanceInitParams,
+    ) -> (BlockPatch, Result<(), ExecutionError>)
+    where
+        C: TransactionCache + ?Sized,
+    {
+        let tx_hashes = match block_params.contents {
+            BlockContents::Transactions(tx_hashes) => tx_hashes,
+            BlockContents::Skip => panic!("Services cannot be started in block skips"),
+        };
+
+        let mut fork = self.fork();
+        let res = self.dispatcher.add_service_in_block(
+            &mut fork,
+            instance.instance_spec,
+            instance.constructor,
+        );
+        let patch = self.create_patch_inner(fork, &block_params, tx_hashes, tx_cache);
+        (patch, res)
+    }
+
     /// Executes a new block skip and returns the corresponding patch.
     fn create_skip_patch(&self, block_data: &BlockParams<'_>) -> BlockPatch {
         let prev_block = self.inner.last_block();
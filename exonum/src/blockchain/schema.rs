@@ -48,6 +48,7 @@ define_names!(
     TRANSACTIONS => "transactions";
     CALL_ERRORS => "call_errors";
     CALL_ERRORS_AUX => "call_errors_aux";
+    CALL_ERRORS_RETAINED_FROM => "call_errors_retained_from";
     TRANSACTIONS_LEN => "transactions_len";
     TRANSACTIONS_POOL => "transactions_pool";
     TRANSACTIONS_POOL_LEN => "transactions_pool_len";
@@ -134,6 +135,24 @@ impl<T: Access> Schema<T> {
         self.access.get_map((CALL_ERRORS_AUX, &block_height.0))
     }
 
+    /// Returns an entry with the earliest height for which call errors are retained.
+    fn call_errors_retained_from(&self) -> Entry<T::Base, u64> {
+        self.access.get_entry(CALL_ERRORS_RETAINED_FROM)
+    }
+
+    /// Returns the earliest height for which execution errors are retained. Errors occurring
+    /// in the earlier blocks have been pruned, so the calls in these blocks appear successful
+    /// in [`call_records`]. Returns `Height(0)` if no errors have been pruned.
+    ///
+    /// Errors are pruned only if the node is configured to do so
+    /// via [`BlockchainMut::set_status_retention`].
+    ///
+    /// [`call_records`]: #method.call_records
+    /// [`BlockchainMut::set_status_retention`]: struct.BlockchainMut.html#method.set_status_retention
+    pub fn call_records_retained_from(&self) -> Height {
+        Height(self.call_errors_retained_from().get().unwrap_or(0))
+    }
+
     /// Returns a record of errors that occurred during execution of a particular block.
     /// If the block is not committed, returns `None`.
     pub fn call_records(&self, block_height: Height) -> Option<CallRecords<T>> {
@@ -158,6 +177,41 @@ impl<T: Access> Schema<T> {
         Some(status)
     }
 
+    /// Returns an aggregated summary of transaction execution in the block at the specified
+    /// height. If the block is not committed, returns `None`.
+    ///
+    /// The summary is assembled from the call records saved by the dispatcher; errors
+    /// in `before_transactions` / `after_transactions` hooks are not included.
+    pub fn block_execution_summary(&self, block_height: Height) -> Option<BlockExecutionSummary> {
+        let records = self.call_records(block_height)?;
+        let transactions = self.block_transactions(block_height);
+        let mut failed: Vec<_> = records
+            .errors()
+            .filter_map(|(call, err)| match call {
+                CallInBlock::Transaction { index } => Some((index, err)),
+                _ => None,
+            })
+            .collect();
+        // Errors are not stored in the order of calls, so we need to restore it.
+        failed.sort_unstable_by_key(|(index, _)| *index);
+        let failed: Vec<_> = failed
+            .into_iter()
+            .map(|(index, err)| {
+                let tx_hash = transactions
+                    .get(u64::from(index))
+                    .expect("BUG: call record refers to a non-existing transaction");
+                (tx_hash, err)
+            })
+            .collect();
+
+        let total = transactions.len();
+        Some(BlockExecutionSummary {
+            total,
+            succeeded: total - failed.len() as u64,
+            failed,
+        })
+    }
+
     /// Returns an entry that represents a count of committed transactions in the blockchain.
     fn transactions_len_index(&self) -> Entry<T::Base, u64> {
         self.access.get_entry(TRANSACTIONS_LEN)
@@ -412,6 +466,18 @@ where
         self.call_errors_aux(height).put(&call, aux);
     }
 
+    /// Removes errors which occurred in blocks with heights less than `height`.
+    pub(crate) fn prune_call_errors(&mut self, height: Height) {
+        let retained_from = self.call_records_retained_from();
+        for pruned_height in retained_from.0..height.0 {
+            self.call_errors_map(Height(pruned_height)).clear();
+            self.call_errors_aux(Height(pruned_height)).clear();
+        }
+        if height > retained_from {
+            self.call_errors_retained_from().set(height.0);
+        }
+    }
+
     pub(super) fn clear_block_skip(&mut self) {
         if let Some(block_skip) = self.block_skip_entry().take() {
             let block_hash = block_skip.object_hash();
@@ -426,6 +492,23 @@ where
     }
 }
 
+/// Aggregated results of transaction execution within a specific block.
+///
+/// Can be obtained via [`Schema::block_execution_summary`].
+///
+/// [`Schema::block_execution_summary`]: struct.Schema.html#method.block_execution_summary
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct BlockExecutionSummary {
+    /// Total number of transactions in the block.
+    pub total: u64,
+    /// Number of transactions executed successfully.
+    pub succeeded: u64,
+    /// Hashes of failed transactions together with the corresponding errors,
+    /// in the order of their appearance in the block.
+    pub failed: Vec<(Hash, ExecutionError)>,
+}
+
 /// Information about call errors within a specific block.
 ///
 /// This data type can be used to get information or build proofs that execution
@@ -784,6 +784,46 @@ fn state_aggregation() {
     assert_eq!(actual_indexes, expected_indexes);
 }
 
+#[test]
+fn pruning_call_errors() {
+    let keys = KeyPair::random();
+    let mut blockchain = create_blockchain(
+        RuntimeInspector::default(),
+        vec![InitAction::Noop.into_default_instance()],
+    );
+    blockchain.set_status_retention(3);
+
+    let mut tx_hashes = vec![];
+    for code in 0_u8..10 {
+        let tx = Transaction::ExecutionError(code, format!("Error #{}", code));
+        let tx = tx.sign(TEST_SERVICE_ID, &keys);
+        tx_hashes.push(tx.object_hash());
+        // The status of the latest transaction is always available.
+        let err = execute_transaction(&mut blockchain, tx).expect_err("Transaction must fail");
+        assert_eq!(err.description(), format!("Error #{}", code));
+    }
+
+    let snapshot = blockchain.snapshot();
+    let schema = Schema::new(&snapshot);
+    assert_eq!(schema.height(), Height(10));
+    // Errors are retained for blocks #8, #9 and #10.
+    assert_eq!(schema.call_records_retained_from(), Height(8));
+
+    for (code, tx_hash) in tx_hashes.iter().enumerate() {
+        let location = schema.transactions_locations().get(tx_hash).unwrap();
+        let height = location.block_height();
+        let records = schema.call_records(height).unwrap();
+        if height < Height(8) {
+            assert_eq!(records.errors().count(), 0);
+            schema.transaction_result(location).unwrap().unwrap();
+        } else {
+            assert_eq!(records.errors().count(), 1);
+            let err = schema.transaction_result(location).unwrap().unwrap_err();
+            assert_eq!(err.description(), format!("Error #{}", code));
+        }
+    }
+}
+
 #[test]
 fn no_data_race_for_transaction_pool() {
     let keys = KeyPair::random();
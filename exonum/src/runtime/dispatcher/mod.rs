@@ -33,18 +33,18 @@ use std::{
 use crate::{
     blockchain::{Blockchain, CallInBlock, Schema as CoreSchema},
     crypto::Hash,
-    helpers::ValidateInput,
+    helpers::{Height, ValidateInput},
     messages::{AnyTx, Verified},
     runtime::{
         error::{CallSite, CallType, CommonError, ErrorKind, ExecutionError, ExecutionFail},
-        execution_context::TopLevelContext,
+        execution_context::{ExecutionContextUnstable, TopLevelContext},
         migrations::{
             InstanceMigration, MigrationContext, MigrationError, MigrationScript, MigrationStatus,
             MigrationType,
         },
-        ArtifactId, ArtifactStatus, CoreError, InstanceDescriptor, InstanceId, InstanceQuery,
-        InstanceSpec, InstanceState, InstanceStatus, Runtime, RuntimeFeature, RuntimeIdentifier,
-        RuntimeInstance,
+        ArtifactId, ArtifactStatus, CallInfo, CoreError, InstanceDescriptor, InstanceId,
+        InstanceQuery, InstanceSpec, InstanceState, InstanceStatus, MethodId, Runtime,
+        RuntimeFeature, RuntimeIdentifier, RuntimeInstance,
     },
 };
 
@@ -352,6 +352,7 @@ pub struct Dispatcher {
     runtimes: BTreeMap<u32, Box<dyn Runtime>>,
     service_infos: CommittedServices,
     migrations: Migrations,
+    status_retention: Option<u64>,
 }
 
 impl Dispatcher {
@@ -367,6 +368,7 @@ impl Dispatcher {
                 .collect(),
             service_infos: CommittedServices::default(),
             migrations: Migrations::new(blockchain),
+            status_retention: None,
         };
         for runtime in this.runtimes.values_mut() {
             runtime.initialize(blockchain);
@@ -466,6 +468,26 @@ impl Dispatcher {
         context.call(|mut ctx| ctx.initiate_adding_service(spec, constructor))
     }
 
+    /// Initiates adding a service instance at the beginning of a block with fork isolation.
+    /// Unlike built-in instances, the instance becomes active after the block is committed.
+    pub(crate) fn add_service_in_block(
+        &self,
+        fork: &mut Fork,
+        spec: InstanceSpec,
+        constructor: Vec<u8>,
+    ) -> Result<(), ExecutionError> {
+        let name = spec.name.clone();
+        let context =
+            TopLevelContext::for_block_call(self, fork, InstanceDescriptor::new(spec.id, &name));
+        let res = context.call(|mut ctx| ctx.initiate_adding_service(spec, constructor));
+        if res.is_err() {
+            fork.rollback();
+        } else {
+            fork.flush();
+        }
+        res
+    }
+
     /// Starts all the built-in instances, creating a `Patch` with persisted changes.
     pub(crate) fn start_builtin_instances(&mut self, fork: Fork) -> Patch {
         // Mark services as active.
@@ -792,6 +814,42 @@ impl Dispatcher {
         res
     }
 
+    /// Calls a method of a service instance on behalf of another active service
+    /// with fork isolation. The called service observes `Caller::Service` with the ID
+    /// of the calling instance, as in the case of a nested call made by the latter.
+    pub(crate) fn call_on_behalf_of(
+        &self,
+        fork: &mut Fork,
+        caller_id: InstanceId,
+        call_info: &CallInfo,
+        arguments: &[u8],
+    ) -> Result<(), ExecutionError> {
+        let caller = self.get_service(caller_id).ok_or_else(|| {
+            let msg = format!(
+                "Cannot make a call on behalf of inactive service with ID {}",
+                caller_id
+            );
+            CoreError::IncorrectInstanceId.with_description(msg)
+        })?;
+
+        let context = TopLevelContext::for_block_call(self, fork, caller);
+        let res = context.call(|mut ctx| {
+            ctx.make_child_call(
+                call_info.instance_id,
+                "",
+                call_info.method_id,
+                arguments,
+                false,
+            )
+        });
+        if res.is_err() {
+            fork.rollback();
+        } else {
+            fork.flush();
+        }
+        res
+    }
+
     /// Calls service hooks of the specified type for all active services.
     fn call_service_hooks(
         &self,
@@ -1060,11 +1118,25 @@ impl Dispatcher {
     /// described in `BlockchainMut::create_genesis_block()`, the processing of the genesis
     /// block is split into 2 parts.
     pub(crate) fn commit_block_and_notify_runtimes(&mut self, fork: Fork) -> Patch {
+        if let Some(retention) = self.status_retention {
+            let mut schema = CoreSchema::new(&fork);
+            let height = schema.height();
+            if height.0 >= retention {
+                schema.prune_call_errors(Height(height.0 - retention + 1));
+            }
+        }
         let patch = self.commit_block(fork);
         self.notify_runtimes_about_commit(&patch);
         patch
     }
 
+    /// Sets the number of latest blocks for which execution errors are retained. Errors
+    /// in the earlier blocks are pruned when a new block is committed.
+    pub(crate) fn set_status_retention(&mut self, blocks: u64) {
+        assert!(blocks > 0, "Status retention should be positive");
+        self.status_retention = Some(blocks);
+    }
+
     /// Return true if the artifact with the given identifier is deployed.
     pub(crate) fn is_artifact_deployed(&self, id: &ArtifactId) -> bool {
         self.runtimes
@@ -1072,6 +1144,35 @@ impl Dispatcher {
             .map_or(false, |runtime| runtime.is_artifact_deployed(id))
     }
 
+    /// Returns artifacts deployed in the blockchain and in the runtimes, ordered by
+    /// the artifact identifier.
+    pub(crate) fn artifacts(&self, snapshot: &dyn Snapshot) -> Vec<ArtifactId> {
+        let mut artifacts: Vec<_> = Schema::new(snapshot)
+            .artifacts()
+            .iter()
+            .filter_map(|(artifact, state)| {
+                if state.status == ArtifactStatus::Active && self.is_artifact_deployed(&artifact) {
+                    Some(artifact)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        // Entries in the proof map are ordered by key hashes rather than by keys.
+        artifacts.sort();
+        artifacts
+    }
+
+    /// Returns specifications of the active service instances, ordered by the instance ID.
+    pub(crate) fn instances(&self, snapshot: &dyn Snapshot) -> Vec<InstanceSpec> {
+        let schema = Schema::new(snapshot);
+        self.service_infos
+            .active_instances()
+            .filter_map(|(descriptor, _)| schema.get_instance(descriptor.id))
+            .map(|state| state.spec)
+            .collect()
+    }
+
     /// Looks up a runtime by its identifier.
     pub(crate) fn runtime_by_id(&self, id: u32) -> Option<&dyn Runtime> {
         self.runtimes.get(&id).map(AsRef::as_ref)
@@ -1090,6 +1191,14 @@ impl Dispatcher {
         Some((runtime_id, runtime))
     }
 
+    /// Returns the name of the method with the specified ID in the default interface
+    /// of a service instance, or `None` if the service is not active, its runtime
+    /// does not support method reflection, or the method is unknown.
+    pub(crate) fn method_name(&self, instance_id: InstanceId, method_id: MethodId) -> Option<&str> {
+        let (_, runtime) = self.runtime_for_service(instance_id)?;
+        runtime.method_name(instance_id, "", method_id)
+    }
+
     /// Returns the service matching the specified query.
     pub(crate) fn get_service<'q>(
         &self,
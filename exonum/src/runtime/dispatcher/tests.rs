@@ -345,8 +345,11 @@ fn test_dispatcher_simple() {
         .call(|mut ctx| ctx.initiate_adding_service(rust_service, vec![]))
         .expect("`initiate_adding_service` failed for rust");
 
-    let java_service =
-        InstanceSpec::from_raw_parts(JAVA_SERVICE_ID, JAVA_SERVICE_NAME.into(), java_artifact);
+    let java_service = InstanceSpec::from_raw_parts(
+        JAVA_SERVICE_ID,
+        JAVA_SERVICE_NAME.into(),
+        java_artifact.clone(),
+    );
     TopLevelContext::for_block_call(&dispatcher, &mut fork, java_service.as_descriptor())
         .call(|mut ctx| ctx.initiate_adding_service(java_service, vec![]))
         .expect("`initiate_adding_service` failed for java");
@@ -382,8 +385,11 @@ fn test_dispatcher_simple() {
             .with_description_containing("Service with numeric ID 2 already exists")
     );
 
-    let conflicting_rust_service =
-        InstanceSpec::from_raw_parts(RUST_SERVICE_ID + 1, RUST_SERVICE_NAME.into(), rust_artifact);
+    let conflicting_rust_service = InstanceSpec::from_raw_parts(
+        RUST_SERVICE_ID + 1,
+        RUST_SERVICE_NAME.into(),
+        rust_artifact.clone(),
+    );
     let context = TopLevelContext::for_block_call(
         &dispatcher,
         &mut fork,
@@ -403,6 +409,23 @@ fn test_dispatcher_simple() {
     db.merge(patch).unwrap();
     let mut fork = db.fork();
 
+    // Check that the dispatcher reports the deployed artifacts and the started services.
+    let snapshot = db.snapshot();
+    let expected_artifacts = vec![rust_artifact.clone(), java_artifact.clone()];
+    assert_eq!(dispatcher.artifacts(&snapshot), expected_artifacts);
+    let instances = dispatcher.instances(&snapshot);
+    let instances: Vec<_> = instances
+        .iter()
+        .map(|spec| (spec.id, spec.name.as_str(), &spec.artifact))
+        .collect();
+    assert_eq!(
+        instances,
+        vec![
+            (RUST_SERVICE_ID, RUST_SERVICE_NAME, &rust_artifact),
+            (JAVA_SERVICE_ID, JAVA_SERVICE_NAME, &java_artifact),
+        ]
+    );
+
     // Check if transactions are ready for execution.
     dispatcher
         .call(
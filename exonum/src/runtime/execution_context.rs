@@ -16,7 +16,7 @@ use crate::{
     blockchain::Schema as CoreSchema,
     crypto::{Hash, PublicKey},
     helpers::{Height, ValidateInput},
-    merkledb::{access::Prefixed, BinaryValue, Fork},
+    merkledb::{access::Prefixed, BinaryValue, Fork, Snapshot},
     runtime::{
         migrations::MigrationType, ArtifactId, BlockchainData, CallSite, CallType, Caller,
         CoreError, Dispatcher, DispatcherSchema, ExecutionError, ExecutionFail, InstanceDescriptor,
@@ -128,6 +128,16 @@ impl<'a> ExecutionContext<'a> {
         self.data().for_executing_service()
     }
 
+    /// Returns the blockchain state as of the start of the block being executed. Unlike
+    /// [`data`], the snapshot does not include changes made by the executed transactions
+    /// and hooks, including ones made by the current call, so it can be used to compare
+    /// the state before the block with the in-progress changes.
+    ///
+    /// [`data`]: #method.data
+    pub fn initial_snapshot(&self) -> &dyn Snapshot {
+        self.fork.base_snapshot()
+    }
+
     /// Returns the authorization information about this call.
     pub fn caller(&self) -> &Caller {
         &self.caller
@@ -624,6 +624,21 @@ pub trait Runtime: Send + fmt::Debug + 'static {
         arguments: &[u8],
     ) -> Result<(), ExecutionError>;
 
+    /// Returns the human-readable name of a method in the specified interface of a service
+    /// instance, or `None` if the method is unknown.
+    ///
+    /// The name is used for diagnostics only, e.g., in logs and tests; it does not influence
+    /// the call processing. The default implementation always returns `None`, i.e., signals
+    /// that the runtime does not support method reflection.
+    fn method_name(
+        &self,
+        instance_id: InstanceId,
+        interface_name: &str,
+        method_id: MethodId,
+    ) -> Option<&str> {
+        None
+    }
+
     /// Notifies a service stored in the present runtime about the beginning of the block. Allows
     /// the service to modify the blockchain state before any transaction in the block is processed.
     ///
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use anyhow::{bail, ensure, format_err};
-use exonum_crypto::{Hash, KeyPair, PublicKey, SecretKey, HASH_SIZE};
+use exonum_crypto::{hash, Hash, KeyPair, PublicKey, SecretKey, HASH_SIZE};
 use exonum_derive::{BinaryValue, ObjectHash};
 use exonum_merkledb::{
     impl_binary_key_for_binary_value,
@@ -133,6 +133,15 @@ impl AnyTx {
     pub fn parse<T: BinaryValue>(&self) -> anyhow::Result<T> {
         T::from_bytes(Cow::Borrowed(&self.arguments))
     }
+
+    /// Returns the hash of the transaction content, i.e., its call info and arguments.
+    ///
+    /// Unlike the hash of a signed transaction, the content hash does not depend on the author
+    /// or the signature. Thus, it can be computed before the transaction is signed, and it is
+    /// the same for identical transactions signed by different keys.
+    pub fn content_hash(&self) -> Hash {
+        hash(&self.to_bytes())
+    }
 }
 
 /// The artifact identifier is required to construct service instances.
@@ -27,4 +27,6 @@ pub enum Error {
     /// Unable to deploy artifact with the specified identifier, it is not listed
     /// among available artifacts.
     UnableToDeploy = 1,
+    /// Unable to deploy artifact because one of its dependencies is not deployed.
+    MissingDependency = 2,
 }
@@ -393,6 +393,10 @@ impl<T: ServiceFactory> ServiceFactory for WithoutMigrations<T> {
     fn create_instance(&self) -> Box<dyn Service> {
         self.0.create_instance()
     }
+
+    fn dependencies(&self) -> Vec<ArtifactId> {
+        self.0.dependencies()
+    }
 }
 
 impl<T> MigrateData for WithoutMigrations<T> {
@@ -572,6 +576,19 @@ impl RustRuntime {
             return Err(Error::UnableToDeploy.with_description(description));
         }
 
+        let dependencies = self.available_artifacts[artifact].dependencies();
+        if let Some(missing) = dependencies
+            .iter()
+            .find(|dependency| !self.deployed_artifacts.contains(dependency))
+        {
+            let description = format!(
+                "Runtime failed to deploy artifact with id {}, \
+                 it depends on artifact {} which is not deployed yet",
+                artifact, missing
+            );
+            return Err(Error::MissingDependency.with_description(description));
+        }
+
         trace!("Deployed artifact: {}", artifact);
         self.deployed_artifacts.insert(artifact.to_owned());
         Ok(())
@@ -815,7 +832,21 @@ impl Runtime for RustRuntime {
             .get(&context.instance().id)
             .expect("BUG: an attempt to execute transaction of unknown service.");
 
-        catch_panic(|| instance.as_ref().call(context, method_id, payload))
+        catch_panic(|| {
+            let service = instance.as_ref();
+            service.verify_transaction(method_id, payload)?;
+            service.call(context, method_id, payload)
+        })
+    }
+
+    fn method_name(
+        &self,
+        instance_id: InstanceId,
+        interface_name: &str,
+        method_id: MethodId,
+    ) -> Option<&str> {
+        let instance = self.started_services.get(&instance_id)?;
+        instance.service.method_name(interface_name, method_id)
     }
 
     fn before_transactions(&self, context: ExecutionContext<'_>) -> Result<(), ExecutionError> {
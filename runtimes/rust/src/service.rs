@@ -16,7 +16,7 @@ use exonum::{
     blockchain::{config::InstanceInitParams, ApiSender, SendError},
     crypto::{Hash, KeyPair, PublicKey},
     helpers::{Height, ValidatorId},
-    merkledb::{access::Prefixed, BinaryValue, ObjectHash, Snapshot},
+    merkledb::{access::Prefixed, BinaryValue, ObjectHash, Snapshot, SystemSchema},
     runtime::{
         ArtifactId, BlockchainData, DispatcherAction, ExecutionContext, ExecutionError,
         InstanceDescriptor, InstanceId, InstanceStatus, Mailbox, MethodId, SnapshotExt,
@@ -44,6 +44,17 @@ pub trait ServiceDispatcher: Send {
         method: MethodId,
         payload: &[u8],
     ) -> Result<(), ExecutionError>;
+
+    /// Returns the name of the method with the specified ID in the specified interface,
+    /// or `None` if the service does not implement the interface or the interface has
+    /// no such method. The name is used for diagnostics only.
+    ///
+    /// The default implementation always returns `None`. The implementation
+    /// derived via the `ServiceDispatcher` macro resolves names of all methods
+    /// in the interfaces declared with the `exonum_interface` macro.
+    fn method_name(&self, _interface_name: &str, _method: MethodId) -> Option<&'static str> {
+        None
+    }
 }
 
 /// Describes an Exonum service instance.
@@ -93,6 +104,22 @@ pub trait Service: ServiceDispatcher + Debug + 'static {
         Ok(())
     }
 
+    /// Verifies a call of the service interface method before it is executed. If the method
+    /// returns an error, the call fails with this error without executing the method handler.
+    ///
+    /// The method is invoked for every call dispatched to the service, including transactions
+    /// and calls from other services. Since the method has no access to the blockchain state,
+    /// it is intended for cheap stateless checks, such as rejecting malformed arguments.
+    ///
+    /// The default implementation does nothing and returns `Ok(())`.
+    fn verify_transaction(
+        &self,
+        _method_id: MethodId,
+        _payload: &[u8],
+    ) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
     /// Performs storage operations on behalf of the service before processing any transaction
     /// in the block.
     ///
@@ -146,6 +173,18 @@ pub trait Service: ServiceDispatcher + Debug + 'static {
     /// The request handlers are mounted on the `/api/services/{instance_name}` path at the
     /// listen address of every full node in the blockchain network.
     fn wire_api(&self, _builder: &mut ServiceApiBuilder) {}
+
+    /// Returns the storage footprint of the service instance, that is, the total byte length
+    /// of keys and values in the instance data. This may be useful for capacity testing.
+    ///
+    /// The default implementation sums the lengths of entries in all indexes in the service
+    /// namespace via [`SystemSchema::namespace_size`]; it iterates over the entire instance data,
+    /// so it may be slow for large services.
+    ///
+    /// [`SystemSchema::namespace_size`]: https://docs.rs/exonum-merkledb/latest/exonum_merkledb/struct.SystemSchema.html#method.namespace_size
+    fn state_size(&self, instance: &InstanceDescriptor, snapshot: &dyn Snapshot) -> u64 {
+        SystemSchema::new(snapshot).namespace_size(&instance.name)
+    }
 }
 
 /// Describes a service instance factory for the specific Rust artifact.
@@ -159,6 +198,14 @@ pub trait ServiceFactory: Send + Debug + 'static {
     fn artifact_protobuf_spec(&self) -> ArtifactProtobufSpec;
     /// Creates a new service instance.
     fn create_instance(&self) -> Box<dyn Service>;
+
+    /// Returns identifiers of the artifacts that must be deployed before this artifact
+    /// can be deployed.
+    ///
+    /// The default implementation returns an empty list, i.e., the artifact has no dependencies.
+    fn dependencies(&self) -> Vec<ArtifactId> {
+        vec![]
+    }
 }
 
 #[allow(clippy::use_self)] // false positive
@@ -84,6 +84,10 @@ pub struct Spec<T, Kind> {
 
 impl<T: ServiceFactory, Kind> Spec<T, Kind> {
     /// Adds a new built-in service instance to instantiate at the genesis block.
+    ///
+    /// The method can be called several times to instantiate the artifact as multiple
+    /// services. The instances must have distinct IDs and names; each instance has
+    /// its own storage and is initialized with its own constructor arguments.
     pub fn with_instance(
         mut self,
         id: InstanceId,
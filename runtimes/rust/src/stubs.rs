@@ -65,6 +65,14 @@ pub trait Interface<'a> {
         method: MethodId,
         payload: &[u8],
     ) -> Result<(), ExecutionError>;
+
+    /// Returns the name of the interface method with the specified ID, or `None` if the interface
+    /// has no such method. The name is used for diagnostics only.
+    ///
+    /// The default implementation always returns `None`.
+    fn method_name(_method: MethodId) -> Option<&'static str> {
+        None
+    }
 }
 
 /// Generic / low-level stub implementation which is defined for any method in any interface.
@@ -17,18 +17,25 @@ use exonum::{
         config::{GenesisConfig, InstanceInitParams},
         Blockchain, BlockchainBuilder, BlockchainMut,
     },
+    crypto::{KeyPair, PublicKey},
     helpers::Height,
-    merkledb::{access::AccessExt, BinaryValue, SystemSchema},
+    merkledb::{
+        access::{AccessExt, FromAccess},
+        indexes::codec_entry::ProtobufCodec,
+        BinaryValue, CodecEntry, SystemSchema,
+    },
     runtime::{
-        Caller, CommonError, CoreError, ErrorMatch, ExecutionContext, ExecutionError,
-        InstanceStatus, SnapshotExt,
+        ArtifactId, Caller, CommonError, CoreError, ErrorKind, ErrorMatch, ExecutionContext,
+        ExecutionError, InstanceStatus, MethodId, Runtime, RuntimeIdentifier, SnapshotExt,
     },
 };
 use exonum_derive::{exonum_interface, BinaryValue, ServiceDispatcher, ServiceFactory};
 use pretty_assertions::assert_eq;
 use serde_derive::{Deserialize, Serialize};
 
-use exonum_rust_runtime::{DefaultInstance, RustRuntimeBuilder, Service, ServiceFactory};
+use exonum_rust_runtime::{
+    ArtifactProtobufSpec, DefaultInstance, RustRuntimeBuilder, Service, ServiceFactory,
+};
 
 use self::inspected::{
     create_block_with_transactions, create_genesis_config_builder, execute_transaction,
@@ -200,6 +207,225 @@ impl DefaultInstance for DependentServiceImpl {
     }
 }
 
+/// Service artifact which requires `TestServiceImpl` artifact to be deployed first.
+#[derive(Debug, ServiceDispatcher)]
+#[service_dispatcher(implements())]
+pub struct ArtifactWithDependency;
+
+impl Service for ArtifactWithDependency {}
+
+impl ServiceFactory for ArtifactWithDependency {
+    fn artifact_id(&self) -> ArtifactId {
+        ArtifactId::new(
+            RuntimeIdentifier::Rust,
+            "artifact_with_dependency",
+            "0.1.0".parse().unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn artifact_protobuf_spec(&self) -> ArtifactProtobufSpec {
+        ArtifactProtobufSpec::default()
+    }
+
+    fn create_instance(&self) -> Box<dyn Service> {
+        Box::new(Self)
+    }
+
+    fn dependencies(&self) -> Vec<ArtifactId> {
+        vec![TestServiceImpl.artifact_id()]
+    }
+}
+
+#[exonum_interface(auto_ids)]
+trait Author<Ctx> {
+    type Output;
+    fn record_author(&self, ctx: Ctx, _: ()) -> Self::Output;
+    fn call_record_author(&self, ctx: Ctx, _: ()) -> Self::Output;
+}
+
+/// Service recording the author of the transaction calling it.
+#[derive(Debug, ServiceFactory, ServiceDispatcher)]
+#[service_dispatcher(implements("Author"))]
+#[service_factory(artifact_name = "author_service", artifact_version = "0.1.0")]
+pub struct AuthorServiceImpl;
+
+impl Author<ExecutionContext<'_>> for AuthorServiceImpl {
+    type Output = Result<(), ExecutionError>;
+
+    fn record_author(&self, ctx: ExecutionContext<'_>, _: ()) -> Self::Output {
+        // The author is absent if the method is called by another service.
+        let mut entry = ctx.service_data().get_entry("author");
+        match ctx.caller().author() {
+            Some(author) => entry.set(author),
+            None => entry.remove(),
+        }
+        Ok(())
+    }
+
+    fn call_record_author(&self, mut ctx: ExecutionContext<'_>, _: ()) -> Self::Output {
+        ctx.record_author(Self::INSTANCE_NAME, ())
+    }
+}
+
+impl Service for AuthorServiceImpl {}
+
+impl DefaultInstance for AuthorServiceImpl {
+    const INSTANCE_ID: u32 = 6;
+    const INSTANCE_NAME: &'static str = "author-service";
+}
+
+#[exonum_interface(auto_ids)]
+trait Peer<Ctx> {
+    type Output;
+    fn copy_peer_entry(&self, ctx: Ctx, peer_name: String) -> Self::Output;
+}
+
+/// Service copying `method_b_entry` of another service instance into its own data.
+#[derive(Debug, ServiceFactory, ServiceDispatcher)]
+#[service_dispatcher(implements("Peer"))]
+#[service_factory(artifact_name = "peer_service", artifact_version = "0.1.0")]
+pub struct PeerServiceImpl;
+
+impl Peer<ExecutionContext<'_>> for PeerServiceImpl {
+    type Output = Result<(), ExecutionError>;
+
+    fn copy_peer_entry(&self, ctx: ExecutionContext<'_>, peer_name: String) -> Self::Output {
+        // Read data of another service instance by its name, if the instance exists.
+        let peer_entry = ctx
+            .data()
+            .for_service(&*peer_name)
+            .and_then(|data| data.get_proof_entry::<_, u64>("method_b_entry").get());
+        let mut entry = ctx.service_data().get_entry("peer_entry");
+        match peer_entry {
+            Some(value) => entry.set(value),
+            None => entry.remove(),
+        }
+        Ok(())
+    }
+}
+
+impl Service for PeerServiceImpl {}
+
+impl DefaultInstance for PeerServiceImpl {
+    const INSTANCE_ID: u32 = 7;
+    const INSTANCE_NAME: &'static str = "peer-service";
+}
+
+#[exonum_interface(auto_ids)]
+trait KeyStore<Ctx> {
+    type Output;
+    fn set_key(&self, ctx: Ctx, key: PublicKey) -> Self::Output;
+}
+
+/// Service storing a key encoded as a Protobuf message.
+#[derive(Debug, ServiceFactory, ServiceDispatcher)]
+#[service_dispatcher(implements("KeyStore"))]
+#[service_factory(artifact_name = "codec_service", artifact_version = "0.1.0")]
+pub struct CodecServiceImpl;
+
+impl KeyStore<ExecutionContext<'_>> for CodecServiceImpl {
+    type Output = Result<(), ExecutionError>;
+
+    fn set_key(&self, ctx: ExecutionContext<'_>, key: PublicKey) -> Self::Output {
+        let mut entry: CodecEntry<_, PublicKey, ProtobufCodec> =
+            CodecEntry::from_access(ctx.service_data(), "key".into()).unwrap();
+        entry.set(key);
+        Ok(())
+    }
+}
+
+impl Service for CodecServiceImpl {}
+
+impl DefaultInstance for CodecServiceImpl {
+    const INSTANCE_ID: u32 = 9;
+    const INSTANCE_NAME: &'static str = "codec-service";
+}
+
+#[exonum_interface(auto_ids)]
+trait NonZero<Ctx> {
+    type Output;
+    fn set_non_zero(&self, ctx: Ctx, arg: u64) -> Self::Output;
+}
+
+/// Service rejecting transactions with zero arguments before their execution.
+#[derive(Debug, ServiceFactory, ServiceDispatcher)]
+#[service_dispatcher(implements("NonZero"))]
+#[service_factory(artifact_name = "non_zero_service", artifact_version = "0.1.0")]
+pub struct NonZeroServiceImpl;
+
+impl NonZero<ExecutionContext<'_>> for NonZeroServiceImpl {
+    type Output = Result<(), ExecutionError>;
+
+    fn set_non_zero(&self, ctx: ExecutionContext<'_>, arg: u64) -> Self::Output {
+        ctx.service_data().get_entry("value").set(arg);
+        Ok(())
+    }
+}
+
+impl Service for NonZeroServiceImpl {
+    fn verify_transaction(
+        &self,
+        method_id: MethodId,
+        payload: &[u8],
+    ) -> Result<(), ExecutionError> {
+        // Reject zero arguments of `set_non_zero` (which has the zero identifier).
+        if method_id == 0 {
+            let arg = u64::from_bytes(payload.into()).map_err(CommonError::malformed_arguments)?;
+            if arg == 0 {
+                return Err(ExecutionError::service(
+                    0,
+                    "Argument of `set_non_zero` is zero",
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl DefaultInstance for NonZeroServiceImpl {
+    const INSTANCE_ID: u32 = 8;
+    const INSTANCE_NAME: &'static str = "non-zero-service";
+}
+
+#[exonum_interface(auto_ids)]
+trait Sum<Ctx> {
+    type Output;
+    fn add_to_sum(&self, ctx: Ctx, arg: u64) -> Self::Output;
+}
+
+/// Service accumulating arguments of its calls and recording the sum as of the start
+/// of the block.
+#[derive(Debug, ServiceFactory, ServiceDispatcher)]
+#[service_dispatcher(implements("Sum"))]
+#[service_factory(artifact_name = "sum_service", artifact_version = "0.1.0")]
+pub struct SumServiceImpl;
+
+impl Sum<ExecutionContext<'_>> for SumServiceImpl {
+    type Output = Result<(), ExecutionError>;
+
+    fn add_to_sum(&self, ctx: ExecutionContext<'_>, arg: u64) -> Self::Output {
+        ctx.service_data()
+            .get_entry::<_, u64>("sum")
+            .modify(|sum| *sum += arg);
+        let initial_sum = ctx
+            .initial_snapshot()
+            .for_service(Self::INSTANCE_NAME)
+            .unwrap()
+            .get_entry::<_, u64>("sum")
+            .get_or_default();
+        ctx.service_data().get_entry("initial_sum").set(initial_sum);
+        Ok(())
+    }
+}
+
+impl Service for SumServiceImpl {}
+
+impl DefaultInstance for SumServiceImpl {
+    const INSTANCE_ID: u32 = 5;
+    const INSTANCE_NAME: &'static str = "sum-service";
+}
+
 fn create_genesis_config_with_supervisor() -> GenesisConfig {
     create_genesis_config_builder()
         .with_artifact(ToySupervisorService.artifact_id())
@@ -217,6 +443,12 @@ fn create_runtime(
             .with_factory(TestServiceImplV2)
             .with_factory(ToySupervisorService)
             .with_factory(DependentServiceImpl)
+            .with_factory(ArtifactWithDependency)
+            .with_factory(AuthorServiceImpl)
+            .with_factory(PeerServiceImpl)
+            .with_factory(CodecServiceImpl)
+            .with_factory(NonZeroServiceImpl)
+            .with_factory(SumServiceImpl)
             .build_for_tests(),
     );
     let events_handle = inspected.events.clone();
@@ -542,6 +774,22 @@ fn state_aggregation() {
     );
 }
 
+#[test]
+fn method_names() {
+    let genesis_config = create_genesis_config_builder()
+        .with_artifact(TestServiceImpl.artifact_id())
+        .with_instance(TestServiceImpl.default_instance())
+        .build();
+    let (blockchain, _) = create_runtime(Blockchain::build_for_tests(), genesis_config);
+
+    let instance_id = TestServiceImpl::INSTANCE_ID;
+    assert_eq!(blockchain.method_name(instance_id, 0), Some("method_a"));
+    assert_eq!(blockchain.method_name(instance_id, 1), Some("method_b"));
+    assert_eq!(blockchain.method_name(instance_id, 2), None);
+    // Unknown services do not have method names.
+    assert_eq!(blockchain.method_name(instance_id + 1, 0), None);
+}
+
 // Create a blockchain instance with two service instances with the same `Test` interface,
 // but with different implementation versions.
 #[test]
@@ -944,6 +1192,50 @@ fn dependent_service_in_successive_block() {
     );
 }
 
+/// Checks that an artifact cannot be deployed before its dependencies.
+#[test]
+fn artifact_with_dependency() {
+    let mut runtime = RustRuntimeBuilder::new()
+        .with_factory(TestServiceImpl)
+        .with_factory(ArtifactWithDependency)
+        .build_for_tests();
+
+    // The dependency is not deployed yet, so the deployment fails.
+    runtime.deploy_artifact(ArtifactWithDependency.artifact_id(), vec![]);
+    assert!(!runtime.is_artifact_deployed(&ArtifactWithDependency.artifact_id()));
+
+    // Deploy the dependency and try again.
+    runtime.deploy_artifact(TestServiceImpl.artifact_id(), vec![]);
+    assert!(runtime.is_artifact_deployed(&TestServiceImpl.artifact_id()));
+    runtime.deploy_artifact(ArtifactWithDependency.artifact_id(), vec![]);
+    assert!(runtime.is_artifact_deployed(&ArtifactWithDependency.artifact_id()));
+}
+
+/// Checks that the deployment error for an artifact with a missing dependency is descriptive.
+#[test]
+#[should_panic(expected = "depends on artifact 0:test_service:0.1.0 which is not deployed yet")]
+fn artifact_with_missing_dependency() {
+    let (mut blockchain, _) = create_runtime(
+        Blockchain::build_for_tests(),
+        create_genesis_config_with_supervisor(),
+    );
+    let keypair = blockchain.as_ref().service_keypair().clone();
+
+    // The artifact is deployed when the block is committed, and since the deployment
+    // cannot succeed, the node panics.
+    execute_transaction(
+        &mut blockchain,
+        keypair.deploy_artifact(
+            ToySupervisorService::INSTANCE_ID,
+            DeployArtifact {
+                test_service_artifact: ArtifactWithDependency.artifact_id(),
+                spec: vec![],
+            },
+        ),
+    )
+    .unwrap();
+}
+
 #[test]
 fn service_freezing() {
     let (mut blockchain, events) = create_runtime(
@@ -1093,3 +1385,213 @@ fn unloading_artifact() {
         ]
     );
 }
+
+#[test]
+fn transaction_author_is_visible_to_service() {
+    let genesis_config = create_genesis_config_builder()
+        .with_artifact(AuthorServiceImpl.artifact_id())
+        .with_instance(AuthorServiceImpl.default_instance())
+        .build();
+    let (mut blockchain, _) = create_runtime(Blockchain::build_for_tests(), genesis_config);
+    let author = |blockchain: &BlockchainMut| {
+        blockchain
+            .snapshot()
+            .for_service(AuthorServiceImpl::INSTANCE_NAME)
+            .unwrap()
+            .get_entry::<_, PublicKey>("author")
+            .get()
+    };
+
+    // `record_author` is called directly by the transaction, so the service sees its signer.
+    let keypair = KeyPair::random();
+    execute_transaction(
+        &mut blockchain,
+        keypair.record_author(AuthorServiceImpl::INSTANCE_ID, ()),
+    )
+    .unwrap();
+    assert_eq!(author(&blockchain), Some(keypair.public_key()));
+
+    // `record_author` is called by the service itself, so there is no author.
+    execute_transaction(
+        &mut blockchain,
+        keypair.call_record_author(AuthorServiceImpl::INSTANCE_ID, ()),
+    )
+    .unwrap();
+    assert_eq!(author(&blockchain), None);
+}
+
+#[test]
+fn service_modifies_entry_across_transactions() {
+    let genesis_config = create_genesis_config_builder()
+        .with_artifact(SumServiceImpl.artifact_id())
+        .with_instance(SumServiceImpl.default_instance())
+        .build();
+    let (mut blockchain, _) = create_runtime(Blockchain::build_for_tests(), genesis_config);
+    let sum = |blockchain: &BlockchainMut| {
+        blockchain
+            .snapshot()
+            .for_service(SumServiceImpl::INSTANCE_NAME)
+            .unwrap()
+            .get_entry::<_, u64>("sum")
+            .get_or_default()
+    };
+    assert_eq!(sum(&blockchain), 0);
+
+    let keypair = KeyPair::random();
+    execute_transaction(
+        &mut blockchain,
+        keypair.add_to_sum(SumServiceImpl::INSTANCE_ID, 2),
+    )
+    .unwrap();
+    assert_eq!(sum(&blockchain), 2);
+    execute_transaction(
+        &mut blockchain,
+        keypair.add_to_sum(SumServiceImpl::INSTANCE_ID, 3),
+    )
+    .unwrap();
+    assert_eq!(sum(&blockchain), 5);
+}
+
+#[test]
+fn service_reads_initial_snapshot() {
+    let genesis_config = create_genesis_config_builder()
+        .with_artifact(SumServiceImpl.artifact_id())
+        .with_instance(SumServiceImpl.default_instance())
+        .build();
+    let (mut blockchain, _) = create_runtime(Blockchain::build_for_tests(), genesis_config);
+    let sums = |blockchain: &BlockchainMut| {
+        let snapshot = blockchain.snapshot();
+        let data = snapshot.for_service(SumServiceImpl::INSTANCE_NAME).unwrap();
+        (
+            data.clone().get_entry::<_, u64>("sum").get_or_default(),
+            data.get_entry::<_, u64>("initial_sum").get(),
+        )
+    };
+
+    // Changes made in the block, including ones made by the same transaction,
+    // are not visible in the initial snapshot.
+    let keypair = KeyPair::random();
+    let txs = vec![
+        keypair.add_to_sum(SumServiceImpl::INSTANCE_ID, 2),
+        keypair.add_to_sum(SumServiceImpl::INSTANCE_ID, 3),
+    ];
+    let patch = create_block_with_transactions(&mut blockchain, txs);
+    blockchain.commit(patch, vec![]).unwrap();
+    assert_eq!(sums(&blockchain), (5, Some(0)));
+
+    // Changes from the previous blocks are visible.
+    execute_transaction(
+        &mut blockchain,
+        keypair.add_to_sum(SumServiceImpl::INSTANCE_ID, 4),
+    )
+    .unwrap();
+    assert_eq!(sums(&blockchain), (9, Some(5)));
+}
+
+#[test]
+fn service_writes_entry_with_protobuf_codec() {
+    let genesis_config = create_genesis_config_builder()
+        .with_artifact(CodecServiceImpl.artifact_id())
+        .with_instance(CodecServiceImpl.default_instance())
+        .build();
+    let (mut blockchain, _) = create_runtime(Blockchain::build_for_tests(), genesis_config);
+
+    let keypair = KeyPair::random();
+    let key = KeyPair::random().public_key();
+    execute_transaction(
+        &mut blockchain,
+        keypair.set_key(CodecServiceImpl::INSTANCE_ID, key),
+    )
+    .unwrap();
+
+    let snapshot = blockchain.snapshot();
+    let data = snapshot
+        .for_service(CodecServiceImpl::INSTANCE_NAME)
+        .unwrap();
+    let entry: CodecEntry<_, PublicKey, ProtobufCodec> =
+        CodecEntry::from_access(data.clone(), "key".into()).unwrap();
+    assert_eq!(entry.get(), Some(key));
+
+    // `exonum.crypto.PublicKey { data: [..] }`: field 1 is length-delimited.
+    let mut expected_bytes = vec![0x0a, 0x20];
+    expected_bytes.extend_from_slice(key.as_ref());
+    let raw_bytes = data.get_entry::<_, Vec<u8>>("key").get();
+    assert_eq!(raw_bytes, Some(expected_bytes));
+}
+
+#[test]
+fn service_verifies_transaction_before_execution() {
+    let genesis_config = create_genesis_config_builder()
+        .with_artifact(NonZeroServiceImpl.artifact_id())
+        .with_instance(NonZeroServiceImpl.default_instance())
+        .build();
+    let (mut blockchain, _) = create_runtime(Blockchain::build_for_tests(), genesis_config);
+    let value = |blockchain: &BlockchainMut| {
+        blockchain
+            .snapshot()
+            .for_service(NonZeroServiceImpl::INSTANCE_NAME)
+            .unwrap()
+            .get_entry::<_, u64>("value")
+            .get()
+    };
+
+    let keypair = KeyPair::random();
+    let err = execute_transaction(
+        &mut blockchain,
+        keypair.set_non_zero(NonZeroServiceImpl::INSTANCE_ID, 0),
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Service { code: 0 });
+    assert!(err.description().contains("is zero"));
+    assert_eq!(value(&blockchain), None);
+
+    execute_transaction(
+        &mut blockchain,
+        keypair.set_non_zero(NonZeroServiceImpl::INSTANCE_ID, 5),
+    )
+    .unwrap();
+    assert_eq!(value(&blockchain), Some(5));
+}
+
+#[test]
+fn service_reads_data_of_another_instance() {
+    let genesis_config = create_genesis_config_builder()
+        .with_artifact(TestServiceImplV2.artifact_id())
+        .with_artifact(PeerServiceImpl.artifact_id())
+        .with_instance(TestServiceImplV2.default_instance())
+        .with_instance(PeerServiceImpl.default_instance())
+        .build();
+    let (mut blockchain, _) = create_runtime(Blockchain::build_for_tests(), genesis_config);
+    let keypair = KeyPair::random();
+    let copy_peer_entry = |blockchain: &mut BlockchainMut, peer_name: &str| {
+        execute_transaction(
+            blockchain,
+            keypair.copy_peer_entry(PeerServiceImpl::INSTANCE_ID, peer_name.to_owned()),
+        )
+        .unwrap();
+        blockchain
+            .snapshot()
+            .for_service(PeerServiceImpl::INSTANCE_NAME)
+            .unwrap()
+            .get_entry::<_, u64>("peer_entry")
+            .get()
+    };
+
+    // The other instance has not written its entry yet.
+    assert_eq!(
+        copy_peer_entry(&mut blockchain, TestServiceImplV2::INSTANCE_NAME),
+        None
+    );
+    // The instance with the specified name does not exist.
+    assert_eq!(copy_peer_entry(&mut blockchain, "non-existing"), None);
+
+    execute_transaction(
+        &mut blockchain,
+        keypair.method_b(TestServiceImplV2::INSTANCE_ID, 1),
+    )
+    .unwrap();
+    assert_eq!(
+        copy_peer_entry(&mut blockchain, TestServiceImplV2::INSTANCE_NAME),
+        Some(43)
+    );
+}
@@ -198,3 +198,30 @@ fn access_to_service_schema() {
     let schema: Schema<_> = snapshot.service_schema("token").unwrap();
     assert_eq!(schema.wallets.values().sum::<u64>(), 300);
 }
+
+#[test]
+fn service_state_size() {
+    let mut blockchain = create_blockchain();
+    let fork = blockchain.fork();
+    {
+        let mut schema = SchemaImpl::from_root(Prefixed::new("token", &fork)).unwrap();
+        // The entry key is empty; the value is 10 bytes long.
+        schema.private.set("Some value".to_owned());
+        // Each entry in the map has a 4-byte key and a 3-byte value.
+        let mut map = fork.get_map("token.plain_map");
+        map.put(&1_u32, "foo".to_owned());
+        map.put(&2_u32, "bar".to_owned());
+        // Data of other services is not taken into account.
+        fork.get_entry("other.entry").set("Other value".to_owned());
+    }
+    blockchain.merge(fork.into_patch()).unwrap();
+
+    let snapshot = blockchain.snapshot();
+    let instance = InstanceDescriptor::new(TokenService::INSTANCE_ID, TokenService::INSTANCE_NAME);
+    assert_eq!(
+        TokenService.state_size(&instance, &snapshot),
+        10 + 2 * (4 + 3)
+    );
+    let instance = InstanceDescriptor::new(OldService::INSTANCE_ID, OldService::INSTANCE_NAME);
+    assert_eq!(OldService.state_size(&instance, &snapshot), 0);
+}
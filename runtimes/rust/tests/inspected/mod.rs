@@ -265,6 +265,16 @@ impl<T: Runtime> Runtime for Inspected<T> {
         self.runtime.execute(context, method_id, arguments)
     }
 
+    fn method_name(
+        &self,
+        instance_id: InstanceId,
+        interface_name: &str,
+        method_id: MethodId,
+    ) -> Option<&str> {
+        self.runtime
+            .method_name(instance_id, interface_name, method_id)
+    }
+
     fn before_transactions(&self, context: ExecutionContext<'_>) -> Result<(), ExecutionError> {
         let height = context.data().for_core().next_height();
         self.events.push(RuntimeEvent::BeforeTransactions(
@@ -103,6 +103,14 @@ impl<'a, T: BinaryValue> Interface<'a> for dyn Configure<Params = T> {
             _ => Err(CommonError::NoSuchMethod.into()),
         }
     }
+
+    fn method_name(method: MethodId) -> Option<&'static str> {
+        match method {
+            VERIFY_CONFIG_METHOD_ID => Some("verify_config"),
+            APPLY_CONFIG_METHOD_ID => Some("apply_config"),
+            _ => None,
+        }
+    }
 }
 
 // Makeshift replacement for generic stubbing, which is made difficult by the existence
@@ -177,6 +177,8 @@ use crate::{configure::ConfigureMut, mode::Mode};
 
 pub mod api;
 pub mod mode;
+#[cfg(feature = "testkit")]
+pub mod testkit;
 
 mod configure;
 mod errors;
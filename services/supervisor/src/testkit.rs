@@ -0,0 +1,73 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Testkit helpers for the supervisor service. Available with the `testkit` crate feature.
+
+use exonum::runtime::SnapshotExt;
+use exonum_testkit::TestKit;
+
+use crate::{ConfigPropose, Schema, Supervisor};
+
+/// Extension trait for `TestKit` simplifying testing of configuration changes.
+pub trait SupervisorTestKitExt {
+    /// Returns the configuration proposal which is scheduled, but not yet applied,
+    /// or `None` if there is no such proposal. The height at which the proposal
+    /// will be applied is available via the `actual_from` field.
+    ///
+    /// The proposal is read from the supervisor with the default name, `Supervisor::NAME`.
+    ///
+    /// # Panics
+    ///
+    /// If the supervisor is not deployed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exonum::{helpers::Height, runtime::SUPERVISOR_INSTANCE_ID};
+    /// use exonum_supervisor::{
+    ///     testkit::SupervisorTestKitExt, ConfigPropose, Supervisor, SupervisorInterface,
+    /// };
+    /// use exonum_testkit::TestKitBuilder;
+    ///
+    /// let mut testkit = TestKitBuilder::validator()
+    ///     .with(Supervisor::simple())
+    ///     .build();
+    /// assert!(testkit.pending_configuration().is_none());
+    ///
+    /// let consensus_config = testkit.consensus_config();
+    /// let propose = ConfigPropose::new(0, Height(5)).consensus_config(consensus_config);
+    /// let tx = testkit
+    ///     .us()
+    ///     .service_keypair()
+    ///     .propose_config_change(SUPERVISOR_INSTANCE_ID, propose);
+    /// testkit.create_block_with_transaction(tx);
+    ///
+    /// let pending = testkit.pending_configuration().unwrap();
+    /// assert_eq!(pending.actual_from, Height(5));
+    /// ```
+    fn pending_configuration(&self) -> Option<ConfigPropose>;
+}
+
+impl SupervisorTestKitExt for TestKit {
+    fn pending_configuration(&self) -> Option<ConfigPropose> {
+        let snapshot = self.snapshot();
+        let schema: Schema<_> = snapshot
+            .service_schema(Supervisor::NAME)
+            .unwrap_or_else(|err| panic!("Cannot access supervisor: {}", err));
+        schema
+            .pending_proposal
+            .get()
+            .map(|proposal| proposal.config_propose)
+    }
+}
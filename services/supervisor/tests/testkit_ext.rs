@@ -0,0 +1,57 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for testkit helpers of the supervisor service.
+
+#![cfg(feature = "testkit")]
+
+use exonum::{helpers::Height, runtime::SUPERVISOR_INSTANCE_ID};
+use exonum_testkit::TestKitBuilder;
+
+use exonum_supervisor::{
+    testkit::SupervisorTestKitExt, ConfigPropose, Supervisor, SupervisorInterface,
+};
+
+#[test]
+fn pending_configuration() {
+    let mut testkit = TestKitBuilder::validator()
+        .with(Supervisor::simple())
+        .build();
+    assert!(testkit.pending_configuration().is_none());
+
+    let cfg_change_height = Height(5);
+    let mut new_consensus_config = testkit.consensus_config();
+    new_consensus_config.min_propose_timeout += 1;
+    let config_propose =
+        ConfigPropose::new(0, cfg_change_height).consensus_config(new_consensus_config.clone());
+    let tx = testkit
+        .us()
+        .service_keypair()
+        .propose_config_change(SUPERVISOR_INSTANCE_ID, config_propose.clone());
+    let block = testkit.create_block_with_transaction(tx);
+    block[0].status().expect("Proposal should be accepted");
+
+    let pending = testkit.pending_configuration().unwrap();
+    assert_eq!(pending, config_propose);
+    assert_eq!(pending.actual_from, cfg_change_height);
+
+    // The proposal remains pending until the activation height.
+    testkit.create_blocks_until(cfg_change_height.previous());
+    assert!(testkit.pending_configuration().is_some());
+    assert_ne!(testkit.consensus_config(), new_consensus_config);
+
+    testkit.create_block();
+    assert!(testkit.pending_configuration().is_none());
+    assert_eq!(testkit.consensus_config(), new_consensus_config);
+}
@@ -23,13 +23,26 @@
 //! |-------------|-------|
 //! | Path        | `/api/services/{INSTANCE_NAME}/v1/current_time` |
 //! | Method      | GET   |
-//! | Query type  | - |
+//! | Query type  | [`CurrentTimeQuery`] |
 //! | Return type | `Option<DateTime<Utc>>` |
 //!
 //! Returns the current stored time available in `exonum-time` service.
 //! `None` will be returned if there is no enough data to provide a trusted
 //! time yet.
 //!
+//! The representation of the time is controlled by the optional `format` query parameter
+//! (e.g., `?format=millis`), which accepts values of [`TimeFormat`]. By default, the time
+//! is returned as an RFC 3339 string.
+//!
+//! If the `with_proof=true` query parameter is specified, the endpoint returns
+//! [`TimeWithProof`] instead, which allows light clients to verify the time against
+//! the state hash of the latest committed block. In this case, the `format` parameter
+//! is ignored.
+//!
+//! [`CurrentTimeQuery`]: struct.CurrentTimeQuery.html
+//! [`TimeFormat`]: enum.TimeFormat.html
+//! [`TimeWithProof`]: struct.TimeWithProof.html
+//!
 //! ```
 //! # use chrono::{DateTime, Utc};
 //! # use exonum::{helpers::Height, runtime::InstanceId};
@@ -67,6 +80,21 @@
 //! # }
 //! ```
 //!
+//! ## Get Time Provider Info
+//!
+//! | Property    | Value |
+//! |-------------|-------|
+//! | Path        | `/api/services/{INSTANCE_NAME}/v1/provider_info` |
+//! | Method      | GET   |
+//! | Query type  | - |
+//! | Return type | [`ProviderInfo`] |
+//!
+//! Returns the description of the [`TimeProvider`] used by the node to report time,
+//! e.g., `{ "kind": "system" }`.
+//!
+//! [`ProviderInfo`]: struct.ProviderInfo.html
+//! [`TimeProvider`]: trait.TimeProvider.html
+//!
 //! # Private API Endpoints
 //!
 //! ## Get Validators Times
@@ -125,18 +153,126 @@
 //! Similar to [`/validator_times`] endpoint, but returns a list of
 //! `ValidatorTime` objects for every node that participated in the
 //! time consolidating process (some of them may not be validators
-//! anymore).
+//! anymore). Times of such nodes are not used to compute the consolidated time.
+//! If the service instance retains tombstones (see [`TimeConfig::with_tombstones`]),
+//! these nodes are marked with `active: false`. The times are serialized
+//! directly from the service storage without an intermediate list of records;
+//! the response body is still assembled in full before it is sent.
 //!
 //! [`ValidatorTime`]: struct.ValidatorTime.html
 //! [`/validator_times`]: #get-validator-times
+//! [`TimeConfig::with_tombstones`]: ../struct.TimeConfig.html#method.with_tombstones
+//!
+//! ## Get Page of All Times
+//!
+//! | Property    | Value |
+//! |-------------|-------|
+//! | Path        | `/api/services/{INSTANCE_NAME}/v1/validators_times/page` |
+//! | Method      | GET   |
+//! | Query type  | [`ValidatorsTimesQuery`] |
+//! | Return type | [`ValidatorsTimesPage`] |
+//!
+//! Paginated version of the [`/validators_times/all`] endpoint. Times are sorted
+//! by the public key of the node; the page is specified with the `offset` and `limit`
+//! query parameters (e.g., `?offset=10&limit=10`). The `limit` cannot exceed
+//! [`MAX_VALIDATORS_TIMES_PER_PAGE`], which is also the default limit. The response
+//! contains the total number of times, so that the client can request remaining pages.
+//!
+//! [`ValidatorsTimesQuery`]: struct.ValidatorsTimesQuery.html
+//! [`ValidatorsTimesPage`]: struct.ValidatorsTimesPage.html
+//! [`/validators_times/all`]: #get-all-times
+//! [`MAX_VALIDATORS_TIMES_PER_PAGE`]: constant.MAX_VALIDATORS_TIMES_PER_PAGE.html
+//!
+//! ## Get Validators Time Drift
+//!
+//! | Property    | Value |
+//! |-------------|-------|
+//! | Path        | `/api/services/{INSTANCE_NAME}/v1/validators_times/drift` |
+//! | Method      | GET   |
+//! | Query type  | - |
+//! | Return type | Vec<[`ValidatorTimeDrift`]> |
+//!
+//! Returns the list of `ValidatorTimeDrift` objects for every node that is currently
+//! a validator. Each object contains the time reported by the validator and its signed
+//! offset from the current consolidated time in milliseconds. The list is sorted
+//! by the absolute value of the drift in the descending order; validators with the unknown
+//! drift (e.g., ones that have not reported time yet) are listed last.
+//!
+//! [`ValidatorTimeDrift`]: struct.ValidatorTimeDrift.html
+//!
+//! ## Get Ranked Validators Times
+//!
+//! | Property    | Value |
+//! |-------------|-------|
+//! | Path        | `/api/services/{INSTANCE_NAME}/v1/validators_times/ranked` |
+//! | Method      | GET   |
+//! | Query type  | - |
+//! | Return type | Vec<[`ValidatorTime`]> |
+//!
+//! Returns the same list as `v1/validators_times`, sorted by the reported time in the
+//! descending order. Validators that have not reported time yet are listed last.
+//! See [`TimeSchema::validators_by_time`] for details.
+//!
+//! [`ValidatorTime`]: struct.ValidatorTime.html
+//! [`TimeSchema::validators_by_time`]: ../struct.TimeSchema.html#method.validators_by_time
+//!
+//! ## Get Validators Time Delta
+//!
+//! | Property    | Value |
+//! |-------------|-------|
+//! | Path        | `/api/services/{INSTANCE_NAME}/v1/validators_times/delta` |
+//! | Method      | GET   |
+//! | Query type  | [`ValidatorsTimesDeltaQuery`] |
+//! | Return type | Vec<[`ValidatorTimeDelta`]> |
+//!
+//! Returns how much the time of each validator has advanced between the blocks at the `from`
+//! and `to` heights (e.g., `?from=10&to=20`), in milliseconds. A zero delta over a long
+//! range of heights indicates a stuck clock. The list includes current validators and
+//! nodes with times known at either height, sorted by the public key; the delta
+//! is `null` if the time of the node is unknown at either height.
+//!
+//! The endpoint requires the history of times, which is recorded only if the service
+//! is created with [`TimeServiceFactory::with_times_history`]. The request fails
+//! if the history is not available for either height.
+//!
+//! [`ValidatorsTimesDeltaQuery`]: struct.ValidatorsTimesDeltaQuery.html
+//! [`ValidatorTimeDelta`]: struct.ValidatorTimeDelta.html
+//! [`TimeServiceFactory::with_times_history`]: ../struct.TimeServiceFactory.html#method.with_times_history
+//!
+//! ## Report Time
+//!
+//! | Property    | Value |
+//! |-------------|-------|
+//! | Path        | `/api/services/{INSTANCE_NAME}/v1/time` |
+//! | Method      | POST  |
+//! | Body type   | [`TxTime`] |
+//! | Return type | `Hash` |
+//!
+//! Creates a [`TxTime`] transaction with the specified time (e.g., `{ "time": "..." }`),
+//! signs it with the service key of the node and broadcasts it, the same way the node
+//! reports its time after each block. Returns the hash of the transaction. The request
+//! fails if the node is not a validator.
+//!
+//! [`TxTime`]: struct.TxTime.html
 
 use chrono::{DateTime, Utc};
-use exonum::crypto::PublicKey;
+use exonum::{
+    blockchain::{IndexProof, ValidatorKeys},
+    crypto::{Hash, PublicKey},
+    helpers::Height,
+};
 use exonum_api::Result;
 use exonum_rust_runtime::api;
+use serde::Serializer;
 use serde_derive::{Deserialize, Serialize};
 
-use crate::TimeSchema;
+use std::{collections::BTreeSet, sync::Arc};
+
+use crate::{ProviderInfo, TimeOracleInterface, TimeProvider, TimeSchema, TxTime};
+
+/// The maximum number of validator times returned on a single page
+/// by the `v1/validators_times/page` endpoint.
+pub const MAX_VALIDATORS_TIMES_PER_PAGE: usize = 1_000;
 
 /// Structure for saving public key of the validator and last known local time.
 #[derive(Debug, Serialize, Deserialize)]
@@ -145,6 +281,250 @@ pub struct ValidatorTime {
     pub public_key: PublicKey,
     /// Time of the validator.
     pub time: Option<DateTime<Utc>>,
+    /// Is the node a validator according to the current consensus configuration?
+    /// Times of the nodes which are not validators anymore are not used to compute
+    /// the consolidated time. The flag is only reported if the service instance retains
+    /// such times as tombstones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+}
+
+impl ValidatorTime {
+    /// Creates a time record for a node which has reported `time`.
+    fn reported(
+        public_key: PublicKey,
+        time: DateTime<Utc>,
+        validator_keys: &[ValidatorKeys],
+        tombstones: bool,
+    ) -> Self {
+        let active = validator_keys
+            .iter()
+            .any(|validator| validator.service_key == public_key);
+        Self {
+            public_key,
+            time: Some(time),
+            active: if tombstones { Some(active) } else { None },
+        }
+    }
+
+    /// Creates a time record for a current validator.
+    fn current(public_key: PublicKey, time: Option<DateTime<Utc>>, tombstones: bool) -> Self {
+        Self {
+            public_key,
+            time,
+            active: if tombstones { Some(true) } else { None },
+        }
+    }
+}
+
+/// Times of all validators returned by the `v1/validators_times/all` endpoint.
+///
+/// The times are serialized as a sequence directly from the storage iterator rather than
+/// being collected into a vector beforehand. Note that the response is not streamed:
+/// the serialized body is buffered by the REST layer.
+#[derive(Debug)]
+pub(crate) struct AllValidatorsTimes {
+    state: api::ServiceApiState,
+}
+
+impl serde::Serialize for AllValidatorsTimes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let validator_keys = self
+            .state
+            .data()
+            .for_core()
+            .consensus_config()
+            .validator_keys;
+        let schema = TimeSchema::new(self.state.service_data());
+        let tombstones = schema.config().tombstones;
+        let times = schema.validators_times.iter().map(|(public_key, time)| {
+            ValidatorTime::reported(public_key, time, &validator_keys, tombstones)
+        });
+        serializer.collect_seq(times)
+    }
+}
+
+/// Representation of the time returned by the `v1/current_time` endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum TimeFormat {
+    /// String in the RFC 3339 format, e.g., `"2020-03-31T12:00:00Z"`. This is the default format.
+    Rfc3339,
+    /// Integer number of milliseconds since the Unix epoch.
+    Millis,
+    /// Object with `secs_since_epoch` and `nanos_since_epoch` fields, which is how `serde`
+    /// serializes `SystemTime`.
+    Struct,
+}
+
+impl Default for TimeFormat {
+    fn default() -> Self {
+        Self::Rfc3339
+    }
+}
+
+/// Query parameters of the `v1/current_time` endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct CurrentTimeQuery {
+    /// Representation of the returned time. The default value is `TimeFormat::Rfc3339`.
+    #[serde(default)]
+    pub format: TimeFormat,
+    /// Whether to return the time together with the proof of its authenticity.
+    /// The default value is `false`.
+    #[serde(default)]
+    pub with_proof: bool,
+}
+
+impl CurrentTimeQuery {
+    /// Creates a query with the specified time format.
+    pub fn new(format: TimeFormat) -> Self {
+        Self {
+            format,
+            with_proof: false,
+        }
+    }
+
+    /// Creates a query for the time together with the proof of its authenticity.
+    pub fn with_proof() -> Self {
+        Self {
+            format: TimeFormat::default(),
+            with_proof: true,
+        }
+    }
+}
+
+/// Consolidated time together with the proof of its authenticity, returned
+/// by the `v1/current_time` endpoint if the proof is requested.
+///
+/// To verify the time, the client should check the `proof` against the keys of the validators
+/// via [`IndexProof::verify`]. The proof includes the header of the latest committed block,
+/// and the index hash returned by the verification should be equal to the object hash
+/// of the `time` (or to the default hash if `time` is `None`).
+///
+/// [`IndexProof::verify`]: https://docs.rs/exonum/latest/exonum/blockchain/struct.IndexProof.html#method.verify
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct TimeWithProof {
+    /// Consolidated time, or `None` if the time is not set yet.
+    pub time: Option<DateTime<Utc>>,
+    /// Proof of authenticity for the index storing the consolidated time. `None` if the index
+    /// has not been initialized yet, which may be the case before the time is set.
+    pub proof: Option<IndexProof>,
+}
+
+/// Response of the `v1/current_time` endpoint.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub(crate) enum CurrentTime {
+    Formatted(Option<FormattedTime>),
+    WithProof(TimeWithProof),
+}
+
+/// Time in the representation selected by `TimeFormat`.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub(crate) enum FormattedTime {
+    Rfc3339(DateTime<Utc>),
+    Millis(i64),
+    Struct {
+        secs_since_epoch: i64,
+        nanos_since_epoch: u32,
+    },
+}
+
+impl FormattedTime {
+    fn new(time: DateTime<Utc>, format: TimeFormat) -> Self {
+        match format {
+            TimeFormat::Rfc3339 => Self::Rfc3339(time),
+            TimeFormat::Millis => Self::Millis(time.timestamp_millis()),
+            TimeFormat::Struct => Self::Struct {
+                secs_since_epoch: time.timestamp(),
+                nanos_since_epoch: time.timestamp_subsec_nanos(),
+            },
+        }
+    }
+}
+
+/// Query parameters of the `v1/validators_times/page` endpoint.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ValidatorsTimesQuery {
+    /// Number of times to skip. The default value is 0.
+    #[serde(default)]
+    pub offset: usize,
+    /// Maximum number of times on the page. Should not be greater than
+    /// `MAX_VALIDATORS_TIMES_PER_PAGE`, which is also the default value.
+    pub limit: Option<usize>,
+}
+
+impl ValidatorsTimesQuery {
+    /// Creates a query for the page with the specified offset and limit.
+    pub fn new(offset: usize, limit: usize) -> Self {
+        Self {
+            offset,
+            limit: Some(limit),
+        }
+    }
+}
+
+/// Page of validator times returned by the `v1/validators_times/page` endpoint.
+#[derive(Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ValidatorsTimesPage {
+    /// Times on the page, sorted by the public key of the node.
+    pub items: Vec<ValidatorTime>,
+    /// Total number of times stored by the service.
+    pub total: usize,
+    /// Number of times skipped before the page.
+    pub offset: usize,
+    /// Maximum number of times on the page.
+    pub limit: usize,
+}
+
+/// Time reported by a validator together with its offset from the consolidated time.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ValidatorTimeDrift {
+    /// Public key of the validator.
+    pub public_key: PublicKey,
+    /// Time of the validator.
+    pub time: Option<DateTime<Utc>>,
+    /// Signed offset of the validator time from the consolidated time, in milliseconds.
+    /// Positive values mean that the validator time is ahead of the consolidated time.
+    ///
+    /// `None` if either the validator time or the consolidated time is unknown.
+    pub drift: Option<i64>,
+}
+
+/// Query parameters of the `v1/validators_times/delta` endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ValidatorsTimesDeltaQuery {
+    /// Height of the block to compute the delta from.
+    pub from: Height,
+    /// Height of the block to compute the delta to.
+    pub to: Height,
+}
+
+impl ValidatorsTimesDeltaQuery {
+    /// Creates a query for the delta between the specified heights.
+    pub fn new(from: Height, to: Height) -> Self {
+        Self { from, to }
+    }
+}
+
+/// Advance of a node time between two heights, returned by the `v1/validators_times/delta`
+/// endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ValidatorTimeDelta {
+    /// Public key of the node.
+    pub public_key: PublicKey,
+    /// Advance of the node time between the heights, in milliseconds.
+    ///
+    /// `None` if the node time is unknown at either height.
+    pub delta: Option<i64>,
 }
 
 /// Implement the public API for Exonum time.
@@ -155,16 +535,33 @@ impl PublicApi {
     /// Endpoint for getting time values for all validators.
     async fn current_time(
         state: api::ServiceApiState,
-        _query: (),
-    ) -> Result<Option<DateTime<Utc>>> {
-        Ok(TimeSchema::new(state.service_data()).time.get())
+        query: CurrentTimeQuery,
+    ) -> Result<CurrentTime> {
+        let time = TimeSchema::new(state.service_data()).consolidated_time();
+        let response = if query.with_proof {
+            let proof = state.data().proof_for_service_index("time");
+            CurrentTime::WithProof(TimeWithProof { time, proof })
+        } else {
+            CurrentTime::Formatted(time.map(|time| FormattedTime::new(time, query.format)))
+        };
+        Ok(response)
+    }
+
+    /// Endpoint for getting information about the time provider of the node.
+    async fn provider_info(time_provider: Arc<dyn TimeProvider>) -> Result<ProviderInfo> {
+        Ok(time_provider.describe())
     }
 
     /// Extend API.
-    pub fn wire(builder: &mut api::ServiceApiBuilder) {
+    pub fn wire(builder: &mut api::ServiceApiBuilder, time_provider: Arc<dyn TimeProvider>) {
         builder
             .public_scope()
-            .endpoint("v1/current_time", Self::current_time);
+            .endpoint("v1/current_time", Self::current_time)
+            .endpoint("v1/provider_info", {
+                move |_state: api::ServiceApiState, _query: ()| {
+                    Self::provider_info(time_provider.clone())
+                }
+            });
     }
 }
 
@@ -173,19 +570,51 @@ impl PublicApi {
 pub(crate) struct PrivateApi;
 
 impl PrivateApi {
-    /// Endpoint for getting time values for all validators.
-    pub async fn all_validators_times(state: api::ServiceApiState) -> Result<Vec<ValidatorTime>> {
+    /// Returns all available times of the validators.
+    fn validators_times(state: &api::ServiceApiState) -> Vec<ValidatorTime> {
+        let validator_keys = state.data().for_core().consensus_config().validator_keys;
         let schema = TimeSchema::new(state.service_data());
-        // All available times of the validators.
-        let validators_times = schema
+        let tombstones = schema.config().tombstones;
+        schema
             .validators_times
             .iter()
-            .map(|(public_key, time)| ValidatorTime {
-                public_key,
-                time: Some(time),
+            .map(|(public_key, time)| {
+                ValidatorTime::reported(public_key, time, &validator_keys, tombstones)
             })
-            .collect::<Vec<_>>();
-        Ok(validators_times)
+            .collect()
+    }
+
+    /// Endpoint for getting time values for all validators.
+    pub async fn all_validators_times(state: api::ServiceApiState) -> Result<AllValidatorsTimes> {
+        Ok(AllValidatorsTimes { state })
+    }
+
+    /// Endpoint for getting a page of time values for all validators.
+    pub async fn validators_times_page(
+        state: api::ServiceApiState,
+        query: ValidatorsTimesQuery,
+    ) -> Result<ValidatorsTimesPage> {
+        let limit = query.limit.unwrap_or(MAX_VALIDATORS_TIMES_PER_PAGE);
+        if limit > MAX_VALIDATORS_TIMES_PER_PAGE {
+            return Err(api::Error::bad_request()
+                .title("Invalid validators times request")
+                .detail(format!(
+                    "Max number of times per page exceeded ({})",
+                    MAX_VALIDATORS_TIMES_PER_PAGE
+                )));
+        }
+
+        let mut times = Self::validators_times(&state);
+        // Entries of the proof map are ordered by key hashes, so we sort them explicitly.
+        times.sort_by(|x, y| x.public_key.cmp(&y.public_key));
+        let total = times.len();
+        let items = times.into_iter().skip(query.offset).take(limit).collect();
+        Ok(ValidatorsTimesPage {
+            items,
+            total,
+            offset: query.offset,
+            limit,
+        })
     }
 
     /// Endpoint for getting time values for current validators.
@@ -194,19 +623,127 @@ impl PrivateApi {
     ) -> Result<Vec<ValidatorTime>> {
         let validator_keys = state.data().for_core().consensus_config().validator_keys;
         let schema = TimeSchema::new(state.service_data());
+        let tombstones = schema.config().tombstones;
 
         // Times of the current validators.
         // `None` if the time of the validator is unknown.
         let validators_times = validator_keys
             .iter()
-            .map(|validator| ValidatorTime {
-                public_key: validator.service_key,
-                time: schema.validators_times.get(&validator.service_key),
+            .map(|validator| {
+                let time = schema.validator_time(&validator.service_key);
+                ValidatorTime::current(validator.service_key, time, tombstones)
             })
             .collect::<Vec<_>>();
         Ok(validators_times)
     }
 
+    /// Endpoint for getting time values for current validators sorted by time.
+    pub async fn ranked_validators_times(
+        state: api::ServiceApiState,
+    ) -> Result<Vec<ValidatorTime>> {
+        let validator_keys = state.data().for_core().consensus_config().validator_keys;
+        let schema = TimeSchema::new(state.service_data());
+        let tombstones = schema.config().tombstones;
+        let times = schema
+            .validators_by_time(&validator_keys)
+            .into_iter()
+            .map(|(public_key, time)| ValidatorTime::current(public_key, time, tombstones))
+            .collect();
+        Ok(times)
+    }
+
+    /// Endpoint for getting time drifts of current validators.
+    pub async fn validators_time_drift(
+        state: api::ServiceApiState,
+    ) -> Result<Vec<ValidatorTimeDrift>> {
+        let validator_keys = state.data().for_core().consensus_config().validator_keys;
+        let schema = TimeSchema::new(state.service_data());
+        let consolidated_time = schema.consolidated_time();
+
+        let mut drifts = validator_keys
+            .iter()
+            .map(|validator| {
+                let time = schema.validator_time(&validator.service_key);
+                let drift = time
+                    .zip(consolidated_time)
+                    .map(|(time, consolidated_time)| (time - consolidated_time).num_milliseconds());
+                ValidatorTimeDrift {
+                    public_key: validator.service_key,
+                    time,
+                    drift,
+                }
+            })
+            .collect::<Vec<_>>();
+        // Sort by the absolute drift in the descending order, with unknown drifts going last.
+        drifts.sort_by_key(|validator| std::cmp::Reverse(validator.drift.map(i64::abs)));
+        Ok(drifts)
+    }
+
+    /// Endpoint for getting advances of node times between two heights.
+    pub async fn validators_times_delta(
+        state: api::ServiceApiState,
+        query: ValidatorsTimesDeltaQuery,
+    ) -> Result<Vec<ValidatorTimeDelta>> {
+        if query.from > query.to {
+            return Err(api::Error::bad_request()
+                .title("Invalid validators times delta request")
+                .detail("`from` height is greater than `to` height"));
+        }
+
+        let core_schema = state.data().for_core();
+        let schema = TimeSchema::new(state.service_data());
+        let history_start = schema.history_start().ok_or_else(|| {
+            api::Error::bad_request()
+                .title("Invalid validators times delta request")
+                .detail("History of validators times is not recorded")
+        })?;
+        let height = core_schema.height();
+        if query.from < history_start || query.to > height {
+            return Err(api::Error::bad_request()
+                .title("Invalid validators times delta request")
+                .detail(format!(
+                    "History of validators times is available for heights {}..={}",
+                    history_start, height
+                )));
+        }
+
+        // Current validators and all nodes with times known at either height.
+        let mut keys = core_schema
+            .consensus_config()
+            .validator_keys
+            .iter()
+            .map(|validator| validator.service_key)
+            .collect::<BTreeSet<_>>();
+        keys.extend(schema.validators_times_history.get(&query.from.0).keys());
+        keys.extend(schema.validators_times_history.get(&query.to.0).keys());
+
+        let deltas = keys
+            .into_iter()
+            .map(|public_key| {
+                let from = schema.validator_time_at(&public_key, query.from);
+                let to = schema.validator_time_at(&public_key, query.to);
+                let delta = from
+                    .zip(to)
+                    .map(|(from, to)| (to - from).num_milliseconds());
+                ValidatorTimeDelta { public_key, delta }
+            })
+            .collect();
+        Ok(deltas)
+    }
+
+    /// Endpoint for reporting time on behalf of the node.
+    pub async fn report_time(state: api::ServiceApiState, arg: TxTime) -> Result<Hash> {
+        let broadcaster = state.broadcaster().ok_or_else(|| {
+            api::Error::bad_request()
+                .title("Invalid time report")
+                .detail("Node is not a validator")
+        })?;
+        broadcaster
+            .report_time((), arg)
+            .await
+            .map_err(|err| api::Error::internal(err).title("Time report failed"))
+    }
+
     /// Wires time service API endpoints.
     pub fn wire(builder: &mut api::ServiceApiBuilder) {
         builder
@@ -216,6 +753,15 @@ impl PrivateApi {
             })
             .endpoint("v1/validators_times/all", {
                 move |state: api::ServiceApiState, _query: ()| Self::all_validators_times(state)
-            });
+            })
+            .endpoint("v1/validators_times/page", Self::validators_times_page)
+            .endpoint("v1/validators_times/drift", {
+                move |state: api::ServiceApiState, _query: ()| Self::validators_time_drift(state)
+            })
+            .endpoint("v1/validators_times/ranked", {
+                move |state: api::ServiceApiState, _query: ()| Self::ranked_validators_times(state)
+            })
+            .endpoint("v1/validators_times/delta", Self::validators_times_delta)
+            .endpoint_mut("v1/time", Self::report_time);
     }
 }
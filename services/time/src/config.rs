@@ -0,0 +1,344 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Configuration of the time service instance.
+
+use anyhow::ensure;
+use chrono::{DateTime, Duration, Utc};
+use exonum::crypto::PublicKey;
+use exonum_derive::{BinaryValue, ObjectHash};
+use exonum_proto::ProtobufConvert;
+
+use std::{collections::BTreeMap, convert::TryFrom};
+
+use crate::proto;
+
+/// Configuration of the time service instance, which is passed to the service
+/// when the instance is initialized and is stored in the service schema.
+///
+/// An empty configuration (e.g., `()` passed as instance parameters) corresponds
+/// to the default one.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(BinaryValue, ObjectHash)]
+#[non_exhaustive]
+pub struct TimeConfig {
+    /// Maximum number of retained times of past validators.
+    pub history_limit: Option<usize>,
+    /// Consolidated time set when the service instance is initialized.
+    pub genesis_time: Option<DateTime<Utc>>,
+    /// Maximum distance from the median of reported times for a time to be consolidated.
+    pub outlier_window: Option<Duration>,
+    /// Policy determining when the consolidated time is recomputed.
+    pub recompute_policy: RecomputePolicy,
+    /// Weights of validators' times keyed by validators' service keys. If empty, all validators
+    /// have the same weight.
+    pub weights: BTreeMap<PublicKey, u64>,
+    /// Maximum advance of the consolidated time in a single update.
+    pub max_step: Option<Duration>,
+    /// Minimum interval between consecutive times reported by a validator, measured
+    /// by the reported times.
+    pub min_submit_interval: Option<Duration>,
+    /// Minimum number of validators that must report time before it is consolidated.
+    pub min_reporters: Option<usize>,
+    /// Whether times of past validators are retained as tombstones.
+    pub tombstones: bool,
+}
+
+impl TimeConfig {
+    /// Limits the number of retained times of past validators, i.e., keys that are
+    /// not present in the current consensus configuration. After each block, the times
+    /// of past validators exceeding the limit are removed, starting from the ones
+    /// that have reported their time at the lowest height. Times of the current validators
+    /// are always retained. The limit does not apply if [tombstones] are retained.
+    ///
+    /// By default, times of past validators are retained indefinitely.
+    ///
+    /// [tombstones]: #method.with_tombstones
+    ///
+    /// # Panics
+    ///
+    /// Panics if the limit is 0.
+    pub fn with_history_limit(mut self, max: usize) -> Self {
+        assert_ne!(max, 0, "History limit must be positive");
+        self.history_limit = Some(max);
+        self
+    }
+
+    /// Sets the consolidated time when a service instance is initialized (e.g., in the genesis
+    /// block), so that the time is available before validators report their times.
+    /// The time is then updated by validators' reports as usual; since the consolidated time
+    /// never goes backwards, reports earlier than the genesis time do not override it.
+    /// The time may be specified as `DateTime<Utc>` or `SystemTime`.
+    ///
+    /// By default, the consolidated time is not set until enough validators report their times.
+    pub fn with_genesis_time(mut self, time: impl Into<DateTime<Utc>>) -> Self {
+        self.genesis_time = Some(time.into());
+        self
+    }
+
+    /// Discards validators' times more than `window` away from their median before
+    /// consolidating the time. For an even number of times, the lower of the two middle
+    /// times is used as the median. The consolidated time is then selected from the remaining
+    /// times as usual. Discarded times are retained in the schema and are taken into account
+    /// when the time is consolidated next time. The window is stored with the millisecond
+    /// precision.
+    ///
+    /// The number of tolerated Byzantine validators, `max_byzantine_nodes = (n - 1) / 3`,
+    /// is determined by the total number `n` of validators and is not affected by
+    /// discarded times. Thus, the time is only updated if more than `2 * max_byzantine_nodes`
+    /// times remain within the window; discarding outliers can delay consolidation,
+    /// but cannot make it rely on fewer honest validators.
+    ///
+    /// By default, no times are discarded.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the window is shorter than a millisecond.
+    pub fn with_outlier_window(mut self, window: Duration) -> Self {
+        assert!(
+            window >= Duration::milliseconds(1),
+            "Outlier window must be at least a millisecond"
+        );
+        self.outlier_window = Some(window);
+        self
+    }
+
+    /// Sets the policy determining when the consolidated time is recomputed. With
+    /// [`RecomputePolicy::EveryNBlocks`], the time is consolidated once per several blocks
+    /// rather than after each transaction, which saves computations for large validator sets.
+    ///
+    /// By default, the time is recomputed after each transaction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the policy is `EveryNBlocks(0)`.
+    ///
+    /// [`RecomputePolicy::EveryNBlocks`]: enum.RecomputePolicy.html#variant.EveryNBlocks
+    pub fn with_recompute_policy(mut self, policy: RecomputePolicy) -> Self {
+        assert_ne!(
+            policy,
+            RecomputePolicy::EveryNBlocks(0),
+            "Number of blocks between time recomputations must be positive"
+        );
+        self.recompute_policy = policy;
+        self
+    }
+
+    /// Weighs validators' times when consolidating the time, e.g., proportionally to
+    /// the validators' stakes. Weights are keyed by service keys of validators; validators
+    /// absent from `weights` have zero weight, so their times do not affect
+    /// the consolidated time.
+    ///
+    /// The consolidated time is the greatest time such that validators with the total weight
+    /// exceeding `max_byzantine_weight = (total_weight - 1) / 3` have reported this
+    /// or a greater time, where `total_weight` is the weight of all current validators.
+    /// The time is only updated if validators with the total weight exceeding
+    /// `2 * max_byzantine_weight` have reported their times.
+    ///
+    /// By default, all validators have the same weight, so that the time is selected
+    /// based on the number of validators.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weights` are empty or if their sum overflows `u64`.
+    pub fn with_weights(mut self, weights: impl IntoIterator<Item = (PublicKey, u64)>) -> Self {
+        let weights = weights.into_iter().collect::<BTreeMap<_, _>>();
+        assert!(!weights.is_empty(), "Validators' weights must not be empty");
+        assert!(
+            total_weight(&weights).is_some(),
+            "Total weight of validators overflows `u64`"
+        );
+        self.weights = weights;
+        self
+    }
+
+    /// Limits the advance of the consolidated time in a single update to `step`. If the newly
+    /// consolidated time exceeds the stored time by more than `step`, the stored time
+    /// is advanced by `step` only, and a warning is logged. This smooths over validators'
+    /// clocks being fast-forwarded at once. The limit does not apply when the time
+    /// is consolidated for the first time.
+    ///
+    /// By default, the advance of the time is not limited.
+    /// The step is stored with the millisecond precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the step is shorter than a millisecond.
+    pub fn with_max_step(mut self, step: Duration) -> Self {
+        assert!(
+            step >= Duration::milliseconds(1),
+            "Maximum step must be at least a millisecond"
+        );
+        self.max_step = Some(step);
+        self
+    }
+
+    /// Requires times reported by each validator to be at least `interval` apart. A `TxTime`
+    /// transaction reporting a time less than `interval` after the previously stored time
+    /// of the same validator fails with the `TooFrequent` error, and such reports
+    /// in `TxTimeBatch` are skipped. Validator nodes do not broadcast their time after a block
+    /// if the report would be rejected.
+    ///
+    /// The interval is measured by the reported time rather than by the consolidated time.
+    /// The consolidated time advances only after validators report time, so measuring
+    /// the interval against it could block reports from all validators indefinitely.
+    ///
+    /// By default, the frequency of reports is not limited.
+    /// The interval is stored with the millisecond precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the interval is shorter than a millisecond.
+    pub fn with_min_submit_interval(mut self, interval: Duration) -> Self {
+        assert!(
+            interval >= Duration::milliseconds(1),
+            "Minimum submit interval must be at least a millisecond"
+        );
+        self.min_submit_interval = Some(interval);
+        self
+    }
+
+    /// Requires at least `count` current validators to report their time before the consolidated
+    /// time is written. With few validators, the Byzantine fault tolerance threshold
+    /// allows a single validator to set the time (e.g., with 3 validators, the time is
+    /// consolidated after the first report); this option makes the consolidation stricter.
+    ///
+    /// By default, only the fault tolerance threshold is checked.
+    pub fn with_min_reporters(mut self, count: usize) -> Self {
+        self.min_reporters = Some(count);
+        self
+    }
+
+    /// Retains times of past validators as tombstones, i.e., records marked as inactive
+    /// for audit purposes. Tombstones are not used to compute the consolidated time
+    /// and are never evicted, even if the [history limit] is set. `ValidatorTime`s
+    /// returned by the private API of the service report whether the record is active.
+    ///
+    /// By default, records are not marked, and times of past validators are evicted
+    /// according to the history limit.
+    ///
+    /// [history limit]: #method.with_history_limit
+    pub fn with_tombstones(mut self) -> Self {
+        self.tombstones = true;
+        self
+    }
+}
+
+/// Policy determining when the consolidated time is recomputed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RecomputePolicy {
+    /// The time is recomputed after each transaction reporting validators' times.
+    EveryTx,
+    /// The time is recomputed after transactions in each block with the height divisible
+    /// by the specified number of blocks. Validators' times are still updated
+    /// by each transaction.
+    EveryNBlocks(u64),
+}
+
+impl Default for RecomputePolicy {
+    fn default() -> Self {
+        Self::EveryTx
+    }
+}
+
+fn total_weight(weights: &BTreeMap<PublicKey, u64>) -> Option<u64> {
+    weights
+        .values()
+        .try_fold(0_u64, |total, &weight| total.checked_add(weight))
+}
+
+fn duration_to_pb(duration: Option<Duration>) -> u64 {
+    duration.map_or(0, |duration| duration.num_milliseconds() as u64)
+}
+
+fn duration_from_pb(millis: u64) -> anyhow::Result<Option<Duration>> {
+    Ok(match millis {
+        0 => None,
+        millis => Some(Duration::milliseconds(i64::try_from(millis)?)),
+    })
+}
+
+impl ProtobufConvert for TimeConfig {
+    type ProtoStruct = proto::Config;
+
+    fn to_pb(&self) -> Self::ProtoStruct {
+        let mut pb = proto::Config::new();
+        pb.set_history_limit(self.history_limit.map_or(0, |limit| limit as u64));
+        if let Some(time) = self.genesis_time {
+            pb.set_genesis_time(time.to_pb());
+        }
+        pb.set_outlier_window(duration_to_pb(self.outlier_window));
+        pb.set_recompute_every_n_blocks(match self.recompute_policy {
+            RecomputePolicy::EveryTx => 0,
+            RecomputePolicy::EveryNBlocks(blocks) => blocks,
+        });
+        for (public_key, &weight) in &self.weights {
+            let mut validator_weight = proto::ValidatorWeight::new();
+            validator_weight.set_public_key(public_key.to_pb());
+            validator_weight.set_weight(weight);
+            pb.mut_weights().push(validator_weight);
+        }
+        pb.set_max_step(duration_to_pb(self.max_step));
+        pb.set_min_submit_interval(duration_to_pb(self.min_submit_interval));
+        pb.set_min_reporters(self.min_reporters.map_or(0, |count| count as u64));
+        pb.set_tombstones(self.tombstones);
+        pb
+    }
+
+    fn from_pb(mut pb: Self::ProtoStruct) -> anyhow::Result<Self> {
+        let history_limit = match pb.get_history_limit() {
+            0 => None,
+            limit => Some(usize::try_from(limit)?),
+        };
+        let genesis_time = if pb.has_genesis_time() {
+            Some(DateTime::from_pb(pb.take_genesis_time())?)
+        } else {
+            None
+        };
+        let min_reporters = match pb.get_min_reporters() {
+            0 => None,
+            count => Some(usize::try_from(count)?),
+        };
+        let mut weights = BTreeMap::new();
+        for mut validator_weight in pb.take_weights() {
+            let public_key = PublicKey::from_pb(validator_weight.take_public_key())?;
+            let prev_weight = weights.insert(public_key, validator_weight.get_weight());
+            ensure!(
+                prev_weight.is_none(),
+                "Duplicate weight for validator {:?}",
+                public_key
+            );
+        }
+        ensure!(
+            total_weight(&weights).is_some(),
+            "Total weight of validators overflows `u64`"
+        );
+
+        Ok(Self {
+            history_limit,
+            genesis_time,
+            outlier_window: duration_from_pb(pb.get_outlier_window())?,
+            recompute_policy: match pb.get_recompute_every_n_blocks() {
+                0 => RecomputePolicy::EveryTx,
+                blocks => RecomputePolicy::EveryNBlocks(blocks),
+            },
+            weights,
+            max_step: duration_from_pb(pb.get_max_step())?,
+            min_submit_interval: duration_from_pb(pb.get_min_submit_interval())?,
+            min_reporters,
+            tombstones: pb.get_tombstones(),
+        })
+    }
+}
@@ -116,22 +116,41 @@
 )]
 
 pub mod api;
+#[cfg(feature = "testkit")]
+pub mod testkit;
 
+mod config;
 mod proto;
 mod schema;
 mod time_provider;
 mod transactions;
 
+use exonum::{
+    crypto::PublicKey,
+    merkledb::{BinaryValue, Snapshot},
+    runtime::{ExecutionContext, ExecutionError, ExecutionFail, SnapshotExt},
+};
 use exonum_derive::{ServiceDispatcher, ServiceFactory};
 use exonum_rust_runtime::{api::ServiceApiBuilder, AfterCommitContext, Service};
 
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 pub use crate::{
-    api::ValidatorTime,
+    api::{
+        CurrentTimeQuery, TimeFormat, TimeWithProof, ValidatorTime, ValidatorTimeDelta,
+        ValidatorTimeDrift, ValidatorsTimesDeltaQuery, ValidatorsTimesPage, ValidatorsTimesQuery,
+        MAX_VALIDATORS_TIMES_PER_PAGE,
+    },
+    config::{RecomputePolicy, TimeConfig},
     schema::TimeSchema,
-    time_provider::{MockTimeProvider, SystemTimeProvider, TimeProvider},
-    transactions::{Error, TimeOracleInterface, TimeOracleInterfaceMut, TxTime},
+    time_provider::{
+        AveragingTimeProvider, ChannelTimeProvider, DeterministicTimeProvider, FailingTimeProvider,
+        MockTimeProvider, MonotonicTimeProvider, ProviderInfo, RetryingTimeProvider,
+        SystemTimeProvider, TimeError, TimeProvider,
+    },
+    transactions::{
+        Error, TimeOracleInterface, TimeOracleInterfaceMut, TimeReport, TxTime, TxTimeBatch,
+    },
 };
 
 /// Time oracle service.
@@ -140,29 +159,89 @@ pub use crate::{
 pub struct TimeService {
     /// Provider of the actual time.
     time: Arc<dyn TimeProvider>,
+    /// Whether the history of validators' times is recorded.
+    record_history: bool,
+}
+
+impl TimeService {
+    /// Checks whether the node with the specified service key is eligible to submit its time,
+    /// i.e., whether the key belongs to a validator in the current consensus configuration.
+    /// Time reports signed by other keys fail with the `UnknownSender` error.
+    pub fn is_submitting_validator(snapshot: &dyn Snapshot, public_key: PublicKey) -> bool {
+        snapshot.for_core().validator_id(public_key).is_some()
+    }
 }
 
 impl Service for TimeService {
+    fn initialize(
+        &self,
+        context: ExecutionContext<'_>,
+        params: Vec<u8>,
+    ) -> Result<(), ExecutionError> {
+        let config = TimeConfig::from_bytes(Cow::from(&params)).map_err(|e| {
+            let msg = format!("Error while parsing time service configuration: {}", e);
+            Error::InvalidConfig.with_description(msg)
+        })?;
+        let mut schema = TimeSchema::new(context.service_data());
+        if let Some(time) = config.genesis_time {
+            schema.time.set(time);
+        }
+        schema.config.set(config);
+        Ok(())
+    }
+
+    fn after_transactions(&self, context: ExecutionContext<'_>) -> Result<(), ExecutionError> {
+        let config = TimeSchema::new(context.service_data()).config();
+        // Tombstones of past validators are never evicted.
+        if let Some(limit) = config.history_limit.filter(|_| !config.tombstones) {
+            let validator_keys = context.data().for_core().consensus_config().validator_keys;
+            let mut schema = TimeSchema::new(context.service_data());
+            schema.evict_past_validators(&validator_keys, limit);
+        }
+
+        if let RecomputePolicy::EveryNBlocks(blocks) = config.recompute_policy {
+            let core_schema = context.data().for_core();
+            if core_schema.next_height().0 % blocks == 0 {
+                let validator_keys = core_schema.consensus_config().validator_keys;
+                let mut schema = TimeSchema::new(context.service_data());
+                schema.update_consolidated_time(&validator_keys);
+            }
+        }
+
+        if self.record_history {
+            let height = context.data().for_core().next_height();
+            TimeSchema::new(context.service_data()).record_history(height);
+        }
+        Ok(())
+    }
+
     fn after_commit(&self, context: AfterCommitContext<'_>) {
         // If current node is a validator, after each block it should broadcast
         // the transaction with the current time.
         if let Some(broadcast) = context.broadcaster() {
-            let time = TxTime::new(self.time.current_time());
-            broadcast.blocking().report_time((), time).ok();
+            // If the provider cannot determine the time, the report is skipped for this block.
+            if let Ok(time) = self.time.try_current_time() {
+                let schema = TimeSchema::new(context.service_data());
+                if !schema.is_too_frequent(&context.service_key(), time) {
+                    broadcast.blocking().report_time((), TxTime::new(time)).ok();
+                }
+            }
         }
     }
 
     fn wire_api(&self, builder: &mut ServiceApiBuilder) {
-        api::PublicApi::wire(builder);
+        api::PublicApi::wire(builder, self.time.clone());
         api::PrivateApi::wire(builder);
     }
 }
 
 /// Time oracle service factory.
 ///
-/// By default, it creates time service instances with [`SystemTimeProvider`].
+/// By default, it creates time service instances with [`SystemTimeProvider`]
+/// wrapped in [`MonotonicTimeProvider`], so that the reported time never goes backwards.
 ///
 /// [`SystemTimeProvider`]: struct.SystemTimeProvider.html
+/// [`MonotonicTimeProvider`]: struct.MonotonicTimeProvider.html
 #[derive(Debug, ServiceFactory)]
 #[service_factory(
     proto_sources = "proto",
@@ -170,6 +249,7 @@ impl Service for TimeService {
 )]
 pub struct TimeServiceFactory {
     time_provider: Arc<dyn TimeProvider>,
+    record_history: bool,
 }
 
 impl TimeServiceFactory {
@@ -182,18 +262,34 @@ impl TimeServiceFactory {
     pub fn with_provider(time_provider: impl Into<Arc<dyn TimeProvider>>) -> Self {
         Self {
             time_provider: time_provider.into(),
+            record_history: false,
         }
     }
 
+    /// Records times of validators as of the end of each block, so that they can be
+    /// compared between heights, e.g., via the `v1/validators_times/delta` endpoint.
+    /// The history is recorded starting from the first block processed with this option.
+    ///
+    /// By default, only the latest times of validators are stored.
+    ///
+    /// The history is stored outside of the service state hash, so nodes may enable
+    /// the option independently. However, the history grows with each block
+    /// proportionally to the number of validators.
+    pub fn with_times_history(mut self) -> Self {
+        self.record_history = true;
+        self
+    }
+
     fn create_instance(&self) -> Box<dyn Service> {
         Box::new(TimeService {
             time: self.time_provider.clone(),
+            record_history: self.record_history,
         })
     }
 }
 
 impl Default for TimeServiceFactory {
     fn default() -> Self {
-        Self::with_provider(SystemTimeProvider)
+        Self::with_provider(MonotonicTimeProvider::new(SystemTimeProvider))
     }
 }
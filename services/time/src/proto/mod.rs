@@ -16,6 +16,8 @@
 
 #![allow(bare_trait_objects, clippy::pedantic, clippy::nursery)]
 
-pub use self::service::TxTime;
+pub use self::service::{Config, TimeReport, TxTime, TxTimeBatch, ValidatorWeight};
 
 include!(concat!(env!("OUT_DIR"), "/protobuf_mod.rs"));
+
+use exonum::crypto::proto::*;
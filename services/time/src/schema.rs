@@ -14,15 +14,17 @@
 
 use chrono::{DateTime, Utc};
 
-use exonum::{blockchain::ValidatorKeys, crypto::PublicKey};
+use exonum::{blockchain::ValidatorKeys, crypto::PublicKey, helpers::Height};
 use exonum_derive::{FromAccess, RequireArtifact};
 use exonum_merkledb::{
     access::{Access, FromAccess, RawAccessMut},
-    ProofEntry, ProofMapIndex,
+    Entry, Group, MapIndex, ProofEntry, ProofMapIndex,
 };
 
 use std::cmp::Reverse;
 
+use crate::config::TimeConfig;
+
 /// Database schema of the time service. The schema is fully public.
 #[derive(Debug, FromAccess, RequireArtifact)]
 pub struct TimeSchema<T: Access> {
@@ -30,23 +32,113 @@ pub struct TimeSchema<T: Access> {
     pub validators_times: ProofMapIndex<T::Base, PublicKey, DateTime<Utc>>,
     /// Consolidated blockchain time, approved by validators.
     pub time: ProofEntry<T::Base, DateTime<Utc>>,
+    /// Latest sequence number of `TxTime` transactions for every validator that has
+    /// reported time with a sequence number. May contain keys corresponding to past validators.
+    pub validators_seqs: ProofMapIndex<T::Base, PublicKey, u64>,
+    /// Height of the block in which the time of every validator was last updated.
+    /// May contain keys corresponding to past validators.
+    pub validators_heights: ProofMapIndex<T::Base, PublicKey, u64>,
+    /// Configuration of the service instance.
+    pub config: ProofEntry<T::Base, TimeConfig>,
+    /// Times of validators as of the end of each block, keyed by the block height.
+    /// Recorded only if the service is configured to keep the history of times.
+    /// The history is not a part of the service state hash.
+    pub validators_times_history: Group<T, u64, MapIndex<T::Base, PublicKey, DateTime<Utc>>>,
+    /// Height of the first block recorded in `validators_times_history`.
+    pub history_start: Entry<T::Base, u64>,
 }
 
 impl<T: Access> TimeSchema<T> {
     pub(crate) fn new(access: T) -> Self {
         Self::from_root(access).unwrap()
     }
+
+    /// Returns the consolidated blockchain time, or `None` if the time is not determined yet.
+    pub fn consolidated_time(&self) -> Option<DateTime<Utc>> {
+        self.time.get()
+    }
+
+    /// Returns the configuration of the service instance.
+    pub fn config(&self) -> TimeConfig {
+        self.config.get().unwrap_or_default()
+    }
+
+    /// Returns the latest time reported by the validator with the specified service key.
+    pub fn validator_time(&self, public_key: &PublicKey) -> Option<DateTime<Utc>> {
+        self.validators_times.get(public_key)
+    }
+
+    /// Checks whether the report of `time` by the validator with the specified key
+    /// is too frequent according to the minimum submit interval.
+    pub(crate) fn is_too_frequent(&self, public_key: &PublicKey, time: DateTime<Utc>) -> bool {
+        match (
+            self.config().min_submit_interval,
+            self.validator_time(public_key),
+        ) {
+            (Some(interval), Some(prev_time)) => time > prev_time && time - prev_time < interval,
+            _ => false,
+        }
+    }
+
+    /// Returns the latest times of the validators with the specified keys, sorted by time
+    /// in the descending order. Validators that have not reported time yet are listed last;
+    /// validators with equal times are ordered by their service keys.
+    pub fn validators_by_time(
+        &self,
+        validator_keys: &[ValidatorKeys],
+    ) -> Vec<(PublicKey, Option<DateTime<Utc>>)> {
+        let mut times = validator_keys
+            .iter()
+            .map(|validator| {
+                let public_key = validator.service_key;
+                (public_key, self.validator_time(&public_key))
+            })
+            .collect::<Vec<_>>();
+        // `None` is less than any known time, so unknown times go last in the descending order.
+        times.sort_unstable_by_key(|&(public_key, time)| (Reverse(time), public_key));
+        times
+    }
+
+    /// Returns the height of the first block for which the history of validators' times
+    /// is recorded, or `None` if the history is not recorded.
+    pub fn history_start(&self) -> Option<Height> {
+        self.history_start.get().map(Height)
+    }
+
+    /// Returns the time reported by the validator with the specified service key as of
+    /// the end of the block at `height`, or `None` if the time is unknown or the history
+    /// is not recorded for this height.
+    pub fn validator_time_at(
+        &self,
+        public_key: &PublicKey,
+        height: Height,
+    ) -> Option<DateTime<Utc>> {
+        self.validators_times_history.get(&height.0).get(public_key)
+    }
 }
 
 impl<T: Access> TimeSchema<T>
 where
     T::Base: RawAccessMut,
 {
+    /// Records the current validators' times as the times at the end of the block at `height`.
+    pub(crate) fn record_history(&mut self, height: Height) {
+        let mut times = self.validators_times_history.get(&height.0);
+        times.clear();
+        for (public_key, time) in self.validators_times.iter() {
+            times.put(&public_key, time);
+        }
+        if self.history_start.get().is_none() {
+            self.history_start.set(height.0);
+        }
+    }
+
     /// Returns an error if the currently registered validator time is greater than `time`.
     pub(crate) fn update_validator_time(
         &mut self,
         author: PublicKey,
         time: DateTime<Utc>,
+        height: Height,
     ) -> Result<(), ()> {
         match self.validators_times.get(&author) {
             // The validator time in the storage should be less than in the transaction.
@@ -54,44 +146,148 @@ where
             // Write the time for the validator.
             _ => {
                 self.validators_times.put(&author, time);
+                self.validators_heights.put(&author, height.0);
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns an error if the currently registered sequence number of the validator
+    /// is not less than `seq`.
+    pub(crate) fn update_validator_seq(&mut self, author: PublicKey, seq: u64) -> Result<(), ()> {
+        match self.validators_seqs.get(&author) {
+            Some(stored_seq) if stored_seq >= seq => Err(()),
+            _ => {
+                self.validators_seqs.put(&author, seq);
                 Ok(())
             }
         }
     }
 
+    /// Removes times of past validators, so that at most `limit` of them are retained.
+    /// The past validators whose times were updated at the lowest heights are removed first.
+    pub(crate) fn evict_past_validators(&mut self, validator_keys: &[ValidatorKeys], limit: usize) {
+        let mut past_validators = self
+            .validators_times
+            .iter()
+            .filter(|(public_key, _)| {
+                validator_keys
+                    .iter()
+                    .all(|validator| validator.service_key != *public_key)
+            })
+            .map(|(public_key, _)| {
+                let height = self.validators_heights.get(&public_key).unwrap_or(0);
+                (height, public_key)
+            })
+            .collect::<Vec<_>>();
+        if past_validators.len() <= limit {
+            return;
+        }
+
+        past_validators.sort_unstable();
+        let evicted_count = past_validators.len() - limit;
+        for (_, public_key) in &past_validators[..evicted_count] {
+            self.validators_times.remove(public_key);
+            self.validators_seqs.remove(public_key);
+            self.validators_heights.remove(public_key);
+        }
+    }
+
     pub(crate) fn update_consolidated_time(&mut self, validator_keys: &[ValidatorKeys]) {
-        // Find all known times for the validators.
+        let config = self.config();
+        let weight_of = |public_key: &PublicKey| {
+            if config.weights.is_empty() {
+                1
+            } else {
+                config.weights.get(public_key).copied().unwrap_or(0)
+            }
+        };
+
+        // Find all known times for the validators together with the validators' weights.
         let validator_times = {
             let mut times = self
                 .validators_times
                 .iter()
-                .filter_map(|(public_key, time)| {
-                    validator_keys.iter().find_map(|validator| {
-                        if validator.service_key == public_key {
-                            Some(time)
-                        } else {
-                            None
-                        }
-                    })
+                .filter(|(public_key, _)| {
+                    validator_keys
+                        .iter()
+                        .any(|validator| validator.service_key == *public_key)
                 })
+                .map(|(public_key, time)| (time, weight_of(&public_key)))
                 .collect::<Vec<_>>();
-            // Ordering time from highest to lowest.
-            times.sort_by_key(|&t| Reverse(t));
+            if times.len() < config.min_reporters.unwrap_or(0) {
+                return;
+            }
+            // Ordering time from highest to lowest. The sort is stable and `validators_times`
+            // is iterated in a fixed order, so equal times never depend on the order
+            // of the reports. Ties do not affect the selected time in any case,
+            // since equal times are interchangeable.
+            times.sort_by_key(|&(time, _)| Reverse(time));
+            match config.outlier_window {
+                Some(window) if !times.is_empty() => {
+                    // Discard times too far from the median; the remaining times stay sorted.
+                    let (median, _) = times[times.len() / 2];
+                    times.retain(|&(time, _)| time - median <= window && median - time <= window);
+                }
+                _ => {}
+            }
             times
         };
 
-        // The largest number of Byzantine nodes.
-        let max_byzantine_nodes = (validator_keys.len() - 1) / 3;
-        if validator_times.len() <= 2 * max_byzantine_nodes {
+        // The largest total weight of Byzantine nodes. If all weights are equal to 1,
+        // this is the largest number of Byzantine nodes. The sums do not overflow since
+        // the total weight of all validators in the configuration fits into `u64`.
+        let total_weight: u64 = validator_keys
+            .iter()
+            .map(|validator| weight_of(&validator.service_key))
+            .sum();
+        if total_weight == 0 {
             return;
         }
+        let max_byzantine_weight = (total_weight - 1) / 3;
+        let reported_weight: u64 = validator_times.iter().map(|&(_, weight)| weight).sum();
+        if reported_weight <= 2 * max_byzantine_weight {
+            return;
+        }
+
+        // Select the greatest time such that validators with the total weight exceeding
+        // `max_byzantine_weight` have reported this or a greater time.
+        let mut cumulative_weight = 0;
+        let selected_time = validator_times
+            .iter()
+            .find_map(|&(time, weight)| {
+                cumulative_weight += weight;
+                if cumulative_weight > max_byzantine_weight {
+                    Some(time)
+                } else {
+                    None
+                }
+            })
+            .expect("Reported weight exceeds the Byzantine weight");
 
         match self.time.get() {
             // Selected time should be greater than the time in the storage.
-            Some(current_time) if current_time >= validator_times[max_byzantine_nodes] => {}
-            _ => {
-                // Change the time in the storage.
-                self.time.set(validator_times[max_byzantine_nodes]);
+            Some(current_time) if current_time >= selected_time => {}
+            Some(current_time) => {
+                // Limit the advance of the time in the storage.
+                let new_time = match config.max_step {
+                    Some(step) if selected_time - current_time > step => {
+                        log::warn!(
+                            "Consolidated time advance from {} to {} exceeds the maximum step {}; \
+                             the advance is clamped",
+                            current_time,
+                            selected_time,
+                            step
+                        );
+                        current_time + step
+                    }
+                    _ => selected_time,
+                };
+                self.time.set(new_time);
+            }
+            None => {
+                // Set the time in the storage for the first time.
+                self.time.set(selected_time);
             }
         }
     }
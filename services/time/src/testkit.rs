@@ -0,0 +1,264 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Testkit helpers for the time service. Available with the `testkit` crate feature.
+
+use chrono::{DateTime, Utc};
+use exonum::{
+    helpers::{Height, ValidatorId},
+    runtime::SnapshotExt,
+};
+use exonum_api::Result;
+use exonum_testkit::{explorer::BlockWithTransactions, TestKit, TestKitApi};
+
+use std::{collections::HashMap, sync::Arc};
+
+use crate::{
+    CurrentTimeQuery, ProviderInfo, TimeOracleInterface, TimeProvider, TimeSchema, TimeWithProof,
+    TxTime, ValidatorTime, ValidatorTimeDrift, ValidatorsTimesPage, ValidatorsTimesQuery,
+};
+
+/// Maximum number of blocks created by `create_blocks_until_time_set`.
+const MAX_BLOCKS_UNTIL_TIME_SET: u64 = 100;
+
+/// Extension trait for `TestKit` simplifying testing of services depending on the time oracle.
+pub trait TimeTestKitExt {
+    /// Creates blocks until the consolidated time is set in the time service with
+    /// the specified name. Returns the blockchain height at which the time was first
+    /// observed. If the time is already set, no blocks are created.
+    ///
+    /// # Panics
+    ///
+    /// - If the time service with the specified name is not deployed.
+    /// - If the time is not set after 100 blocks. This may happen, for example,
+    ///   if the testkit emulates several validators, since only the node
+    ///   emulated by the testkit reports time automatically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use exonum_testkit::{Spec, TestKitBuilder};
+    /// use exonum_time::{testkit::TimeTestKitExt, TimeServiceFactory};
+    ///
+    /// let time_service = Spec::new(TimeServiceFactory::default()).with_instance(10, "time", ());
+    /// let mut testkit = TestKitBuilder::validator().with(time_service).build();
+    /// let height = testkit.create_blocks_until_time_set("time");
+    /// assert!(height.0 <= 2);
+    /// ```
+    fn create_blocks_until_time_set(&mut self, instance_name: &str) -> Height;
+
+    /// Creates a block with time reports to the time service with the specified name.
+    /// Each validator from `providers` reports the current time of its own provider,
+    /// which allows to emulate validators with different clock offsets. Validators
+    /// absent from `providers` do not report time in the block.
+    ///
+    /// # Panics
+    ///
+    /// - If the time service with the specified name is not deployed.
+    /// - If `providers` contain an identifier of a non-existing validator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{Duration, TimeZone, Utc};
+    /// use exonum::{helpers::ValidatorId, runtime::SnapshotExt};
+    /// use exonum_testkit::{Spec, TestKitBuilder};
+    /// use exonum_time::{
+    ///     testkit::TimeTestKitExt, MockTimeProvider, TimeProvider, TimeSchema, TimeServiceFactory,
+    /// };
+    ///
+    /// use std::{collections::HashMap, sync::Arc};
+    ///
+    /// let time_service = Spec::new(TimeServiceFactory::default()).with_instance(10, "time", ());
+    /// let mut testkit = TestKitBuilder::validator()
+    ///     .with_validators(4)
+    ///     .with(time_service)
+    ///     .build();
+    ///
+    /// let base_time = Utc.timestamp(1_000, 0);
+    /// // Validators' clocks are offset from each other by 1 second.
+    /// let providers: HashMap<_, _> = (0..4)
+    ///     .map(|i| {
+    ///         let provider = MockTimeProvider::new(base_time + Duration::seconds(i.into()));
+    ///         (ValidatorId(i), Arc::new(provider) as Arc<dyn TimeProvider>)
+    ///     })
+    ///     .collect();
+    /// testkit.create_block_with_validator_times("time", &providers);
+    ///
+    /// let snapshot = testkit.snapshot();
+    /// let schema: TimeSchema<_> = snapshot.service_schema("time").unwrap();
+    /// // The consolidated time is the second largest reported time.
+    /// assert_eq!(schema.time.get(), Some(base_time + Duration::seconds(2)));
+    /// ```
+    fn create_block_with_validator_times(
+        &mut self,
+        instance_name: &str,
+        providers: &HashMap<ValidatorId, Arc<dyn TimeProvider>>,
+    ) -> BlockWithTransactions;
+}
+
+impl TimeTestKitExt for TestKit {
+    fn create_blocks_until_time_set(&mut self, instance_name: &str) -> Height {
+        let is_time_set = |testkit: &Self| {
+            let snapshot = testkit.snapshot();
+            let schema: TimeSchema<_> =
+                snapshot
+                    .service_schema(instance_name)
+                    .unwrap_or_else(|err| {
+                        panic!("Cannot access time service `{}`: {}", instance_name, err)
+                    });
+            schema.time.get().is_some()
+        };
+
+        if is_time_set(self) {
+            return self.height();
+        }
+        for _ in 0..MAX_BLOCKS_UNTIL_TIME_SET {
+            self.create_block();
+            if is_time_set(self) {
+                return self.height();
+            }
+        }
+        panic!(
+            "Time is not set in time service `{}` after {} blocks",
+            instance_name, MAX_BLOCKS_UNTIL_TIME_SET
+        );
+    }
+
+    fn create_block_with_validator_times(
+        &mut self,
+        instance_name: &str,
+        providers: &HashMap<ValidatorId, Arc<dyn TimeProvider>>,
+    ) -> BlockWithTransactions {
+        let instance_id = self
+            .snapshot()
+            .for_dispatcher()
+            .get_instance(instance_name)
+            .unwrap_or_else(|| panic!("Time service `{}` is not deployed", instance_name))
+            .spec
+            .id;
+
+        let mut validator_ids: Vec<_> = providers.keys().copied().collect();
+        validator_ids.sort();
+        let txs: Vec<_> = validator_ids
+            .into_iter()
+            .map(|validator_id| {
+                let time = providers[&validator_id].current_time();
+                self.validator(validator_id)
+                    .service_keypair()
+                    .report_time(instance_id, TxTime::new(time))
+            })
+            .collect();
+        self.create_block_with_transactions(txs)
+    }
+}
+
+/// Typed client for the HTTP API of a time service instance, which wraps `TestKitApi`.
+///
+/// Each method of the client corresponds to an endpoint described in the [`api`] module
+/// and returns the typed response of the endpoint.
+///
+/// # Examples
+///
+/// ```
+/// use exonum::helpers::Height;
+/// use exonum_testkit::{Spec, TestKitBuilder};
+/// use exonum_time::{testkit::TimeServiceClient, TimeServiceFactory};
+///
+/// # #[tokio::main]
+/// # async fn main() -> anyhow::Result<()> {
+/// let time_service = Spec::new(TimeServiceFactory::default()).with_instance(10, "time", ());
+/// let mut testkit = TestKitBuilder::validator().with(time_service).build();
+/// testkit.create_blocks_until(Height(2));
+///
+/// let api = testkit.api();
+/// let client = TimeServiceClient::new(&api, "time");
+/// assert!(client.current_time().await?.is_some());
+/// assert_eq!(client.validators_times().await?.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`api`]: ../api/index.html
+#[derive(Debug)]
+pub struct TimeServiceClient<'a> {
+    api: &'a TestKitApi,
+    /// Prefix of the URLs of the service endpoints.
+    prefix: String,
+}
+
+impl<'a> TimeServiceClient<'a> {
+    /// Creates a client for the time service instance with the specified name.
+    pub fn new(api: &'a TestKitApi, instance_name: &str) -> Self {
+        Self {
+            api,
+            prefix: format!("api/services/{}", instance_name),
+        }
+    }
+
+    /// Returns the consolidated time, or `None` if the time is not set yet.
+    pub async fn current_time(&self) -> Result<Option<DateTime<Utc>>> {
+        self.api.public(&self.prefix).get("v1/current_time").await
+    }
+
+    /// Returns the consolidated time together with the proof of its authenticity.
+    pub async fn current_time_with_proof(&self) -> Result<TimeWithProof> {
+        self.api
+            .public(&self.prefix)
+            .query(&CurrentTimeQuery::with_proof())
+            .get("v1/current_time")
+            .await
+    }
+
+    /// Returns the description of the time provider used by the node.
+    pub async fn provider_info(&self) -> Result<ProviderInfo> {
+        self.api.public(&self.prefix).get("v1/provider_info").await
+    }
+
+    /// Returns times reported by the current validators.
+    pub async fn validators_times(&self) -> Result<Vec<ValidatorTime>> {
+        self.api
+            .private(&self.prefix)
+            .get("v1/validators_times")
+            .await
+    }
+
+    /// Returns times reported by all nodes, including ones that are no longer validators.
+    pub async fn all_validators_times(&self) -> Result<Vec<ValidatorTime>> {
+        self.api
+            .private(&self.prefix)
+            .get("v1/validators_times/all")
+            .await
+    }
+
+    /// Returns a page of times reported by all nodes.
+    pub async fn validators_times_page(
+        &self,
+        query: ValidatorsTimesQuery,
+    ) -> Result<ValidatorsTimesPage> {
+        self.api
+            .private(&self.prefix)
+            .query(&query)
+            .get("v1/validators_times/page")
+            .await
+    }
+
+    /// Returns drifts of the current validators' times from the consolidated time.
+    pub async fn validators_time_drift(&self) -> Result<Vec<ValidatorTimeDrift>> {
+        self.api
+            .private(&self.prefix)
+            .get("v1/validators_times/drift")
+            .await
+    }
+}
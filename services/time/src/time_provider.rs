@@ -13,13 +13,101 @@
 // limitations under the License.
 
 use chrono::{DateTime, Duration, TimeZone, Utc};
+use exonum::helpers::Height;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::{json, Value};
 
-use std::sync::{Arc, RwLock};
+use std::{
+    convert::TryFrom,
+    error::Error as StdError,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::Receiver,
+        Arc, Mutex, RwLock,
+    },
+    time::{Duration as StdDuration, Instant},
+};
 
 /// A helper trait that provides the node with a current time.
 pub trait TimeProvider: Send + Sync + std::fmt::Debug {
     /// Returns the current time.
     fn current_time(&self) -> DateTime<Utc>;
+
+    /// Returns the current time or an error if the provider cannot determine it.
+    /// The time service uses this method to obtain the time reported by the node;
+    /// if the method returns an error, the node skips reporting time after the block.
+    ///
+    /// The default implementation is an adapter for infallible providers, which always
+    /// returns the result of [`current_time`]. Providers that may fail (e.g., ones querying
+    /// a remote time source) should override this method.
+    ///
+    /// [`current_time`]: #tymethod.current_time
+    fn try_current_time(&self) -> Result<DateTime<Utc>, TimeError> {
+        Ok(self.current_time())
+    }
+
+    /// Describes the provider. The description is returned by the `v1/provider_info`
+    /// endpoint of the service.
+    ///
+    /// The default implementation returns a description with the `custom` kind.
+    fn describe(&self) -> ProviderInfo {
+        ProviderInfo::new("custom")
+    }
+}
+
+/// Error returned by a `TimeProvider` if it cannot determine the current time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeError {
+    description: String,
+}
+
+impl TimeError {
+    /// Creates an error with the specified description.
+    pub fn new(description: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+        }
+    }
+}
+
+impl fmt::Display for TimeError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "Cannot determine current time: {}",
+            self.description
+        )
+    }
+}
+
+impl StdError for TimeError {}
+
+/// Description of a `TimeProvider`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ProviderInfo {
+    /// Kind of the provider, e.g., `system` or `mock`.
+    pub kind: String,
+    /// Provider-specific configuration, such as a list of NTP servers.
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub config: Value,
+}
+
+impl ProviderInfo {
+    /// Creates a description of the provider with the specified kind and no configuration.
+    pub fn new(kind: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            config: Value::Null,
+        }
+    }
+
+    /// Adds the provider configuration to the description.
+    pub fn with_config(mut self, config: Value) -> Self {
+        self.config = config;
+        self
+    }
 }
 
 #[derive(Debug)]
@@ -30,6 +118,316 @@ impl TimeProvider for SystemTimeProvider {
     fn current_time(&self) -> DateTime<Utc> {
         Utc::now()
     }
+
+    fn describe(&self) -> ProviderInfo {
+        ProviderInfo::new("system")
+    }
+}
+
+/// Time provider which never reports time earlier than the time it has reported before.
+///
+/// The provider wraps another provider (the time source). If the source time goes backwards
+/// (e.g., because the system clock was adjusted), the provider keeps reporting
+/// the latest time it has returned until the source time catches up. This is useful
+/// since the time service rejects validator times that are less than the previously
+/// reported ones.
+///
+/// The time service factory uses this provider over [`SystemTimeProvider`] by default.
+///
+/// The reported time is truncated to microseconds, which allows to track the latest time
+/// for all dates representable by `DateTime`.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use exonum_time::{MockTimeProvider, MonotonicTimeProvider, TimeProvider};
+///
+/// let source = MockTimeProvider::new(Utc.timestamp(10, 0));
+/// let provider = MonotonicTimeProvider::new(source.clone());
+/// assert_eq!(provider.current_time(), Utc.timestamp(10, 0));
+/// source.set_time(Utc.timestamp(5, 0));
+/// assert_eq!(provider.current_time(), Utc.timestamp(10, 0));
+/// source.add_time(Duration::seconds(10));
+/// assert_eq!(provider.current_time(), Utc.timestamp(15, 0));
+/// ```
+///
+/// [`SystemTimeProvider`]: struct.SystemTimeProvider.html
+#[derive(Debug)]
+pub struct MonotonicTimeProvider<T> {
+    source: T,
+    /// Latest reported time encoded with `encode_micros`, or 0 if no time was reported yet.
+    last_micros: AtomicU64,
+}
+
+impl<T: TimeProvider> MonotonicTimeProvider<T> {
+    /// Creates a provider over the specified time source.
+    pub fn new(source: T) -> Self {
+        Self {
+            source,
+            last_micros: AtomicU64::new(0),
+        }
+    }
+
+    fn adjust(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let micros = encode_micros(time);
+        let last_micros = self.last_micros.fetch_max(micros, Ordering::SeqCst);
+        decode_micros(micros.max(last_micros))
+    }
+}
+
+const MICROS_PER_SEC: i64 = 1_000_000;
+const SIGN_BIT: u64 = 1 << 63;
+
+/// Converts time to the number of microseconds since the Unix epoch, mapped to `u64`
+/// preserving the order of times. The number of microseconds fits into `i64` for all
+/// dates representable by `DateTime`.
+fn encode_micros(time: DateTime<Utc>) -> u64 {
+    let micros = time
+        .timestamp()
+        .saturating_mul(MICROS_PER_SEC)
+        .saturating_add(i64::from(time.timestamp_subsec_micros()));
+    micros as u64 ^ SIGN_BIT
+}
+
+fn decode_micros(encoded: u64) -> DateTime<Utc> {
+    let micros = (encoded ^ SIGN_BIT) as i64;
+    let nanos = micros.rem_euclid(MICROS_PER_SEC) * 1_000;
+    Utc.timestamp(micros.div_euclid(MICROS_PER_SEC), nanos as u32)
+}
+
+impl<T: TimeProvider> TimeProvider for MonotonicTimeProvider<T> {
+    fn current_time(&self) -> DateTime<Utc> {
+        self.adjust(self.source.current_time())
+    }
+
+    fn try_current_time(&self) -> Result<DateTime<Utc>, TimeError> {
+        self.source.try_current_time().map(|time| self.adjust(time))
+    }
+
+    fn describe(&self) -> ProviderInfo {
+        self.source.describe()
+    }
+}
+
+#[allow(clippy::use_self)] // false positive
+impl<T: TimeProvider + 'static> From<MonotonicTimeProvider<T>> for Arc<dyn TimeProvider> {
+    fn from(time_provider: MonotonicTimeProvider<T>) -> Self {
+        Arc::new(time_provider)
+    }
+}
+
+/// Time provider retrying failed requests to another provider with an exponential backoff.
+///
+/// If the wrapped provider fails, the request is immediately retried up to `retries` times.
+/// The provider never waits for the time: if all attempts fail, the error of the latest
+/// attempt is returned, and the request is retried when the time is requested next time
+/// (e.g., after the next block). Requests made within `backoff` after a failure fail
+/// immediately without querying the wrapped provider; the delay is doubled after each
+/// consecutive failure, up to the maximum backoff (by default, [`DEFAULT_MAX_BACKOFF`]).
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use exonum_time::{FailingTimeProvider, MockTimeProvider, RetryingTimeProvider, TimeProvider};
+/// use std::time::Duration;
+///
+/// let source = FailingTimeProvider::new(MockTimeProvider::new(Utc.timestamp(10, 0)));
+/// let provider = RetryingTimeProvider::new(source.clone(), 3, Duration::from_millis(1));
+/// assert_eq!(provider.try_current_time(), Ok(Utc.timestamp(10, 0)));
+/// source.set_failing(true);
+/// assert!(provider.try_current_time().is_err());
+/// ```
+///
+/// [`DEFAULT_MAX_BACKOFF`]: #associatedconstant.DEFAULT_MAX_BACKOFF
+#[derive(Debug)]
+pub struct RetryingTimeProvider<T> {
+    inner: T,
+    retries: u32,
+    backoff: StdDuration,
+    max_backoff: StdDuration,
+    state: Mutex<RetryState>,
+}
+
+/// State of a `RetryingTimeProvider` after failed requests.
+#[derive(Debug, Default)]
+struct RetryState {
+    /// Delay after the latest failure.
+    delay: Option<StdDuration>,
+    /// Instant before which requests to the wrapped provider are not made.
+    next_request: Option<Instant>,
+}
+
+impl<T: TimeProvider> RetryingTimeProvider<T> {
+    /// Default maximum delay of requests after a failure.
+    pub const DEFAULT_MAX_BACKOFF: StdDuration = StdDuration::from_secs(60);
+
+    /// Creates a provider retrying failed requests to `inner` up to `retries` times,
+    /// with the initial delay `backoff` of requests after a failure.
+    pub fn new(inner: T, retries: u32, backoff: StdDuration) -> Self {
+        Self {
+            inner,
+            retries,
+            backoff,
+            max_backoff: Self::DEFAULT_MAX_BACKOFF,
+            state: Mutex::default(),
+        }
+    }
+
+    /// Sets the maximum delay of requests after a failure.
+    pub fn with_max_backoff(mut self, max_backoff: StdDuration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    fn retry(&self) -> Result<DateTime<Utc>, TimeError> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(next_request) = state.next_request {
+            if Instant::now() < next_request {
+                return Err(TimeError::new(
+                    "requests are delayed after a failure of the wrapped provider",
+                ));
+            }
+        }
+
+        let mut result = self.inner.try_current_time();
+        for _ in 0..self.retries {
+            if result.is_ok() {
+                break;
+            }
+            result = self.inner.try_current_time();
+        }
+
+        if result.is_ok() {
+            *state = RetryState::default();
+        } else {
+            let delay = state.delay.map_or(self.backoff, |delay| {
+                delay.checked_mul(2).unwrap_or(self.max_backoff)
+            });
+            let delay = delay.min(self.max_backoff);
+            state.delay = Some(delay);
+            state.next_request = Instant::now().checked_add(delay);
+        }
+        result
+    }
+}
+
+impl<T: TimeProvider> TimeProvider for RetryingTimeProvider<T> {
+    /// Returns the current time. If the time cannot be obtained from the wrapped provider,
+    /// the system time of the node is returned; use `try_current_time` to detect failures.
+    fn current_time(&self) -> DateTime<Utc> {
+        self.retry().unwrap_or_else(|_| Utc::now())
+    }
+
+    fn try_current_time(&self) -> Result<DateTime<Utc>, TimeError> {
+        self.retry()
+    }
+
+    fn describe(&self) -> ProviderInfo {
+        ProviderInfo::new("retrying").with_config(json!({
+            "retries": self.retries,
+            "backoff_millis": self.backoff.as_millis() as u64,
+            "max_backoff_millis": self.max_backoff.as_millis() as u64,
+            "provider": self.inner.describe(),
+        }))
+    }
+}
+
+#[allow(clippy::use_self)] // false positive
+impl<T: TimeProvider + 'static> From<RetryingTimeProvider<T>> for Arc<dyn TimeProvider> {
+    fn from(time_provider: RetryingTimeProvider<T>) -> Self {
+        Arc::new(time_provider)
+    }
+}
+
+/// Time provider deriving time deterministically from the blockchain height.
+///
+/// The provider reports `genesis + step * height`, where `height` is returned by
+/// the closure passed on construction; usually, it is the height of the latest committed block.
+/// Since the reported time does not depend on the wall clock, blockchains using this provider
+/// have a perfectly reproducible time progression, which is useful for testing.
+/// If the reported time overflows, `try_current_time` returns an error.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use exonum::helpers::Height;
+/// use exonum_time::{DeterministicTimeProvider, TimeProvider};
+/// use std::sync::{
+///     atomic::{AtomicU64, Ordering},
+///     Arc,
+/// };
+///
+/// let height = Arc::new(AtomicU64::new(0));
+/// let height_ = Arc::clone(&height);
+/// let provider = DeterministicTimeProvider::new(
+///     Utc.timestamp(100, 0),
+///     Duration::seconds(5),
+///     move || Height(height_.load(Ordering::SeqCst)),
+/// );
+/// assert_eq!(provider.current_time(), Utc.timestamp(100, 0));
+/// height.store(3, Ordering::SeqCst);
+/// assert_eq!(provider.current_time(), Utc.timestamp(115, 0));
+/// ```
+pub struct DeterministicTimeProvider {
+    genesis: DateTime<Utc>,
+    step: Duration,
+    height: Box<dyn Fn() -> Height + Send + Sync>,
+}
+
+impl DeterministicTimeProvider {
+    /// Creates a provider reporting `genesis` time at height 0 and advancing it by `step`
+    /// with each height, as returned by the `height` closure.
+    pub fn new<F>(genesis: DateTime<Utc>, step: Duration, height: F) -> Self
+    where
+        F: Fn() -> Height + Send + Sync + 'static,
+    {
+        Self {
+            genesis,
+            step,
+            height: Box::new(height),
+        }
+    }
+}
+
+impl fmt::Debug for DeterministicTimeProvider {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("DeterministicTimeProvider")
+            .field("genesis", &self.genesis)
+            .field("step", &self.step)
+            .finish()
+    }
+}
+
+impl TimeProvider for DeterministicTimeProvider {
+    /// Returns the current time.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the reported time overflows, i.e., cannot be represented as `DateTime`.
+    fn current_time(&self) -> DateTime<Utc> {
+        self.try_current_time().expect("Time provider failed")
+    }
+
+    fn try_current_time(&self) -> Result<DateTime<Utc>, TimeError> {
+        let height = (self.height)().0;
+        let time = i64::try_from(height)
+            .ok()
+            .and_then(|height| self.step.num_nanoseconds()?.checked_mul(height))
+            .map(Duration::nanoseconds)
+            .and_then(|offset| self.genesis.checked_add_signed(offset));
+        time.ok_or_else(|| TimeError::new(format!("time overflows at height {}", height)))
+    }
+
+    fn describe(&self) -> ProviderInfo {
+        ProviderInfo::new("deterministic").with_config(json!({
+            "genesis": self.genesis,
+            "step_millis": self.step.num_milliseconds(),
+        }))
+    }
 }
 
 /// Mock time provider for service testing.
@@ -115,6 +513,265 @@ impl TimeProvider for MockTimeProvider {
     fn current_time(&self) -> DateTime<Utc> {
         self.time()
     }
+
+    fn describe(&self) -> ProviderInfo {
+        ProviderInfo::new("mock")
+    }
+}
+
+/// Time provider that can be switched to a failing state, in which it does not report time.
+///
+/// This provider is intended for testing how services handle time provider errors.
+/// Similar to [`MockTimeProvider`], clones of the provider control the same state
+/// as the original instance. While the provider is not failing, it reports the time
+/// of the wrapped [`MockTimeProvider`].
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use exonum_time::{FailingTimeProvider, MockTimeProvider, TimeProvider};
+///
+/// let provider = FailingTimeProvider::new(MockTimeProvider::new(Utc.timestamp(10, 0)));
+/// assert_eq!(provider.try_current_time(), Ok(Utc.timestamp(10, 0)));
+/// provider.set_failing(true);
+/// assert!(provider.try_current_time().is_err());
+/// ```
+///
+/// [`MockTimeProvider`]: struct.MockTimeProvider.html
+#[derive(Debug, Clone, Default)]
+pub struct FailingTimeProvider {
+    inner: MockTimeProvider,
+    is_failing: Arc<AtomicBool>,
+}
+
+impl FailingTimeProvider {
+    /// Creates a non-failing provider reporting the time of `inner`.
+    pub fn new(inner: MockTimeProvider) -> Self {
+        Self {
+            inner,
+            is_failing: Arc::default(),
+        }
+    }
+
+    /// Returns the wrapped mock provider, which can be used to adjust the reported time.
+    pub fn inner(&self) -> &MockTimeProvider {
+        &self.inner
+    }
+
+    /// Switches the provider to the failing state or back.
+    pub fn set_failing(&self, is_failing: bool) {
+        self.is_failing.store(is_failing, Ordering::SeqCst);
+    }
+}
+
+impl TimeProvider for FailingTimeProvider {
+    /// Returns the current time.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the provider is in the failing state.
+    fn current_time(&self) -> DateTime<Utc> {
+        self.try_current_time().expect("Time provider failed")
+    }
+
+    fn try_current_time(&self) -> Result<DateTime<Utc>, TimeError> {
+        if self.is_failing.load(Ordering::SeqCst) {
+            Err(TimeError::new("provider is switched to the failing state"))
+        } else {
+            Ok(self.inner.time())
+        }
+    }
+
+    fn describe(&self) -> ProviderInfo {
+        ProviderInfo::new("failing")
+    }
+}
+
+/// Time provider reporting times pushed into a channel, e.g., from a test thread.
+///
+/// The provider reports the latest time received from the channel; times pushed
+/// since the previous call are drained, so only the most recent one is observed.
+/// [`try_current_time`] never blocks and returns an error if no time has been pushed yet,
+/// in which case the node skips reporting time. [`current_time`], on the other hand,
+/// blocks until the first time is received.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{TimeZone, Utc};
+/// use exonum_time::{ChannelTimeProvider, TimeProvider};
+/// use std::sync::mpsc;
+///
+/// let (sender, receiver) = mpsc::channel();
+/// let provider = ChannelTimeProvider::new(receiver);
+/// assert!(provider.try_current_time().is_err());
+/// sender.send(Utc.timestamp(10, 0)).unwrap();
+/// sender.send(Utc.timestamp(20, 0)).unwrap();
+/// assert_eq!(provider.current_time(), Utc.timestamp(20, 0));
+/// // The last seen time is reported until a new one is pushed.
+/// assert_eq!(provider.try_current_time(), Ok(Utc.timestamp(20, 0)));
+/// ```
+///
+/// [`try_current_time`]: trait.TimeProvider.html#method.try_current_time
+/// [`current_time`]: trait.TimeProvider.html#tymethod.current_time
+#[derive(Debug)]
+pub struct ChannelTimeProvider {
+    inner: Mutex<ChannelState>,
+}
+
+#[derive(Debug)]
+struct ChannelState {
+    receiver: Receiver<DateTime<Utc>>,
+    last_seen: Option<DateTime<Utc>>,
+}
+
+impl ChannelState {
+    /// Drains times available in the channel without blocking, remembering the latest one.
+    fn poll(&mut self) {
+        while let Ok(time) = self.receiver.try_recv() {
+            self.last_seen = Some(time);
+        }
+    }
+}
+
+impl ChannelTimeProvider {
+    /// Creates a provider reporting times received from `receiver`.
+    pub fn new(receiver: Receiver<DateTime<Utc>>) -> Self {
+        Self {
+            inner: Mutex::new(ChannelState {
+                receiver,
+                last_seen: None,
+            }),
+        }
+    }
+}
+
+impl TimeProvider for ChannelTimeProvider {
+    /// Returns the latest time pushed into the channel, blocking until the first time
+    /// is received.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the channel is disconnected before any time is pushed into it.
+    fn current_time(&self) -> DateTime<Utc> {
+        let mut state = self.inner.lock().unwrap();
+        state.poll();
+        if let Some(time) = state.last_seen {
+            return time;
+        }
+        let time = state
+            .receiver
+            .recv()
+            .expect("Channel disconnected before any time was received");
+        state.last_seen = Some(time);
+        state.poll();
+        state.last_seen.unwrap()
+    }
+
+    fn try_current_time(&self) -> Result<DateTime<Utc>, TimeError> {
+        let mut state = self.inner.lock().unwrap();
+        state.poll();
+        state
+            .last_seen
+            .ok_or_else(|| TimeError::new("no time has been received from the channel"))
+    }
+
+    fn describe(&self) -> ProviderInfo {
+        ProviderInfo::new("channel")
+    }
+}
+
+/// Time provider combining several other providers and reporting the average of their times.
+///
+/// If the list of providers is empty, the system time is reported.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{Duration, TimeZone, Utc};
+/// use exonum_time::{AveragingTimeProvider, MockTimeProvider, TimeProvider};
+///
+/// let first = MockTimeProvider::new(Utc.timestamp(10, 0));
+/// let second = MockTimeProvider::new(Utc.timestamp(20, 0));
+/// let provider = AveragingTimeProvider::new(vec![Box::new(first), Box::new(second)]);
+/// assert_eq!(provider.current_time(), Utc.timestamp(15, 0));
+/// ```
+#[derive(Debug)]
+pub struct AveragingTimeProvider {
+    providers: Vec<Box<dyn TimeProvider>>,
+}
+
+impl AveragingTimeProvider {
+    /// Creates a provider averaging times reported by `providers`.
+    pub fn new(providers: Vec<Box<dyn TimeProvider>>) -> Self {
+        Self { providers }
+    }
+}
+
+impl AveragingTimeProvider {
+    /// Averages times reported by the providers. If any of the providers fails,
+    /// returns its error.
+    fn average<F>(&self, mut get_time: F) -> Result<DateTime<Utc>, TimeError>
+    where
+        F: FnMut(&dyn TimeProvider) -> Result<DateTime<Utc>, TimeError>,
+    {
+        const NANOS_IN_SECOND: i128 = 1_000_000_000;
+
+        if self.providers.is_empty() {
+            return Ok(Utc::now());
+        }
+
+        // Nanoseconds since the Unix epoch are summed as `i128`, so that the sum cannot overflow.
+        let mut total_nanos: i128 = 0;
+        for provider in &self.providers {
+            let time = get_time(provider.as_ref())?;
+            total_nanos += i128::from(time.timestamp()) * NANOS_IN_SECOND
+                + i128::from(time.timestamp_subsec_nanos());
+        }
+        let average_nanos = total_nanos / self.providers.len() as i128;
+
+        let secs = average_nanos.div_euclid(NANOS_IN_SECOND) as i64;
+        let nanos = average_nanos.rem_euclid(NANOS_IN_SECOND) as u32;
+        Ok(Utc.timestamp(secs, nanos))
+    }
+}
+
+impl TimeProvider for AveragingTimeProvider {
+    fn current_time(&self) -> DateTime<Utc> {
+        self.average(|provider| Ok(provider.current_time()))
+            .expect("`current_time` cannot fail")
+    }
+
+    fn try_current_time(&self) -> Result<DateTime<Utc>, TimeError> {
+        self.average(|provider| provider.try_current_time())
+    }
+
+    fn describe(&self) -> ProviderInfo {
+        let providers: Vec<_> = self.providers.iter().map(|p| p.describe()).collect();
+        ProviderInfo::new("averaging").with_config(json!({ "providers": providers }))
+    }
+}
+
+#[allow(clippy::use_self)] // false positive
+impl From<AveragingTimeProvider> for Arc<dyn TimeProvider> {
+    fn from(time_provider: AveragingTimeProvider) -> Self {
+        Arc::new(time_provider)
+    }
+}
+
+#[allow(clippy::use_self)] // false positive
+impl From<ChannelTimeProvider> for Arc<dyn TimeProvider> {
+    fn from(time_provider: ChannelTimeProvider) -> Self {
+        Arc::new(time_provider)
+    }
+}
+
+#[allow(clippy::use_self)] // false positive
+impl From<DeterministicTimeProvider> for Arc<dyn TimeProvider> {
+    fn from(time_provider: DeterministicTimeProvider) -> Self {
+        Arc::new(time_provider)
+    }
 }
 
 #[allow(clippy::use_self)] // false positive
@@ -124,6 +781,13 @@ impl From<MockTimeProvider> for Arc<dyn TimeProvider> {
     }
 }
 
+#[allow(clippy::use_self)] // false positive
+impl From<FailingTimeProvider> for Arc<dyn TimeProvider> {
+    fn from(time_provider: FailingTimeProvider) -> Self {
+        Arc::new(time_provider)
+    }
+}
+
 #[allow(clippy::use_self)] // false positive
 impl From<SystemTimeProvider> for Arc<dyn TimeProvider> {
     fn from(time_provider: SystemTimeProvider) -> Self {
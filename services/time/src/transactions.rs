@@ -13,21 +13,43 @@
 // limitations under the License.
 
 use chrono::{DateTime, Utc};
-use exonum::runtime::{CommonError, ExecutionContext, ExecutionError};
+use exonum::{
+    crypto::{self, KeyPair, PublicKey, Signature},
+    merkledb::BinaryValue,
+    messages::{CoreMessage, SignedMessage, Verified},
+    runtime::{AnyTx, CallInfo, CommonError, ExecutionContext, ExecutionError, InstanceId},
+};
 use exonum_derive::{exonum_interface, interface_method, BinaryValue, ExecutionFail, ObjectHash};
 use exonum_proto::ProtobufConvert;
 use serde::{Deserialize, Serialize};
 
-use crate::{proto, schema::TimeSchema, TimeService};
+use crate::{proto, schema::TimeSchema, RecomputePolicy, TimeService};
 
 /// Common errors emitted by transactions during execution.
 #[derive(Debug, ExecutionFail)]
 pub enum Error {
     /// The validator time that is stored in storage is greater than the proposed one.
     ValidatorTimeIsGreater = 0,
+    /// The transaction author is not a validator according to the current consensus
+    /// configuration.
+    UnknownSender = 1,
+    /// The sequence number of the transaction is not greater than the last sequence number
+    /// stored for the validator, i.e., the transaction is a replay of an older report.
+    StaleSequenceNumber = 2,
+    /// The reported time is too close to the previously reported time of the validator,
+    /// according to the minimum submit interval of the service.
+    TooFrequent = 3,
+    /// The configuration passed to the service instance on initialization is invalid.
+    InvalidConfig = 4,
 }
 
 /// Transaction that is sent by the validator after the commit of the block.
+///
+/// The transaction may contain a sequence number protecting against replays of older
+/// reports. If the number is set (i.e., is non-zero), it must be greater than the number
+/// in the latest report of the validator containing a sequence number; otherwise,
+/// the transaction fails with the `StaleSequenceNumber` error. Reports without
+/// a sequence number are not checked.
 #[derive(Clone, Debug)]
 #[derive(Serialize, Deserialize)]
 #[derive(ProtobufConvert, BinaryValue, ObjectHash)]
@@ -35,12 +57,145 @@ pub enum Error {
 pub struct TxTime {
     /// Time of the validator.
     pub time: DateTime<Utc>,
+    /// Sequence number of the report, or 0 if the number is not set.
+    #[serde(default)]
+    pub seq: u64,
 }
 
 impl TxTime {
-    /// Creates a new transaction.
+    /// Identifier of the `report_time` method in `TimeOracleInterface`.
+    const REPORT_TIME_METHOD_ID: u32 = 0;
+
+    /// Creates a new transaction without a sequence number.
     pub fn new(time: DateTime<Utc>) -> Self {
-        Self { time }
+        Self { time, seq: 0 }
+    }
+
+    /// Sets the sequence number of the report.
+    pub fn with_seq(mut self, seq: u64) -> Self {
+        self.seq = seq;
+        self
+    }
+
+    /// Returns the bytes to be signed in order to report `time` to the service instance
+    /// with the specified ID. This allows signing the report with an offline key;
+    /// the signed transaction can then be assembled with [`from_signed`].
+    ///
+    /// [`from_signed`]: #method.from_signed
+    pub fn build_unsigned(instance_id: InstanceId, time: DateTime<Utc>) -> Vec<u8> {
+        let call_info = CallInfo::new(instance_id, Self::REPORT_TIME_METHOD_ID);
+        let tx = AnyTx::new(call_info, Self::new(time).into_bytes());
+        CoreMessage::from(tx).into_bytes()
+    }
+
+    /// Assembles a signed transaction from the bytes produced by [`build_unsigned`]
+    /// and an externally produced signature over these bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signature is incorrect, or if the bytes do not encode
+    /// a `report_time` call.
+    ///
+    /// [`build_unsigned`]: #method.build_unsigned
+    pub fn from_signed(
+        bytes: Vec<u8>,
+        public_key: PublicKey,
+        signature: Signature,
+    ) -> anyhow::Result<Verified<AnyTx>> {
+        let signed = SignedMessage {
+            payload: bytes,
+            author: public_key,
+            signature,
+        };
+        let tx = signed.into_verified::<AnyTx>()?;
+        anyhow::ensure!(
+            tx.payload().call_info.method_id == Self::REPORT_TIME_METHOD_ID,
+            "Transaction is not a `report_time` call"
+        );
+        tx.payload().parse::<Self>()?;
+        Ok(tx)
+    }
+}
+
+/// Time reported by a validator, which can be submitted by any node as a part
+/// of [`TxTimeBatch`].
+///
+/// The report is signed with the service key of the validator. The signed data consists
+/// of the domain separation tag `exonum-time:report`, the identifier of the time service
+/// instance (4 bytes, little-endian) and the binary serialization of [`TxTime`] with
+/// the reported time. Thus, a report signed for one service instance cannot be replayed
+/// to another instance, and signatures over reports cannot be confused with signatures
+/// over other data (e.g., transactions).
+///
+/// [`TxTimeBatch`]: struct.TxTimeBatch.html
+/// [`TxTime`]: struct.TxTime.html
+#[derive(Clone, Debug)]
+#[derive(Serialize, Deserialize)]
+#[derive(ProtobufConvert, BinaryValue, ObjectHash)]
+#[protobuf_convert(source = "proto::TimeReport")]
+pub struct TimeReport {
+    /// Service key of the validator.
+    pub public_key: PublicKey,
+    /// Time of the validator.
+    pub time: DateTime<Utc>,
+    /// Signature of the validator.
+    pub signature: Signature,
+}
+
+impl TimeReport {
+    /// Domain separation tag prepended to the signed data.
+    const DOMAIN_TAG: &'static [u8] = b"exonum-time:report";
+
+    /// Creates a new report with the specified time for the time service instance
+    /// with the specified ID, signed by the provided keys.
+    pub fn new(instance_id: InstanceId, time: DateTime<Utc>, keys: &KeyPair) -> Self {
+        let signature = crypto::sign(&Self::signed_data(instance_id, time), keys.secret_key());
+        Self {
+            public_key: keys.public_key(),
+            time,
+            signature,
+        }
+    }
+
+    /// Checks whether the report signature is correct for the time service instance
+    /// with the specified ID.
+    pub fn verify(&self, instance_id: InstanceId) -> bool {
+        crypto::verify(
+            &self.signature,
+            &Self::signed_data(instance_id, self.time),
+            &self.public_key,
+        )
+    }
+
+    fn signed_data(instance_id: InstanceId, time: DateTime<Utc>) -> Vec<u8> {
+        let mut data = Self::DOMAIN_TAG.to_vec();
+        data.extend_from_slice(&instance_id.to_le_bytes());
+        data.extend_from_slice(&TxTime::new(time).to_bytes());
+        data
+    }
+}
+
+/// Transaction reporting times of several validators at once.
+///
+/// Each report is processed like a separate [`TxTime`] transaction from the corresponding
+/// validator. Reports with an incorrect signature, reports from nodes that are not
+/// validators, and reports with a time not greater than the known time
+/// of the validator are skipped without failing the transaction.
+///
+/// [`TxTime`]: struct.TxTime.html
+#[derive(Clone, Debug)]
+#[derive(Serialize, Deserialize)]
+#[derive(ProtobufConvert, BinaryValue, ObjectHash)]
+#[protobuf_convert(source = "proto::TxTimeBatch")]
+pub struct TxTimeBatch {
+    /// Reports of the validators.
+    pub reports: Vec<TimeReport>,
+}
+
+impl TxTimeBatch {
+    /// Creates a new transaction.
+    pub fn new(reports: Vec<TimeReport>) -> Self {
+        Self { reports }
     }
 }
 
@@ -52,9 +207,16 @@ pub trait TimeOracleInterface<Ctx> {
 
     /// Receives a new time from one of validators.
     ///
-    /// Transaction sent not by a validator will be discarded.
+    /// Transaction sent not by a validator will fail with the `UnknownSender` error.
     #[interface_method(id = 0)]
     fn report_time(&self, ctx: Ctx, arg: TxTime) -> Self::Output;
+
+    /// Receives new times from several validators at once.
+    ///
+    /// Unlike `report_time`, the transaction can be sent by any node; invalid reports
+    /// within the batch are skipped.
+    #[interface_method(id = 1)]
+    fn report_times(&self, ctx: Ctx, arg: TxTimeBatch) -> Self::Output;
 }
 
 impl TimeOracleInterface<ExecutionContext<'_>> for TimeService {
@@ -69,15 +231,50 @@ impl TimeOracleInterface<ExecutionContext<'_>> for TimeService {
         let core_schema = context.data().for_core();
         core_schema
             .validator_id(author)
-            .ok_or(CommonError::UnauthorizedCaller)?;
+            .ok_or(Error::UnknownSender)?;
 
         let mut schema = TimeSchema::new(context.service_data());
+        if schema.is_too_frequent(&author, arg.time) {
+            return Err(Error::TooFrequent.into());
+        }
+        if arg.seq != 0 {
+            schema
+                .update_validator_seq(author, arg.seq)
+                .map_err(|()| Error::StaleSequenceNumber)?;
+        }
         schema
-            .update_validator_time(author, arg.time)
+            .update_validator_time(author, arg.time, core_schema.next_height())
             .map_err(|()| Error::ValidatorTimeIsGreater)?;
 
-        let validator_keys = core_schema.consensus_config().validator_keys;
-        schema.update_consolidated_time(&validator_keys);
+        if schema.config().recompute_policy == RecomputePolicy::EveryTx {
+            let validator_keys = core_schema.consensus_config().validator_keys;
+            schema.update_consolidated_time(&validator_keys);
+        }
+        Ok(())
+    }
+
+    fn report_times(&self, context: ExecutionContext<'_>, arg: TxTimeBatch) -> Self::Output {
+        let core_schema = context.data().for_core();
+        let instance_id = context.instance().id;
+        let mut schema = TimeSchema::new(context.service_data());
+
+        for report in arg.reports {
+            if !report.verify(instance_id)
+                || core_schema.validator_id(report.public_key).is_none()
+                || schema.is_too_frequent(&report.public_key, report.time)
+            {
+                continue;
+            }
+            // Outdated reports are skipped similar to invalid ones.
+            schema
+                .update_validator_time(report.public_key, report.time, core_schema.next_height())
+                .ok();
+        }
+
+        if schema.config().recompute_policy == RecomputePolicy::EveryTx {
+            let validator_keys = core_schema.consensus_config().validator_keys;
+            schema.update_consolidated_time(&validator_keys);
+        }
         Ok(())
     }
 }
@@ -14,20 +14,36 @@
 
 use chrono::{DateTime, Duration, TimeZone, Utc};
 use exonum::{
-    crypto::{KeyPair, PublicKey},
-    helpers::Height,
-    merkledb::{access::Access, Snapshot},
-    runtime::{CommonError, ErrorMatch, InstanceId, SnapshotExt, SUPERVISOR_INSTANCE_ID},
+    blockchain::Blockchain,
+    crypto::{self, Hash, KeyPair, PublicKey},
+    helpers::{Height, ValidatorId},
+    keys::Keys,
+    merkledb::{access::Access, ObjectHash, Snapshot},
+    runtime::{ErrorMatch, InstanceId, SnapshotExt, SUPERVISOR_INSTANCE_ID},
 };
+use exonum_api::HttpStatusCode;
+use exonum_rust_runtime::TxStub;
 use exonum_supervisor::{ConfigPropose, Supervisor, SupervisorInterface};
 use exonum_testkit::{ApiKind, Spec, TestKit, TestKitApi, TestKitBuilder, TestNode};
 use pretty_assertions::assert_eq;
 
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    thread,
+    time::{Duration as StdDuration, Instant, SystemTime},
+};
 
 use exonum_time::{
-    Error, MockTimeProvider, TimeOracleInterface, TimeSchema, TimeServiceFactory, TxTime,
-    ValidatorTime,
+    AveragingTimeProvider, ChannelTimeProvider, CurrentTimeQuery, DeterministicTimeProvider, Error,
+    FailingTimeProvider, MockTimeProvider, MonotonicTimeProvider, ProviderInfo, RecomputePolicy,
+    RetryingTimeProvider, TimeConfig, TimeError, TimeFormat, TimeOracleInterface, TimeProvider,
+    TimeReport, TimeSchema, TimeService, TimeServiceFactory, TimeWithProof, TxTime, TxTimeBatch,
+    ValidatorTime, ValidatorTimeDelta, ValidatorTimeDrift, ValidatorsTimesDeltaQuery,
+    ValidatorsTimesPage, ValidatorsTimesQuery, MAX_VALIDATORS_TIMES_PER_PAGE,
 };
 
 const INSTANCE_ID: InstanceId = 112;
@@ -44,12 +60,12 @@ fn assert_storage_times_eq(
     expected_validators_times: &[Option<DateTime<Utc>>],
 ) {
     let schema = get_schema(snapshot);
-    assert_eq!(schema.time.get(), expected_current_time);
+    assert_eq!(schema.consolidated_time(), expected_current_time);
 
     for (i, validator) in validators.iter().enumerate() {
         let public_key = &validator.public_keys().service_key;
         assert_eq!(
-            schema.validators_times.get(&public_key),
+            schema.validator_time(public_key),
             expected_validators_times[i]
         );
     }
@@ -253,6 +269,387 @@ fn test_exonum_time_service_with_7_validators() {
     }
 }
 
+#[test]
+fn test_outlier_window() {
+    let time_service = TimeServiceFactory::with_provider(MockTimeProvider::default());
+    let config = TimeConfig::default().with_outlier_window(Duration::minutes(1));
+    let mut testkit = TestKitBuilder::validator()
+        .with_validators(4)
+        .with(Spec::new(time_service).with_instance(INSTANCE_ID, INSTANCE_NAME, config))
+        .build();
+    let validators = testkit.network().validators().to_vec();
+
+    // The last validator's clock is an hour ahead. Without the outlier window,
+    // the consolidated time would be `base_time + 2s`.
+    let base_time = Utc::now();
+    let offsets = [
+        Duration::zero(),
+        Duration::seconds(1),
+        Duration::seconds(2),
+        Duration::hours(1),
+    ];
+    let txs = validators.iter().zip(&offsets).map(|(validator, &offset)| {
+        validator
+            .service_keypair()
+            .report_time(INSTANCE_ID, TxTime::new(base_time + offset))
+    });
+    let block = testkit.create_block_with_transactions(txs);
+    for tx in block.iter() {
+        tx.status().unwrap();
+    }
+
+    let snapshot = testkit.snapshot();
+    let schema = get_schema(&snapshot);
+    assert_eq!(schema.time.get(), Some(base_time + Duration::seconds(1)));
+    // The discarded time is still stored.
+    let outlier_key = validators[3].public_keys().service_key;
+    assert_eq!(
+        schema.validators_times.get(&outlier_key),
+        Some(base_time + Duration::hours(1))
+    );
+}
+
+#[test]
+fn test_weighted_consolidation() {
+    let keys: Vec<_> = (0..4).map(|_| Keys::random()).collect();
+    // The last validator has more weight than the other validators combined.
+    let weights: HashMap<_, _> = keys
+        .iter()
+        .zip(&[1, 1, 1, 10])
+        .map(|(keys, &weight)| (keys.service_pk(), weight))
+        .collect();
+
+    let config = TimeConfig::default().with_weights(weights);
+    let time_service = create_failing_time_service();
+    let mut testkit = TestKitBuilder::validator()
+        .with_keys(keys.clone())
+        .with(Spec::new(time_service).with_instance(INSTANCE_ID, INSTANCE_NAME, config))
+        .build();
+
+    // Without weights, the consolidated time would be `base_time + 2s`.
+    let base_time = Utc.timestamp(10_000, 0);
+    let offsets = [3, 2, 1, 0];
+    let txs = keys.iter().zip(&offsets).map(|(keys, &offset)| {
+        let time = base_time + Duration::seconds(offset);
+        (keys.service_pk(), keys.service_sk().clone()).report_time(INSTANCE_ID, TxTime::new(time))
+    });
+    let block = testkit.create_block_with_transactions(txs);
+    for tx in block.iter() {
+        tx.status().unwrap();
+    }
+
+    let snapshot = testkit.snapshot();
+    assert_eq!(get_schema(&snapshot).time.get(), Some(base_time));
+}
+
+#[test]
+#[should_panic(expected = "Total weight of validators overflows")]
+fn test_weights_overflow() {
+    let weights = vec![
+        (Keys::random().service_pk(), u64::max_value()),
+        (Keys::random().service_pk(), 1),
+    ];
+    TimeConfig::default().with_weights(weights);
+}
+
+#[test]
+fn test_recompute_policy_every_n_blocks() {
+    let config = TimeConfig::default().with_recompute_policy(RecomputePolicy::EveryNBlocks(3));
+    let mut testkit = create_testkit_with_config(1, config);
+    let validator = testkit.network().validators()[0].service_keypair();
+    let public_key = validator.public_key();
+
+    let base_time = Utc.timestamp(10_000, 0);
+    let mut consolidated_times = vec![];
+    for i in 1..=6 {
+        let time = base_time + Duration::seconds(i);
+        let tx = validator.report_time(INSTANCE_ID, TxTime::new(time));
+        let block = testkit.create_block_with_transaction(tx);
+        block[0].status().unwrap();
+
+        let snapshot = testkit.snapshot();
+        let schema = get_schema(&snapshot);
+        // The validator time is updated by each transaction.
+        assert_eq!(schema.validators_times.get(&public_key), Some(time));
+        consolidated_times.push(schema.time.get());
+    }
+
+    let time_at = |height: i64| Some(base_time + Duration::seconds(height));
+    assert_eq!(
+        consolidated_times,
+        vec![None, None, time_at(3), time_at(3), time_at(3), time_at(6)]
+    );
+}
+
+#[test]
+fn test_max_step() {
+    let config = TimeConfig::default().with_max_step(Duration::minutes(10));
+    let mut testkit = create_testkit_with_config(1, config);
+    let validator = testkit.network().validators()[0].service_keypair();
+
+    // The first consolidated time is not clamped.
+    let base_time = Utc.timestamp(10_000, 0);
+    let tx = validator.report_time(INSTANCE_ID, TxTime::new(base_time));
+    testkit.create_block_with_transaction(tx)[0]
+        .status()
+        .unwrap();
+    assert_eq!(get_schema(&testkit.snapshot()).time.get(), Some(base_time));
+
+    // The validator clock jumps by an hour; the consolidated time advances by the step only.
+    for i in 1..=6 {
+        let time = base_time + Duration::hours(1) + Duration::seconds(i);
+        let tx = validator.report_time(INSTANCE_ID, TxTime::new(time));
+        testkit.create_block_with_transaction(tx)[0]
+            .status()
+            .unwrap();
+
+        let snapshot = testkit.snapshot();
+        let schema = get_schema(&snapshot);
+        assert_eq!(
+            schema.validators_times.get(&validator.public_key()),
+            Some(time)
+        );
+        assert_eq!(
+            schema.time.get(),
+            Some(base_time + Duration::minutes(10 * i))
+        );
+    }
+
+    // Once the consolidated time catches up, it is no longer clamped.
+    let time = base_time + Duration::hours(1) + Duration::seconds(10);
+    let tx = validator.report_time(INSTANCE_ID, TxTime::new(time));
+    testkit.create_block_with_transaction(tx)[0]
+        .status()
+        .unwrap();
+    assert_eq!(get_schema(&testkit.snapshot()).time.get(), Some(time));
+}
+
+#[test]
+fn test_min_reporters() {
+    let config = TimeConfig::default().with_min_reporters(2);
+    let mut testkit = create_testkit_with_config(3, config);
+    let validators = testkit.network().validators().to_vec();
+
+    // Without the option, the first report would be consolidated immediately
+    // (see `test_exonum_time_service_with_3_validators`).
+    let time0 = Utc.timestamp(10_000, 0);
+    let tx0 = validators[0]
+        .service_keypair()
+        .report_time(INSTANCE_ID, TxTime::new(time0));
+    testkit.create_block_with_transaction(tx0)[0]
+        .status()
+        .unwrap();
+    assert_storage_times_eq(
+        &testkit.snapshot(),
+        &validators,
+        None,
+        &[Some(time0), None, None],
+    );
+
+    // Another report by the same validator does not activate the consolidation.
+    let time0 = time0 + Duration::seconds(5);
+    let tx0 = validators[0]
+        .service_keypair()
+        .report_time(INSTANCE_ID, TxTime::new(time0));
+    testkit.create_block_with_transaction(tx0)[0]
+        .status()
+        .unwrap();
+    assert_storage_times_eq(
+        &testkit.snapshot(),
+        &validators,
+        None,
+        &[Some(time0), None, None],
+    );
+
+    let time1 = time0 - Duration::seconds(3);
+    let tx1 = validators[1]
+        .service_keypair()
+        .report_time(INSTANCE_ID, TxTime::new(time1));
+    testkit.create_block_with_transaction(tx1)[0]
+        .status()
+        .unwrap();
+    assert_storage_times_eq(
+        &testkit.snapshot(),
+        &validators,
+        Some(time0),
+        &[Some(time0), Some(time1), None],
+    );
+}
+
+#[test]
+fn test_genesis_time() {
+    let genesis_time = Utc.timestamp(100, 0);
+    let mock_provider = MockTimeProvider::new(Utc.timestamp(50, 0));
+    let time_service = TimeServiceFactory::with_provider(mock_provider.clone());
+    let config = TimeConfig::default().with_genesis_time(SystemTime::from(genesis_time));
+    let mut testkit = TestKitBuilder::validator()
+        .with(Spec::new(time_service).with_instance(INSTANCE_ID, INSTANCE_NAME, config))
+        .build();
+
+    // The time is available right after the genesis block.
+    assert_eq!(testkit.height(), Height(0));
+    let snapshot = testkit.snapshot();
+    assert_eq!(get_schema(&snapshot).time.get(), Some(genesis_time));
+
+    // Reports earlier than the genesis time do not override it.
+    testkit.create_blocks_until(Height(2));
+    let snapshot = testkit.snapshot();
+    assert_eq!(get_schema(&snapshot).time.get(), Some(genesis_time));
+
+    // Later reports override the genesis time as usual.
+    mock_provider.set_time(Utc.timestamp(150, 0));
+    testkit.create_blocks_until(Height(4));
+    let snapshot = testkit.snapshot();
+    assert_eq!(
+        get_schema(&snapshot).time.get(),
+        Some(Utc.timestamp(150, 0))
+    );
+}
+
+#[test]
+fn test_min_submit_interval() {
+    let config = TimeConfig::default().with_min_submit_interval(Duration::seconds(30));
+    let mut testkit = create_testkit_with_config(1, config);
+    let validator = testkit.network().validators()[0].service_keypair();
+
+    let base_time = Utc.timestamp(10_000, 0);
+    let tx = validator.report_time(INSTANCE_ID, TxTime::new(base_time));
+    testkit.create_block_with_transaction(tx)[0]
+        .status()
+        .unwrap();
+
+    // The second report within the interval is rejected.
+    let time = base_time + Duration::seconds(10);
+    let tx = validator.report_time(INSTANCE_ID, TxTime::new(time));
+    let block = testkit.create_block_with_transaction(tx);
+    let expected_err = ErrorMatch::from_fail(&Error::TooFrequent).for_service(INSTANCE_ID);
+    assert_eq!(*block[0].status().unwrap_err(), expected_err);
+    let snapshot = testkit.snapshot();
+    let schema = get_schema(&snapshot);
+    assert_eq!(
+        schema.validators_times.get(&validator.public_key()),
+        Some(base_time)
+    );
+
+    // Reports separated by at least the interval are accepted.
+    let time = base_time + Duration::seconds(30);
+    let tx = validator.report_time(INSTANCE_ID, TxTime::new(time));
+    testkit.create_block_with_transaction(tx)[0]
+        .status()
+        .unwrap();
+    let snapshot = testkit.snapshot();
+    assert_eq!(get_schema(&snapshot).time.get(), Some(time));
+}
+
+#[test]
+fn test_equal_validator_times() {
+    // Equal times are interchangeable, so the consolidated time does not depend
+    // on the order in which validators report them.
+    let time = Utc.timestamp(10_000, 0);
+    let consolidate = |reverse_order: bool| {
+        let mut testkit = create_testkit_with_config(3, TimeConfig::default());
+
+        let mut validators = testkit.network().validators().to_vec();
+        if reverse_order {
+            validators.reverse();
+        }
+        for validator in &validators {
+            let tx = validator
+                .service_keypair()
+                .report_time(INSTANCE_ID, TxTime::new(time));
+            testkit.create_block_with_transaction(tx)[0]
+                .status()
+                .unwrap();
+        }
+        get_schema(&testkit.snapshot()).time.get()
+    };
+
+    assert_eq!(consolidate(false), Some(time));
+    assert_eq!(consolidate(true), Some(time));
+}
+
+#[test]
+fn test_deterministic_provider() {
+    let genesis_time = Utc.timestamp(1_000, 0);
+    let step = Duration::seconds(10);
+    // The blockchain is not available until the testkit is built.
+    let blockchain: Arc<Mutex<Option<Blockchain>>> = Arc::default();
+    let blockchain_ = Arc::clone(&blockchain);
+    let provider = DeterministicTimeProvider::new(genesis_time, step, move || {
+        let blockchain = blockchain_.lock().unwrap();
+        blockchain
+            .as_ref()
+            .map_or(Height(0), |blockchain| blockchain.last_block().height)
+    });
+
+    let time_service = TimeServiceFactory::with_provider(provider);
+    let mut testkit = TestKitBuilder::validator()
+        .with(Spec::new(time_service).with_instance(INSTANCE_ID, INSTANCE_NAME, ()))
+        .build();
+    *blockchain.lock().unwrap() = Some(testkit.blockchain());
+
+    testkit.create_blocks_until(Height(2));
+    let mut prev_time = get_schema(&testkit.snapshot()).time.get().unwrap();
+    for _ in 0..5 {
+        testkit.create_block();
+        let time = get_schema(&testkit.snapshot()).time.get().unwrap();
+        assert_eq!(time - prev_time, step);
+        prev_time = time;
+    }
+}
+
+#[test]
+fn test_deterministic_provider_overflow() {
+    let genesis_time = Utc.timestamp(1_000, 0);
+    let provider = DeterministicTimeProvider::new(genesis_time, Duration::milliseconds(1), || {
+        Height(3_000_000_000)
+    });
+    // The height does not fit into `i32`, but the time is still representable.
+    assert_eq!(
+        provider.try_current_time(),
+        Ok(genesis_time + Duration::seconds(3_000_000))
+    );
+
+    let provider =
+        DeterministicTimeProvider::new(genesis_time, Duration::seconds(1), || Height(u64::MAX));
+    assert_eq!(
+        provider.try_current_time(),
+        Err(TimeError::new(format!(
+            "time overflows at height {}",
+            u64::MAX
+        )))
+    );
+}
+
+#[test]
+fn test_channel_provider() {
+    let (sender, receiver) = mpsc::channel();
+    let time_service = TimeServiceFactory::with_provider(ChannelTimeProvider::new(receiver));
+    let mut testkit = TestKitBuilder::validator()
+        .with(Spec::new(time_service).with_instance(INSTANCE_ID, INSTANCE_NAME, ()))
+        .build();
+
+    // No time is pushed yet, so the node does not report time.
+    testkit.create_blocks_until(Height(2));
+    assert_eq!(get_schema(&testkit.snapshot()).consolidated_time(), None);
+
+    let times = vec![
+        Utc.timestamp(10, 0),
+        Utc.timestamp(25, 0),
+        Utc.timestamp(31, 0),
+    ];
+    let mut consolidated_times = vec![];
+    for &time in &times {
+        sender.send(time).unwrap();
+        // The time is reported after the first block and is committed in the second one.
+        testkit.create_block();
+        testkit.create_block();
+        let snapshot = testkit.snapshot();
+        consolidated_times.push(get_schema(&snapshot).consolidated_time().unwrap());
+    }
+    assert_eq!(consolidated_times, times);
+}
+
 #[test]
 fn test_mock_provider() {
     let mock_provider = MockTimeProvider::default();
@@ -297,6 +694,216 @@ fn test_mock_provider() {
     );
 }
 
+#[test]
+fn test_failing_provider() {
+    let provider = FailingTimeProvider::default();
+    provider.inner().add_time(Duration::seconds(10));
+    // While the provider fails, the node does not report time.
+    provider.set_failing(true);
+    let time_service = TimeServiceFactory::with_provider(provider.clone());
+    let mut testkit = TestKitBuilder::validator()
+        .with(Spec::new(time_service).with_instance(INSTANCE_ID, INSTANCE_NAME, ()))
+        .build();
+    let validators = testkit.network().validators().to_vec();
+    testkit.create_blocks_until(Height(3));
+    assert_storage_times_eq(&testkit.snapshot(), &validators, None, &[None]);
+
+    // Once the provider recovers, the time is reported again.
+    provider.set_failing(false);
+    testkit.create_blocks_until(Height(5));
+    let time = Some(Utc.timestamp(10, 0));
+    assert_storage_times_eq(&testkit.snapshot(), &validators, time, &[time]);
+}
+
+/// Time provider returning the predefined sequence of times.
+#[derive(Debug)]
+struct ScriptedTimeProvider {
+    times: Mutex<VecDeque<DateTime<Utc>>>,
+}
+
+impl ScriptedTimeProvider {
+    fn new(times: impl IntoIterator<Item = DateTime<Utc>>) -> Self {
+        Self {
+            times: Mutex::new(times.into_iter().collect()),
+        }
+    }
+}
+
+impl TimeProvider for ScriptedTimeProvider {
+    fn current_time(&self) -> DateTime<Utc> {
+        self.times
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("No more scripted times")
+    }
+}
+
+#[test]
+fn test_monotonic_provider() {
+    let secs = [10, 20, 15, 5, 20, 25, 24, 30];
+    let source = ScriptedTimeProvider::new(secs.iter().map(|&s| Utc.timestamp(s, 0)));
+    let provider = MonotonicTimeProvider::new(source);
+
+    let times: Vec<_> = secs.iter().map(|_| provider.current_time()).collect();
+    assert!(times.windows(2).all(|window| window[0] <= window[1]));
+    let expected_secs = [10, 20, 20, 20, 20, 25, 25, 30];
+    let expected_times: Vec<_> = expected_secs.iter().map(|&s| Utc.timestamp(s, 0)).collect();
+    assert_eq!(times, expected_times);
+}
+
+#[test]
+fn test_monotonic_provider_with_distant_times() {
+    // Times outside of the range of `i64` nanoseconds since the Unix epoch.
+    let before_epoch = Utc.ymd(1600, 1, 1).and_hms(0, 0, 0);
+    let far_future = Utc.ymd(3000, 1, 1).and_hms(0, 0, 0);
+    let recent = Utc.ymd(2020, 1, 1).and_hms(0, 0, 0);
+    let source = ScriptedTimeProvider::new(vec![
+        Utc.ymd(1500, 1, 1).and_hms(0, 0, 0),
+        before_epoch,
+        recent,
+        before_epoch,
+        far_future,
+        recent,
+    ]);
+    let provider = MonotonicTimeProvider::new(source);
+
+    let times: Vec<_> = (0..6).map(|_| provider.current_time()).collect();
+    let expected_times = vec![
+        Utc.ymd(1500, 1, 1).and_hms(0, 0, 0),
+        before_epoch,
+        recent,
+        recent,
+        far_future,
+        far_future,
+    ];
+    assert_eq!(times, expected_times);
+}
+
+#[test]
+fn test_monotonic_provider_truncates_time_to_micros() {
+    let time = Utc.timestamp(10, 123_456_789);
+    let provider = MonotonicTimeProvider::new(MockTimeProvider::new(time));
+    assert_eq!(provider.current_time(), Utc.timestamp(10, 123_456_000));
+}
+
+/// Time provider failing the specified number of times before reporting time.
+#[derive(Debug)]
+struct FlakyTimeProvider {
+    failures_left: AtomicU32,
+    attempts: AtomicU32,
+    time: DateTime<Utc>,
+}
+
+impl FlakyTimeProvider {
+    fn new(failures: u32, time: DateTime<Utc>) -> Self {
+        Self {
+            failures_left: AtomicU32::new(failures),
+            attempts: AtomicU32::new(0),
+            time,
+        }
+    }
+}
+
+impl TimeProvider for &FlakyTimeProvider {
+    fn current_time(&self) -> DateTime<Utc> {
+        self.try_current_time().unwrap()
+    }
+
+    fn try_current_time(&self) -> Result<DateTime<Utc>, TimeError> {
+        self.attempts.fetch_add(1, Ordering::SeqCst);
+        let failures_left = self.failures_left.load(Ordering::SeqCst);
+        if failures_left > 0 {
+            self.failures_left
+                .store(failures_left - 1, Ordering::SeqCst);
+            Err(TimeError::new("flaky provider failure"))
+        } else {
+            Ok(self.time)
+        }
+    }
+}
+
+#[test]
+fn test_retrying_provider() {
+    let time = Utc.timestamp(10, 0);
+    let flaky_provider = FlakyTimeProvider::new(2, time);
+    let provider = RetryingTimeProvider::new(&flaky_provider, 3, StdDuration::from_millis(1));
+
+    assert_eq!(provider.try_current_time(), Ok(time));
+    assert_eq!(flaky_provider.attempts.load(Ordering::SeqCst), 3);
+
+    // The provider gives up after exhausting retries.
+    let flaky_provider = FlakyTimeProvider::new(5, time);
+    let provider = RetryingTimeProvider::new(&flaky_provider, 3, StdDuration::from_millis(1));
+    assert!(provider.try_current_time().is_err());
+    assert_eq!(flaky_provider.attempts.load(Ordering::SeqCst), 4);
+    // The infallible method falls back to the system time instead of panicking.
+    let start_time = Utc::now();
+    assert!(provider.current_time() >= start_time);
+}
+
+#[test]
+fn test_retrying_provider_backoff() {
+    let time = Utc.timestamp(10, 0);
+    let flaky_provider = FlakyTimeProvider::new(2, time);
+    let backoff = StdDuration::from_millis(50);
+    let provider = RetryingTimeProvider::new(&flaky_provider, 0, backoff);
+
+    let start = Instant::now();
+    assert!(provider.try_current_time().is_err());
+    // The provider does not wait for the time after a failure.
+    assert!(start.elapsed() < backoff);
+    // Requests within the backoff fail without querying the wrapped provider.
+    assert!(provider.try_current_time().is_err());
+    assert_eq!(flaky_provider.attempts.load(Ordering::SeqCst), 1);
+
+    // The request is retried after the backoff, which is doubled after another failure.
+    thread::sleep(backoff);
+    assert!(provider.try_current_time().is_err());
+    assert_eq!(flaky_provider.attempts.load(Ordering::SeqCst), 2);
+    thread::sleep(backoff);
+    assert!(provider.try_current_time().is_err());
+    assert_eq!(flaky_provider.attempts.load(Ordering::SeqCst), 2);
+    thread::sleep(backoff);
+    assert_eq!(provider.try_current_time(), Ok(time));
+    assert_eq!(flaky_provider.attempts.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+fn test_averaging_provider() {
+    let first_provider = MockTimeProvider::new(Utc.timestamp(10, 0));
+    let second_provider = MockTimeProvider::new(Utc.timestamp(20, 500_000_000));
+    let averaging_provider = AveragingTimeProvider::new(vec![
+        Box::new(first_provider.clone()),
+        Box::new(second_provider.clone()),
+    ]);
+    assert_eq!(
+        averaging_provider.current_time(),
+        Utc.timestamp(15, 250_000_000)
+    );
+
+    // Updates of the underlying providers are reflected by the averaging one.
+    first_provider.add_time(Duration::seconds(30));
+    second_provider.set_time(Utc.timestamp(60, 0));
+    assert_eq!(averaging_provider.current_time(), Utc.timestamp(50, 0));
+
+    // Times before the Unix epoch are averaged correctly as well.
+    let averaging_provider = AveragingTimeProvider::new(vec![
+        Box::new(MockTimeProvider::new(Utc.timestamp(-3, 0))),
+        Box::new(MockTimeProvider::new(Utc.timestamp(0, 0))),
+    ]);
+    assert_eq!(
+        averaging_provider.current_time(),
+        Utc.timestamp(-2, 500_000_000)
+    );
+
+    // Without providers, the system time is reported.
+    let averaging_provider = AveragingTimeProvider::new(vec![]);
+    let before = Utc::now();
+    let time = averaging_provider.current_time();
+    assert!(before <= time && time <= Utc::now());
+}
+
 #[test]
 fn test_selected_time_less_than_time_in_storage() {
     let time_service = TimeServiceFactory::default();
@@ -365,24 +972,142 @@ fn test_selected_time_less_than_time_in_storage() {
     );
 }
 
+#[test]
+fn test_is_submitting_validator() {
+    let testkit = create_testkit_with_validators(2);
+    let snapshot = testkit.snapshot();
+    for validator in testkit.network().validators() {
+        let key = validator.service_keypair().public_key();
+        assert!(TimeService::is_submitting_validator(&snapshot, key));
+    }
+    let key = KeyPair::random().public_key();
+    assert!(!TimeService::is_submitting_validator(&snapshot, key));
+}
+
 #[test]
 fn test_creating_transaction_is_not_validator() {
     let mut testkit = create_testkit_with_validators(1);
 
     let keypair = KeyPair::random();
     let tx = keypair.report_time(INSTANCE_ID, TxTime::new(Utc::now()));
+    let tx_hash = tx.object_hash();
     let block = testkit.create_block_with_transaction(tx);
-    assert_eq!(
-        *block[0].status().unwrap_err(),
-        ErrorMatch::from_fail(&CommonError::UnauthorizedCaller).for_service(INSTANCE_ID)
-    );
+    let expected_err = ErrorMatch::from_fail(&Error::UnknownSender).for_service(INSTANCE_ID);
+    assert_eq!(*block[0].status().unwrap_err(), expected_err);
 
+    // The error is stored as the transaction status.
     let snapshot = testkit.snapshot();
+    let core_schema = snapshot.for_core();
+    let location = core_schema.transactions_locations().get(&tx_hash).unwrap();
+    let status = core_schema.transaction_result(location).unwrap();
+    assert_eq!(status.unwrap_err(), expected_err);
+
     let schema = get_schema(&snapshot);
     assert!(schema.time.get().is_none());
     assert!(schema.validators_times.get(&keypair.public_key()).is_none());
 }
 
+#[test]
+fn test_duplicate_transaction_in_block() {
+    let mut testkit = create_testkit_with_validators(1);
+    let validator = testkit.network().validators()[0].clone();
+    let time = Utc.timestamp(10, 0);
+    let tx = validator.sign(TxStub.report_time(INSTANCE_ID, TxTime::new(time)));
+    let tx_hash = tx.object_hash();
+
+    // The repeated transaction is included into the block only once.
+    let block = testkit.create_block_with_transactions(vec![tx.clone(), tx]);
+    assert_eq!(block.len(), 1);
+    block[0].status().unwrap();
+
+    let snapshot = testkit.snapshot();
+    let location = snapshot
+        .for_core()
+        .transactions_locations()
+        .get(&tx_hash)
+        .unwrap();
+    assert_eq!(location.position_in_block(), 0);
+    let schema = get_schema(&snapshot);
+    assert_eq!(
+        schema
+            .validators_times
+            .get(&validator.service_keypair().public_key()),
+        Some(time)
+    );
+}
+
+#[test]
+fn test_batch_of_time_reports() {
+    let mut testkit = create_testkit_with_validators(3);
+    let validators = testkit.network().validators().to_vec();
+    let outsider = KeyPair::random();
+
+    let base_time = Utc.timestamp(1_000, 0);
+    let mut reports: Vec<_> = validators
+        .iter()
+        .enumerate()
+        .map(|(i, validator)| {
+            let time = base_time + Duration::seconds(i as i64);
+            TimeReport::new(INSTANCE_ID, time, &validator.service_keypair())
+        })
+        .collect();
+    // Report from a node which is not a validator.
+    reports.push(TimeReport::new(INSTANCE_ID, base_time, &outsider));
+    // Report on behalf of a validator with an incorrect signature.
+    let mut forged_report =
+        TimeReport::new(INSTANCE_ID, base_time + Duration::seconds(10), &outsider);
+    forged_report.public_key = validators[0].service_keypair().public_key();
+    reports.push(forged_report);
+    // Report signed by a validator for another service instance.
+    reports.push(TimeReport::new(
+        INSTANCE_ID + 1,
+        base_time + Duration::seconds(20),
+        &validators[1].service_keypair(),
+    ));
+
+    // The batch can be submitted by any node.
+    let tx = outsider.report_times(INSTANCE_ID, TxTimeBatch::new(reports));
+    let block = testkit.create_block_with_transaction(tx);
+    block[0].status().unwrap();
+
+    let snapshot = testkit.snapshot();
+    let schema = get_schema(&snapshot);
+    let validators_times: HashMap<_, _> = schema.validators_times.iter().collect();
+    let expected_times: HashMap<_, _> = validators
+        .iter()
+        .enumerate()
+        .map(|(i, validator)| {
+            let time = base_time + Duration::seconds(i as i64);
+            (validator.service_keypair().public_key(), time)
+        })
+        .collect();
+    assert_eq!(validators_times, expected_times);
+    assert_eq!(schema.time.get(), Some(base_time + Duration::seconds(2)));
+
+    // Outdated reports are skipped as well.
+    let reports = vec![
+        TimeReport::new(INSTANCE_ID, base_time, &validators[2].service_keypair()),
+        TimeReport::new(
+            INSTANCE_ID,
+            base_time + Duration::seconds(5),
+            &validators[0].service_keypair(),
+        ),
+    ];
+    let tx = outsider.report_times(INSTANCE_ID, TxTimeBatch::new(reports));
+    let block = testkit.create_block_with_transaction(tx);
+    block[0].status().unwrap();
+
+    let snapshot = testkit.snapshot();
+    let schema = get_schema(&snapshot);
+    let validator_time = |i: usize| {
+        let key = validators[i].service_keypair().public_key();
+        schema.validators_times.get(&key)
+    };
+    assert_eq!(validator_time(0), Some(base_time + Duration::seconds(5)));
+    assert_eq!(validator_time(2), Some(base_time + Duration::seconds(2)));
+    assert_eq!(schema.time.get(), Some(base_time + Duration::seconds(5)));
+}
+
 #[test]
 fn test_transaction_time_less_than_validator_time_in_storage() {
     let mut testkit = create_testkit_with_validators(1);
@@ -418,16 +1143,405 @@ fn test_transaction_time_less_than_validator_time_in_storage() {
     );
 }
 
-fn create_testkit_with_validators(validators_count: u16) -> TestKit {
-    let time_service =
-        Spec::new(TimeServiceFactory::default()).with_instance(INSTANCE_ID, INSTANCE_NAME, ());
-    TestKitBuilder::validator()
-        .with_validators(validators_count)
-        .with(time_service)
-        .build()
-}
+#[test]
+fn test_replayed_transaction_with_stale_seq() {
+    let mut testkit = create_testkit_with_validators(1);
+    let validator = testkit.network().validators()[0].service_keypair();
 
-async fn get_current_time(api: &mut TestKitApi) -> Option<DateTime<Utc>> {
+    let time0 = Utc::now();
+    let tx0 = validator.report_time(INSTANCE_ID, TxTime::new(time0).with_seq(2));
+    let block = testkit.create_block_with_transaction(tx0);
+    block[0].status().unwrap();
+
+    // The time in the replayed report is fresh, but the sequence number is stale.
+    let time1 = time0 + Duration::seconds(10);
+    let tx1 = validator.report_time(INSTANCE_ID, TxTime::new(time1).with_seq(1));
+    let block = testkit.create_block_with_transaction(tx1);
+    assert_eq!(
+        *block[0].status().unwrap_err(),
+        ErrorMatch::from_fail(&Error::StaleSequenceNumber).for_service(INSTANCE_ID),
+    );
+
+    let snapshot = testkit.snapshot();
+    let schema = get_schema(&snapshot);
+    assert_eq!(schema.time.get(), Some(time0));
+    assert_eq!(schema.validators_seqs.get(&validator.public_key()), Some(2));
+
+    let tx2 = validator.report_time(INSTANCE_ID, TxTime::new(time1).with_seq(3));
+    let block = testkit.create_block_with_transaction(tx2);
+    block[0].status().unwrap();
+
+    let snapshot = testkit.snapshot();
+    let schema = get_schema(&snapshot);
+    assert_eq!(schema.time.get(), Some(time1));
+    assert_eq!(schema.validators_seqs.get(&validator.public_key()), Some(3));
+}
+
+#[test]
+fn test_transaction_signed_offline() {
+    let mut testkit = create_testkit_with_validators(1);
+    let validator = testkit.network().validators()[0].service_keypair();
+    let time = Utc.timestamp(10_000, 0);
+
+    // Sign the report separately from assembling the transaction, as an offline signer would.
+    let bytes = TxTime::build_unsigned(INSTANCE_ID, time);
+    let signature = crypto::sign(&bytes, validator.secret_key());
+    let tx = TxTime::from_signed(bytes, validator.public_key(), signature).unwrap();
+    let expected_tx = validator.report_time(INSTANCE_ID, TxTime::new(time));
+    assert_eq!(tx.object_hash(), expected_tx.object_hash());
+    assert_eq!(tx, expected_tx);
+
+    let block = testkit.create_block_with_transaction(tx);
+    block[0].status().unwrap();
+    let snapshot = testkit.snapshot();
+    assert_eq!(get_schema(&snapshot).time.get(), Some(time));
+
+    // A signature by another key is rejected.
+    let bytes = TxTime::build_unsigned(INSTANCE_ID, time);
+    let signature = crypto::sign(&bytes, KeyPair::random().secret_key());
+    assert!(TxTime::from_signed(bytes, validator.public_key(), signature).is_err());
+}
+
+#[test]
+fn test_content_hash_does_not_depend_on_signature() {
+    let time = Utc.timestamp(10_000, 0);
+    let tx = KeyPair::random().report_time(INSTANCE_ID, TxTime::new(time));
+    let other_tx = KeyPair::random().report_time(INSTANCE_ID, TxTime::new(time));
+    assert_ne!(tx.object_hash(), other_tx.object_hash());
+    assert_eq!(
+        tx.payload().content_hash(),
+        other_tx.payload().content_hash()
+    );
+
+    let later_tx =
+        KeyPair::random().report_time(INSTANCE_ID, TxTime::new(time + Duration::seconds(1)));
+    assert_ne!(
+        tx.payload().content_hash(),
+        later_tx.payload().content_hash()
+    );
+}
+
+#[test]
+fn test_signing_transactions_with_validator_keys() {
+    let mut testkit = create_testkit_with_validators(3);
+    let validator_id = ValidatorId(1);
+    let service_key = testkit.validator(validator_id).public_keys().service_key;
+
+    let time = Utc.timestamp(10_000, 0);
+    let tx = testkit.sign_with_validator(
+        validator_id,
+        TxStub.report_time(INSTANCE_ID, TxTime::new(time)),
+    );
+    assert_eq!(tx.author(), service_key);
+    let block = testkit.create_block_with_transaction(tx);
+    block[0].status().unwrap();
+
+    let snapshot = testkit.snapshot();
+    let schema = get_schema(&snapshot);
+    assert_eq!(schema.validator_time(&service_key), Some(time));
+}
+
+#[test]
+fn test_schema_time_helpers() {
+    let mut testkit = create_testkit_with_validators(3);
+    let validators = testkit.network().validators().to_vec();
+
+    let snapshot = testkit.snapshot();
+    let schema = get_schema(&snapshot);
+    assert_eq!(schema.consolidated_time(), None);
+    assert_eq!(
+        schema.validator_time(&validators[0].public_keys().service_key),
+        None
+    );
+
+    let base_time = Utc.timestamp(10_000, 0);
+    let txs = validators[..2].iter().enumerate().map(|(i, validator)| {
+        let time = base_time + Duration::seconds(i as i64);
+        validator
+            .service_keypair()
+            .report_time(INSTANCE_ID, TxTime::new(time))
+    });
+    testkit.create_block_with_transactions(txs);
+
+    let snapshot = testkit.snapshot();
+    let schema = get_schema(&snapshot);
+    assert_eq!(schema.consolidated_time(), schema.time.get());
+    assert_eq!(
+        schema.consolidated_time(),
+        Some(base_time + Duration::seconds(1))
+    );
+    for validator in &validators {
+        let public_key = validator.public_keys().service_key;
+        assert_eq!(
+            schema.validator_time(&public_key),
+            schema.validators_times.get(&public_key)
+        );
+    }
+    assert_eq!(
+        schema.validator_time(&validators[2].public_keys().service_key),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_validators_by_time() {
+    let mut testkit = create_testkit_with_config(5, TimeConfig::default());
+    let mut api = testkit.api();
+    let validators = testkit.network().validators().to_vec();
+    let keys: Vec<_> = validators
+        .iter()
+        .map(|validator| validator.service_keypair().public_key())
+        .collect();
+
+    // The first two validators report the same time, and the last one does not report
+    // time at all.
+    let base_time = Utc.timestamp(10_000, 0);
+    let reports = vec![(0, 5), (1, 5), (2, 1), (3, 8)];
+    let txs = reports.into_iter().map(|(i, secs)| {
+        let time = TxTime::new(base_time + Duration::seconds(secs));
+        validators[i]
+            .service_keypair()
+            .report_time(INSTANCE_ID, time)
+    });
+    for tx in testkit.create_block_with_transactions(txs).iter() {
+        tx.status().unwrap();
+    }
+
+    let (first_equal, second_equal) = if keys[0] < keys[1] {
+        (keys[0], keys[1])
+    } else {
+        (keys[1], keys[0])
+    };
+    let expected = vec![
+        (keys[3], Some(base_time + Duration::seconds(8))),
+        (first_equal, Some(base_time + Duration::seconds(5))),
+        (second_equal, Some(base_time + Duration::seconds(5))),
+        (keys[2], Some(base_time + Duration::seconds(1))),
+        (keys[4], None),
+    ];
+    let snapshot = testkit.snapshot();
+    let validator_keys = snapshot.for_core().consensus_config().validator_keys;
+    assert_eq!(
+        get_schema(&snapshot).validators_by_time(&validator_keys),
+        expected
+    );
+
+    let ranked_times: Vec<ValidatorTime> = api
+        .private(ApiKind::Service(INSTANCE_NAME))
+        .get("v1/validators_times/ranked")
+        .await
+        .unwrap();
+    let ranked_times: Vec<_> = ranked_times
+        .into_iter()
+        .map(|validator_time| {
+            assert_eq!(validator_time.active, None);
+            (validator_time.public_key, validator_time.time)
+        })
+        .collect();
+    assert_eq!(ranked_times, expected);
+}
+
+#[tokio::test]
+async fn test_departed_validator_time_is_retained() {
+    // The mock provider reports the epoch start, so the times automatically reported
+    // by our node are outdated and do not influence the test.
+    let time_service = TimeServiceFactory::with_provider(MockTimeProvider::default());
+    let config = TimeConfig::default().with_tombstones();
+    let mut testkit = TestKitBuilder::validator()
+        .with_validators(4)
+        .with(Spec::new(time_service).with_instance(INSTANCE_ID, INSTANCE_NAME, config))
+        .with(Supervisor::simple())
+        .build();
+    let mut api = testkit.api();
+    let validators = testkit.network().validators().to_vec();
+    let departed_key = validators[3].service_keypair().public_key();
+
+    let base_time = Utc.timestamp(10_000, 0);
+    let reports = vec![(3, 100), (0, 1), (1, 2)];
+    let txs = reports.into_iter().map(|(i, secs)| {
+        let time = TxTime::new(base_time + Duration::seconds(secs));
+        validators[i]
+            .service_keypair()
+            .report_time(INSTANCE_ID, time)
+    });
+    testkit.create_block_with_transactions(txs);
+    // With 4 validators, the second largest time is selected.
+    assert_current_time_eq(&mut api, Some(base_time + Duration::seconds(2))).await;
+
+    // Remove the last validator from the network.
+    let cfg_change_height = Height(5);
+    let new_cfg = {
+        let validator_keys = validators[..3].iter().map(TestNode::public_keys).collect();
+        testkit
+            .consensus_config()
+            .with_validator_keys(validator_keys)
+    };
+    let change = ConfigPropose::new(0, cfg_change_height).consensus_config(new_cfg);
+    let change = validators[0]
+        .service_keypair()
+        .propose_config_change(SUPERVISOR_INSTANCE_ID, change);
+    testkit.create_block_with_transaction(change);
+    testkit.create_blocks_until(cfg_change_height);
+    assert_eq!(testkit.network().validators().len(), 3);
+
+    // The time of the departed validator is retained, but marked as inactive.
+    let all_times = get_all_validators_times(&mut api).await;
+    assert_eq!(all_times.len(), 4);
+    for validator_time in &all_times {
+        assert_eq!(
+            validator_time.active,
+            Some(validator_time.public_key != departed_key)
+        );
+    }
+    let departed_time = all_times
+        .iter()
+        .find(|validator_time| validator_time.public_key == departed_key)
+        .unwrap();
+    assert_eq!(departed_time.time, Some(base_time + Duration::seconds(100)));
+    let current_times = get_current_validators_times(&mut api).await;
+    assert!(current_times
+        .iter()
+        .all(|validator_time| validator_time.active == Some(true)));
+
+    // The departed validator time is not used for consolidation: with 3 validators,
+    // the largest time among the current validators is selected.
+    let time = TxTime::new(base_time + Duration::seconds(3));
+    let tx = validators[0]
+        .service_keypair()
+        .report_time(INSTANCE_ID, time);
+    testkit.create_block_with_transaction(tx)[0]
+        .status()
+        .unwrap();
+    assert_current_time_eq(&mut api, Some(base_time + Duration::seconds(3))).await;
+}
+
+#[test]
+fn test_history_limit() {
+    const HISTORY_LIMIT: usize = 2;
+
+    let time_service = TimeServiceFactory::with_provider(MockTimeProvider::default());
+    let config = TimeConfig::default().with_history_limit(HISTORY_LIMIT);
+    let mut testkit = TestKitBuilder::validator()
+        .with(Spec::new(time_service).with_instance(INSTANCE_ID, INSTANCE_NAME, config.clone()))
+        .with(Supervisor::simple())
+        .build();
+    let us = testkit.us();
+    let base_time = Utc.timestamp(10_000, 0);
+    assert_eq!(get_schema(&testkit.snapshot()).config(), config);
+
+    // Rotate the second validator several times; each new validator reports its time.
+    // Later validators report earlier times, so that evicting by time would retain
+    // the earliest validators rather than the latest ones.
+    let mut past_validators = vec![];
+    for i in 0..5 {
+        let time = base_time - Duration::seconds(i);
+        past_validators.push(replace_second_validator(&mut testkit, time));
+
+        let snapshot = testkit.snapshot();
+        let schema = get_schema(&snapshot);
+        // The map contains times of the 2 current validators and at most
+        // `HISTORY_LIMIT` past validators.
+        let past_count = past_validators.len() - 1;
+        assert_eq!(
+            schema.validators_times.keys().count(),
+            2 + past_count.min(HISTORY_LIMIT)
+        );
+    }
+
+    // Only the past validators that have reported their time at the greatest heights
+    // are retained.
+    let snapshot = testkit.snapshot();
+    let schema = get_schema(&snapshot);
+    let (current, past) = past_validators.split_last().unwrap();
+    assert!(schema.validator_time(current).is_some());
+    assert!(schema
+        .validator_time(&us.public_keys().service_key)
+        .is_some());
+    let evicted_count = past.len() - HISTORY_LIMIT;
+    for (i, public_key) in past.iter().enumerate() {
+        assert_eq!(
+            schema.validator_time(public_key).is_some(),
+            i >= evicted_count
+        );
+    }
+}
+
+#[test]
+fn test_history_limit_with_tombstones() {
+    let time_service = TimeServiceFactory::with_provider(MockTimeProvider::default());
+    let config = TimeConfig::default()
+        .with_history_limit(1)
+        .with_tombstones();
+    let mut testkit = TestKitBuilder::validator()
+        .with(Spec::new(time_service).with_instance(INSTANCE_ID, INSTANCE_NAME, config))
+        .with(Supervisor::simple())
+        .build();
+    let base_time = Utc.timestamp(10_000, 0);
+
+    let past_validators: Vec<_> = (0..3)
+        .map(|i| replace_second_validator(&mut testkit, base_time + Duration::seconds(i)))
+        .collect();
+
+    // Tombstones of all past validators are retained despite the history limit.
+    let snapshot = testkit.snapshot();
+    let schema = get_schema(&snapshot);
+    assert_eq!(schema.validators_times.keys().count(), 4);
+    for public_key in &past_validators {
+        assert!(schema.validator_time(public_key).is_some());
+    }
+}
+
+fn create_testkit_with_validators(validators_count: u16) -> TestKit {
+    let time_service =
+        Spec::new(TimeServiceFactory::default()).with_instance(INSTANCE_ID, INSTANCE_NAME, ());
+    TestKitBuilder::validator()
+        .with_validators(validators_count)
+        .with(time_service)
+        .build()
+}
+
+fn create_failing_time_service() -> TimeServiceFactory {
+    // The failing provider prevents nodes from reporting time automatically.
+    let provider = FailingTimeProvider::default();
+    provider.set_failing(true);
+    TimeServiceFactory::with_provider(provider)
+}
+
+fn create_testkit_with_config(validators_count: u16, config: TimeConfig) -> TestKit {
+    let time_service =
+        Spec::new(create_failing_time_service()).with_instance(INSTANCE_ID, INSTANCE_NAME, config);
+    TestKitBuilder::validator()
+        .with_validators(validators_count)
+        .with(time_service)
+        .build()
+}
+
+/// Replaces the second validator with a new node via the supervisor, and makes
+/// the new validator report `time`. Returns the service key of the new validator.
+fn replace_second_validator(testkit: &mut TestKit, time: DateTime<Utc>) -> PublicKey {
+    let us = testkit.us();
+    let new_node = testkit.network_mut().add_node().clone();
+    let cfg_change_height = testkit.height().next().next();
+    let new_cfg = testkit
+        .consensus_config()
+        .with_validator_keys(vec![us.public_keys(), new_node.public_keys()]);
+    let change = ConfigPropose::new(0, cfg_change_height).consensus_config(new_cfg);
+    let change = us
+        .service_keypair()
+        .propose_config_change(SUPERVISOR_INSTANCE_ID, change);
+    testkit.create_block_with_transaction(change);
+    testkit.create_blocks_until(cfg_change_height);
+
+    let tx = new_node
+        .service_keypair()
+        .report_time(INSTANCE_ID, TxTime::new(time));
+    testkit.create_block_with_transaction(tx)[0]
+        .status()
+        .unwrap();
+    new_node.public_keys().service_key
+}
+
+async fn get_current_time(api: &mut TestKitApi) -> Option<DateTime<Utc>> {
     api.public(ApiKind::Service(INSTANCE_NAME))
         .get("v1/current_time")
         .await
@@ -448,6 +1562,35 @@ async fn get_all_validators_times(api: &mut TestKitApi) -> Vec<ValidatorTime> {
         .unwrap()
 }
 
+async fn get_validators_times_page(
+    api: &mut TestKitApi,
+    offset: usize,
+    limit: usize,
+) -> exonum_api::Result<ValidatorsTimesPage> {
+    api.private(ApiKind::Service(INSTANCE_NAME))
+        .query(&ValidatorsTimesQuery::new(offset, limit))
+        .get("v1/validators_times/page")
+        .await
+}
+
+async fn get_validators_time_drift(api: &mut TestKitApi) -> Vec<ValidatorTimeDrift> {
+    api.private(ApiKind::Service(INSTANCE_NAME))
+        .get("v1/validators_times/drift")
+        .await
+        .unwrap()
+}
+
+async fn get_validators_times_delta(
+    api: &mut TestKitApi,
+    from: Height,
+    to: Height,
+) -> exonum_api::Result<Vec<ValidatorTimeDelta>> {
+    api.private(ApiKind::Service(INSTANCE_NAME))
+        .query(&ValidatorsTimesDeltaQuery::new(from, to))
+        .get("v1/validators_times/delta")
+        .await
+}
+
 async fn assert_current_time_eq(api: &mut TestKitApi, expected_time: Option<DateTime<Utc>>) {
     let current_time = get_current_time(api).await;
     assert_eq!(expected_time, current_time);
@@ -576,3 +1719,391 @@ async fn test_endpoint_api() {
     assert_current_validators_times_eq(&mut api, &current_validators_times).await;
     assert_all_validators_times_eq(&mut api, &all_validators_times).await;
 }
+
+#[tokio::test]
+async fn test_validators_times_page_endpoint() {
+    let mut testkit = create_testkit_with_validators(7);
+    let validators = testkit.network().validators();
+    let txs: Vec<_> = validators
+        .iter()
+        .map(|validator| {
+            let tx = TxStub.report_time(INSTANCE_ID, TxTime::new(Utc.timestamp(10, 0)));
+            validator.sign(tx)
+        })
+        .collect();
+    testkit.create_block_with_transactions(txs);
+    let mut api = testkit.api();
+
+    let first_page = get_validators_times_page(&mut api, 0, 4).await.unwrap();
+    let second_page = get_validators_times_page(&mut api, 4, 4).await.unwrap();
+    assert_eq!(
+        (first_page.total, first_page.offset, first_page.limit),
+        (7, 0, 4)
+    );
+    assert_eq!(
+        (second_page.total, second_page.offset, second_page.limit),
+        (7, 4, 4)
+    );
+    assert_eq!(first_page.items.len(), 4);
+    assert_eq!(second_page.items.len(), 3);
+
+    let keys: Vec<_> = first_page
+        .items
+        .iter()
+        .chain(&second_page.items)
+        .map(|time| time.public_key)
+        .collect();
+    let mut expected_keys: Vec<_> = validators
+        .iter()
+        .map(|validator| validator.public_keys().service_key)
+        .collect();
+    expected_keys.sort();
+    assert_eq!(keys, expected_keys);
+
+    // The page size is limited.
+    let err = get_validators_times_page(&mut api, 0, MAX_VALIDATORS_TIMES_PER_PAGE + 1)
+        .await
+        .unwrap_err();
+    assert_eq!(err.http_code, HttpStatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_current_time_formats() {
+    let mut testkit = create_testkit_with_validators(1);
+    let time = Utc.timestamp(1_000, 250_000_000);
+    let keypair = testkit.us().service_keypair();
+    testkit.create_block_with_transaction(keypair.report_time(INSTANCE_ID, TxTime::new(time)));
+    let api = testkit.api();
+
+    // The default format is RFC 3339.
+    let default_time: Option<DateTime<Utc>> = api
+        .public(ApiKind::Service(INSTANCE_NAME))
+        .get("v1/current_time")
+        .await
+        .unwrap();
+    assert_eq!(default_time, Some(time));
+    let rfc3339_time: Option<DateTime<Utc>> = api
+        .public(ApiKind::Service(INSTANCE_NAME))
+        .query(&CurrentTimeQuery::new(TimeFormat::Rfc3339))
+        .get("v1/current_time")
+        .await
+        .unwrap();
+    assert_eq!(rfc3339_time, Some(time));
+
+    let millis: Option<i64> = api
+        .public(ApiKind::Service(INSTANCE_NAME))
+        .query(&CurrentTimeQuery::new(TimeFormat::Millis))
+        .get("v1/current_time")
+        .await
+        .unwrap();
+    assert_eq!(millis, Some(1_000_250));
+    assert_eq!(Utc.timestamp_millis(millis.unwrap()), time);
+
+    let system_time: Option<SystemTime> = api
+        .public(ApiKind::Service(INSTANCE_NAME))
+        .query(&CurrentTimeQuery::new(TimeFormat::Struct))
+        .get("v1/current_time")
+        .await
+        .unwrap();
+    assert_eq!(system_time, Some(SystemTime::from(time)));
+}
+
+#[tokio::test]
+async fn test_current_time_with_proof() {
+    let mut testkit = create_testkit_with_validators(1);
+    let time = Utc.timestamp(1_000, 250_000_000);
+    let keypair = testkit.us().service_keypair();
+    testkit.create_block_with_transaction(keypair.report_time(INSTANCE_ID, TxTime::new(time)));
+    let api = testkit.api();
+
+    let response: TimeWithProof = api
+        .public(ApiKind::Service(INSTANCE_NAME))
+        .query(&CurrentTimeQuery::with_proof())
+        .get("v1/current_time")
+        .await
+        .unwrap();
+    assert_eq!(response.time, Some(time));
+
+    let proof = response.proof.unwrap();
+    assert_eq!(proof.block_proof.block, testkit.blockchain().last_block());
+    assert_eq!(
+        proof.block_proof.block.state_hash,
+        testkit.blockchain().last_block().state_hash
+    );
+    let validator_keys: Vec<_> = testkit
+        .consensus_config()
+        .validator_keys
+        .iter()
+        .map(|keys| keys.consensus_key)
+        .collect();
+    let (index_name, index_hash) = proof.verify(&validator_keys).unwrap();
+    assert_eq!(index_name, format!("{}.time", INSTANCE_NAME));
+    assert_eq!(index_hash, time.object_hash());
+}
+
+#[tokio::test]
+async fn test_all_validators_times_endpoint_with_many_validators() {
+    let mut testkit = create_testkit_with_validators(50);
+    let validators = testkit.network().validators().to_vec();
+    let txs: Vec<_> = validators
+        .iter()
+        .enumerate()
+        .map(|(i, validator)| {
+            let time = Utc.timestamp(10 + i as i64, 0);
+            let tx = TxStub.report_time(INSTANCE_ID, TxTime::new(time));
+            validator.sign(tx)
+        })
+        .collect();
+    testkit.create_block_with_transactions(txs);
+    let mut api = testkit.api();
+
+    // The lazily serialized list should contain the same times as the collected one.
+    let mut serialized_times = get_all_validators_times(&mut api).await;
+    serialized_times.sort_by(|x, y| x.public_key.cmp(&y.public_key));
+    let page = get_validators_times_page(&mut api, 0, MAX_VALIDATORS_TIMES_PER_PAGE)
+        .await
+        .unwrap();
+    assert_eq!(page.total, 50);
+
+    let as_tuple = |time: &ValidatorTime| (time.public_key, time.time, time.active);
+    let serialized_times: Vec<_> = serialized_times.iter().map(as_tuple).collect();
+    let collected_times: Vec<_> = page.items.iter().map(as_tuple).collect();
+    assert_eq!(serialized_times, collected_times);
+
+    for (i, validator) in validators.iter().enumerate() {
+        let public_key = validator.public_keys().service_key;
+        let expected = (public_key, Some(Utc.timestamp(10 + i as i64, 0)), None);
+        assert!(serialized_times.contains(&expected));
+    }
+}
+
+#[tokio::test]
+async fn test_validators_time_drift_endpoint() {
+    let mut testkit = create_testkit_with_validators(3);
+    let mut api = testkit.api();
+    let validators = testkit.network().validators().to_vec();
+    let keys: Vec<_> = validators
+        .iter()
+        .map(|validator| validator.service_keypair().public_key())
+        .collect();
+
+    // No validator has reported time yet, so all drifts are unknown.
+    let drifts = get_validators_time_drift(&mut api).await;
+    assert_eq!(drifts.len(), 3);
+    assert!(drifts.iter().all(|drift| drift.time.is_none()));
+    assert!(drifts.iter().all(|drift| drift.drift.is_none()));
+
+    // With 3 validators, the consolidated time is the maximum of the reported times,
+    // so the first validator time becomes the consolidated one.
+    let time0 = Utc.timestamp(1_000_000, 0);
+    let time1 = time0 - Duration::seconds(5);
+    let txs = vec![
+        validators[0]
+            .service_keypair()
+            .report_time(INSTANCE_ID, TxTime::new(time0)),
+        validators[1]
+            .service_keypair()
+            .report_time(INSTANCE_ID, TxTime::new(time1)),
+    ];
+    testkit.create_block_with_transactions(txs);
+    assert_current_time_eq(&mut api, Some(time0)).await;
+
+    let drifts: Vec<_> = get_validators_time_drift(&mut api)
+        .await
+        .into_iter()
+        .map(|drift| (drift.public_key, drift.time, drift.drift))
+        .collect();
+    assert_eq!(
+        drifts,
+        vec![
+            (keys[1], Some(time1), Some(-5_000)),
+            (keys[0], Some(time0), Some(0)),
+            (keys[2], None, None),
+        ]
+    );
+
+    // The third validator reports time ahead of the others, which moves the consolidated time.
+    let time2 = time0 + Duration::seconds(20);
+    testkit.create_block_with_transaction(
+        validators[2]
+            .service_keypair()
+            .report_time(INSTANCE_ID, TxTime::new(time2)),
+    );
+    assert_current_time_eq(&mut api, Some(time2)).await;
+
+    let drifts: Vec<_> = get_validators_time_drift(&mut api)
+        .await
+        .into_iter()
+        .map(|drift| (drift.public_key, drift.time, drift.drift))
+        .collect();
+    assert_eq!(
+        drifts,
+        vec![
+            (keys[1], Some(time1), Some(-25_000)),
+            (keys[0], Some(time0), Some(-20_000)),
+            (keys[2], Some(time2), Some(0)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_validators_times_delta_endpoint() {
+    let time_service = create_failing_time_service().with_times_history();
+    let mut testkit = TestKitBuilder::validator()
+        .with_validators(3)
+        .with(Spec::new(time_service).with_instance(INSTANCE_ID, INSTANCE_NAME, ()))
+        .build();
+    let mut api = testkit.api();
+    let validators = testkit.network().validators().to_vec();
+    let keys: Vec<_> = validators
+        .iter()
+        .map(|validator| validator.service_keypair().public_key())
+        .collect();
+
+    // Each block contains reports advancing validators by the specified number of seconds
+    // from the base time.
+    let base_time = Utc.timestamp(1_000_000, 0);
+    let blocks = vec![
+        vec![(0, 0), (1, 0)],
+        vec![(0, 10), (1, 3), (2, 1)],
+        vec![(0, 15)],
+    ];
+    for reports in blocks {
+        let txs = reports.into_iter().map(|(i, secs)| {
+            let time = TxTime::new(base_time + Duration::seconds(secs));
+            validators[i]
+                .service_keypair()
+                .report_time(INSTANCE_ID, time)
+        });
+        for tx in testkit.create_block_with_transactions(txs).iter() {
+            tx.status().unwrap();
+        }
+    }
+    assert_eq!(testkit.height(), Height(3));
+
+    let get_deltas = |deltas: Vec<ValidatorTimeDelta>| {
+        let sorted_keys: Vec<_> = deltas.iter().map(|delta| delta.public_key).collect();
+        let mut expected_keys = keys.clone();
+        expected_keys.sort();
+        assert_eq!(sorted_keys, expected_keys);
+        deltas
+            .into_iter()
+            .map(|delta| (delta.public_key, delta.delta))
+            .collect::<HashMap<_, _>>()
+    };
+
+    // The third validator has no time at the first height, so its delta is unknown.
+    let deltas = get_validators_times_delta(&mut api, Height(1), Height(3))
+        .await
+        .unwrap();
+    let deltas = get_deltas(deltas);
+    assert_eq!(deltas[&keys[0]], Some(15_000));
+    assert_eq!(deltas[&keys[1]], Some(3_000));
+    assert_eq!(deltas[&keys[2]], None);
+
+    let deltas = get_validators_times_delta(&mut api, Height(2), Height(3))
+        .await
+        .unwrap();
+    let deltas = get_deltas(deltas);
+    assert_eq!(deltas[&keys[0]], Some(5_000));
+    assert_eq!(deltas[&keys[1]], Some(0));
+    assert_eq!(deltas[&keys[2]], Some(0));
+
+    // Invalid ranges of heights are rejected.
+    let err = get_validators_times_delta(&mut api, Height(3), Height(2))
+        .await
+        .unwrap_err();
+    assert_eq!(err.http_code, HttpStatusCode::BAD_REQUEST);
+    let err = get_validators_times_delta(&mut api, Height(1), Height(4))
+        .await
+        .unwrap_err();
+    assert_eq!(err.http_code, HttpStatusCode::BAD_REQUEST);
+
+    // Without the history of times, the endpoint is not available.
+    let mut testkit = create_testkit_with_validators(3);
+    testkit.create_block();
+    let mut api = testkit.api();
+    let err = get_validators_times_delta(&mut api, Height(0), Height(1))
+        .await
+        .unwrap_err();
+    assert_eq!(err.http_code, HttpStatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_provider_info_endpoint() {
+    let time_service = TimeServiceFactory::with_provider(MockTimeProvider::default());
+    let mut testkit = TestKitBuilder::validator()
+        .with(Spec::new(time_service).with_instance(INSTANCE_ID, INSTANCE_NAME, ()))
+        .build();
+    let info: ProviderInfo = testkit
+        .api()
+        .public(ApiKind::Service(INSTANCE_NAME))
+        .get("v1/provider_info")
+        .await
+        .unwrap();
+    assert_eq!(info, ProviderInfo::new("mock"));
+
+    let mut testkit = create_testkit_with_validators(1);
+    let info: ProviderInfo = testkit
+        .api()
+        .public(ApiKind::Service(INSTANCE_NAME))
+        .get("v1/provider_info")
+        .await
+        .unwrap();
+    assert_eq!(info.kind, "system");
+    assert!(info.config.is_null());
+}
+
+#[tokio::test]
+async fn test_report_time_endpoint() {
+    let time_service = TimeServiceFactory::with_provider(MockTimeProvider::default());
+    let mut testkit = TestKitBuilder::validator()
+        .with(Spec::new(time_service).with_instance(INSTANCE_ID, INSTANCE_NAME, ()))
+        .build();
+    let api = testkit.api();
+
+    let time = Utc.timestamp(10_000, 0);
+    let tx_hash: Hash = api
+        .private(ApiKind::Service(INSTANCE_NAME))
+        .query(&TxTime::new(time))
+        .post("v1/time")
+        .await
+        .unwrap();
+    assert!(testkit.is_tx_in_pool(&tx_hash));
+
+    let block = testkit.create_block();
+    block[tx_hash].status().unwrap();
+    let snapshot = testkit.snapshot();
+    let schema = get_schema(&snapshot);
+    let our_key = testkit.us().public_keys().service_key;
+    assert_eq!(schema.validator_time(&our_key), Some(time));
+    assert_eq!(schema.consolidated_time(), Some(time));
+
+    // Auditors cannot report time.
+    let time_service = TimeServiceFactory::with_provider(MockTimeProvider::default());
+    let mut testkit = TestKitBuilder::auditor()
+        .with(Spec::new(time_service).with_instance(INSTANCE_ID, INSTANCE_NAME, ()))
+        .build();
+    let err = testkit
+        .api()
+        .private(ApiKind::Service(INSTANCE_NAME))
+        .query(&TxTime::new(time))
+        .post::<Hash>("v1/time")
+        .await
+        .unwrap_err();
+    assert_eq!(err.http_code, HttpStatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn test_averaging_provider_description() {
+    let provider = AveragingTimeProvider::new(vec![
+        Box::new(MockTimeProvider::default()),
+        Box::new(exonum_time::SystemTimeProvider),
+    ]);
+    let info = provider.describe();
+    assert_eq!(info.kind, "averaging");
+    assert_eq!(
+        info.config,
+        serde_json::json!({ "providers": [{ "kind": "mock" }, { "kind": "system" }] })
+    );
+}
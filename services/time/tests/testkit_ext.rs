@@ -0,0 +1,184 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for testkit helpers of the time service.
+
+#![cfg(feature = "testkit")]
+
+use chrono::{Duration, TimeZone, Utc};
+use exonum::{
+    helpers::{Height, ValidatorId},
+    runtime::SnapshotExt,
+};
+use exonum_testkit::{ApiKind, Spec, TestKit, TestKitBuilder};
+use serde_json::Value;
+
+use std::{collections::HashMap, sync::Arc};
+
+use exonum_time::{
+    testkit::{TimeServiceClient, TimeTestKitExt},
+    MockTimeProvider, ProviderInfo, TimeProvider, TimeSchema, TimeServiceFactory,
+    ValidatorsTimesQuery,
+};
+
+const INSTANCE_ID: u32 = 112;
+const INSTANCE_NAME: &str = "my-time";
+
+fn create_testkit(validators_count: u16, provider: MockTimeProvider) -> TestKit {
+    let time_service = TimeServiceFactory::with_provider(provider);
+    TestKitBuilder::validator()
+        .with_validators(validators_count)
+        .with(Spec::new(time_service).with_instance(INSTANCE_ID, INSTANCE_NAME, ()))
+        .build()
+}
+
+#[test]
+fn create_blocks_until_time_set() {
+    let provider = MockTimeProvider::new(Utc.timestamp(100, 0));
+    let mut testkit = create_testkit(1, provider);
+
+    let height = testkit.create_blocks_until_time_set(INSTANCE_NAME);
+    assert!(height > Height(0) && height <= Height(2), "{:?}", height);
+    assert_eq!(testkit.height(), height);
+
+    let snapshot = testkit.snapshot();
+    let schema: TimeSchema<_> = snapshot.service_schema(INSTANCE_NAME).unwrap();
+    assert_eq!(schema.time.get(), Some(Utc.timestamp(100, 0)));
+
+    // If the time is already set, no blocks are created.
+    assert_eq!(testkit.create_blocks_until_time_set(INSTANCE_NAME), height);
+}
+
+#[test]
+#[should_panic(expected = "Time is not set in time service `my-time` after 100 blocks")]
+fn create_blocks_until_time_set_with_several_validators() {
+    // Only one of 4 validators reports time, which is not enough to consolidate it.
+    let mut testkit = create_testkit(4, MockTimeProvider::default());
+    testkit.create_blocks_until_time_set(INSTANCE_NAME);
+}
+
+#[test]
+#[should_panic(expected = "Cannot access time service `other-time`")]
+fn create_blocks_until_time_set_with_unknown_service() {
+    let mut testkit = create_testkit(1, MockTimeProvider::default());
+    testkit.create_blocks_until_time_set("other-time");
+}
+
+#[test]
+fn create_block_with_validator_times() {
+    let base_time = Utc.timestamp(1_000, 0);
+    let mut testkit = create_testkit(4, MockTimeProvider::new(base_time));
+    let offsets = [-5, 3, 10, 0];
+    let providers: HashMap<_, _> = (0..4)
+        .map(|i| {
+            let time = base_time + Duration::seconds(offsets[usize::from(i)]);
+            let provider: Arc<dyn TimeProvider> = MockTimeProvider::new(time).into();
+            (ValidatorId(i), provider)
+        })
+        .collect();
+
+    let block = testkit.create_block_with_validator_times(INSTANCE_NAME, &providers);
+    assert_eq!(block.len(), 4);
+    for tx in block.iter() {
+        tx.status().unwrap();
+    }
+
+    let snapshot = testkit.snapshot();
+    let schema: TimeSchema<_> = snapshot.service_schema(INSTANCE_NAME).unwrap();
+    for (validator_id, provider) in &providers {
+        let key = testkit
+            .validator(*validator_id)
+            .service_keypair()
+            .public_key();
+        assert_eq!(
+            schema.validators_times.get(&key),
+            Some(provider.current_time())
+        );
+    }
+    // With 4 validators, at most 1 of them may be Byzantine, so the consolidated time
+    // is the second largest of the reported times.
+    assert_eq!(schema.time.get(), Some(base_time + Duration::seconds(3)));
+}
+
+#[test]
+#[should_panic(expected = "Time service `other-time` is not deployed")]
+fn create_block_with_validator_times_with_unknown_service() {
+    let mut testkit = create_testkit(1, MockTimeProvider::default());
+    testkit.create_block_with_validator_times("other-time", &HashMap::new());
+}
+
+#[tokio::test]
+async fn typed_client_matches_manual_requests() {
+    let mut testkit = create_testkit(1, MockTimeProvider::new(Utc.timestamp(100, 0)));
+    testkit.create_blocks_until_time_set(INSTANCE_NAME);
+    let api = testkit.api();
+    let client = TimeServiceClient::new(&api, INSTANCE_NAME);
+
+    let time = client.current_time().await.unwrap();
+    assert_eq!(time, Some(Utc.timestamp(100, 0)));
+    let manual_time: Option<chrono::DateTime<Utc>> = api
+        .public(ApiKind::Service(INSTANCE_NAME))
+        .get("v1/current_time")
+        .await
+        .unwrap();
+    assert_eq!(time, manual_time);
+    let time_with_proof = client.current_time_with_proof().await.unwrap();
+    assert_eq!(time_with_proof.time, time);
+    assert!(time_with_proof.proof.is_some());
+
+    let provider_info = client.provider_info().await.unwrap();
+    let manual_provider_info: ProviderInfo = api
+        .public(ApiKind::Service(INSTANCE_NAME))
+        .get("v1/provider_info")
+        .await
+        .unwrap();
+    assert_eq!(provider_info, manual_provider_info);
+
+    // Types returned by private endpoints are compared by their JSON presentation.
+    let times = client.validators_times().await.unwrap();
+    assert_eq!(times.len(), 1);
+    let manual_times: Value = api
+        .private(ApiKind::Service(INSTANCE_NAME))
+        .get("v1/validators_times")
+        .await
+        .unwrap();
+    assert_eq!(serde_json::to_value(&times).unwrap(), manual_times);
+
+    let all_times = client.all_validators_times().await.unwrap();
+    let manual_all_times: Value = api
+        .private(ApiKind::Service(INSTANCE_NAME))
+        .get("v1/validators_times/all")
+        .await
+        .unwrap();
+    assert_eq!(serde_json::to_value(&all_times).unwrap(), manual_all_times);
+
+    let page = client
+        .validators_times_page(ValidatorsTimesQuery::default())
+        .await
+        .unwrap();
+    let manual_page: Value = api
+        .private(ApiKind::Service(INSTANCE_NAME))
+        .get("v1/validators_times/page")
+        .await
+        .unwrap();
+    assert_eq!(serde_json::to_value(&page).unwrap(), manual_page);
+
+    let drift = client.validators_time_drift().await.unwrap();
+    let manual_drift: Value = api
+        .private(ApiKind::Service(INSTANCE_NAME))
+        .get("v1/validators_times/drift")
+        .await
+        .unwrap();
+    assert_eq!(serde_json::to_value(&drift).unwrap(), manual_drift);
+}
@@ -15,21 +15,28 @@
 //! Testkit builder.
 
 use exonum::{
-    blockchain::config::GenesisConfigBuilder,
+    blockchain::{
+        config::{GenesisConfigBuilder, InstanceInitParams},
+        ConsensusConfig, Schema,
+    },
     crypto,
-    helpers::ValidatorId,
+    helpers::{Height, ValidatorId},
     keys::Keys,
-    merkledb::TemporaryDB,
-    runtime::{RuntimeInstance, WellKnownRuntime},
+    merkledb::{BinaryValue, Database, TemporaryDB},
+    runtime::{ArtifactId, InstanceId, RuntimeInstance, WellKnownRuntime},
 };
 #[cfg(feature = "exonum-node")]
 use exonum_node::NodePlugin;
 use exonum_rust_runtime::{spec::Deploy, RustRuntime, RustRuntimeBuilder};
 use futures::channel::mpsc;
 
-use std::net::SocketAddr;
+#[cfg(feature = "tls")]
+use std::io;
+use std::{collections::HashMap, mem, net::SocketAddr, time::SystemTime};
 
-use crate::{ApiNotifierChannel, TestKit, TestNetwork};
+#[cfg(feature = "tls")]
+use crate::TlsConfig;
+use crate::{ApiNotifierChannel, Clock, TestKit, TestNetwork, TxOrdering};
 
 /// Builder for `TestKit`.
 ///
@@ -62,6 +69,7 @@ use crate::{ApiNotifierChannel, TestKit, TestNetwork};
 #[derive(Debug)]
 pub struct TestKitBuilder {
     our_validator_id: Option<ValidatorId>,
+    is_passive: bool,
     test_network: Option<TestNetwork>,
     logger: bool,
     rust_runtime: RustRuntimeBuilder,
@@ -70,6 +78,16 @@ pub struct TestKitBuilder {
     #[cfg(feature = "exonum-node")]
     plugins: Vec<Box<dyn NodePlugin>>,
     genesis_config: GenesisConfigBuilder,
+    consensus_config: Option<ConsensusConfig>,
+    max_block_transactions: Option<usize>,
+    tx_ordering: TxOrdering,
+    database: Option<TemporaryDB>,
+    genesis_services: bool,
+    status_history: bool,
+    snapshot_history: Option<usize>,
+    clock: Option<Clock>,
+    panic_on_tx_error: bool,
+    strict_decoding: bool,
 }
 
 impl TestKitBuilder {
@@ -79,10 +97,25 @@ impl TestKitBuilder {
     }
 
     /// Creates testkit for the auditor node.
+    ///
+    /// The testkit emulates the entire network, so an auditor testkit still creates blocks
+    /// on behalf of the validators. Use [`passive_auditor`](#method.passive_auditor)
+    /// to create an auditor that only applies blocks produced elsewhere.
     pub fn auditor() -> Self {
         Self::new(None)
     }
 
+    /// Creates testkit for the auditor node, which does not create blocks itself.
+    /// Blocks produced by validators (e.g., by another testkit built with the same validator
+    /// keys) can be applied to such a testkit with [`TestKit::apply_block`].
+    ///
+    /// [`TestKit::apply_block`]: struct.TestKit.html#method.apply_block
+    pub fn passive_auditor() -> Self {
+        let mut builder = Self::new(None);
+        builder.is_passive = true;
+        builder
+    }
+
     /// Creates the validator nodes from the specified keys.
     pub fn with_keys(mut self, keys: impl IntoIterator<Item = Keys>) -> Self {
         assert!(
@@ -111,6 +144,140 @@ impl TestKitBuilder {
         self
     }
 
+    /// Sets the consensus configuration (e.g., propose timeouts) recorded in the genesis block.
+    ///
+    /// Validator keys in the provided configuration are ignored; they are replaced with the keys
+    /// of the validators in the test network.
+    pub fn with_consensus_config(mut self, consensus_config: ConsensusConfig) -> Self {
+        self.consensus_config = Some(consensus_config);
+        self
+    }
+
+    /// Limits the number of transactions taken from the transaction pool into a single block
+    /// by [`TestKit::create_block`]. Transactions exceeding the limit remain in the pool
+    /// until the following blocks, similar to how real nodes limit the size of proposals.
+    ///
+    /// By default, the number of transactions in a block is not limited.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `max_transactions` is zero.
+    ///
+    /// [`TestKit::create_block`]: struct.TestKit.html#method.create_block
+    pub fn with_max_block_transactions(mut self, max_transactions: usize) -> Self {
+        assert!(
+            max_transactions > 0,
+            "Maximum number of transactions in a block should be positive"
+        );
+        self.max_block_transactions = Some(max_transactions);
+        self
+    }
+
+    /// Sets the order in which transactions from the transaction pool are included into a block
+    /// by [`TestKit::create_block`]. By default, transactions are ordered by their hashes.
+    ///
+    /// [`TestKit::create_block`]: struct.TestKit.html#method.create_block
+    pub fn with_tx_ordering(mut self, ordering: TxOrdering) -> Self {
+        self.tx_ordering = ordering;
+        self
+    }
+
+    /// Starts the testkit from the provided database rather than from an empty one.
+    /// The database can be obtained from another testkit with [`StoppedTestKit::into_database`].
+    ///
+    /// If the database already contains the genesis block, the genesis configuration
+    /// of the builder (including deployed artifacts and services) is ignored, and the blockchain
+    /// state is restored from the database. Runtimes and service factories still need to be
+    /// supplied to the builder. The test network should be built with the validator keys
+    /// from the genesis configuration recorded in the database, e.g., with [`with_keys`].
+    ///
+    /// [`StoppedTestKit::into_database`]: struct.StoppedTestKit.html#method.into_database
+    /// [`with_keys`]: #method.with_keys
+    pub fn with_database(mut self, database: TemporaryDB) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    /// Skips starting service instances in the genesis block. The artifacts of the instances
+    /// are still deployed, and the instances can be started in later blocks with
+    /// [`TestKit::start_service`]. This allows to test the logic of services which
+    /// are started after the genesis block.
+    ///
+    /// This option has no effect if the database provided with [`with_database`]
+    /// already contains the genesis block.
+    ///
+    /// [`TestKit::start_service`]: struct.TestKit.html#method.start_service
+    /// [`with_database`]: #method.with_database
+    pub fn without_genesis_services(mut self) -> Self {
+        self.genesis_services = false;
+        self
+    }
+
+    /// Makes the testkit record statuses of committed transactions, so that they can be
+    /// retrieved with [`TestKit::historical_status`] even after the corresponding blocks
+    /// are rolled back.
+    ///
+    /// [`TestKit::historical_status`]: struct.TestKit.html#method.historical_status
+    pub fn with_status_history(mut self) -> Self {
+        self.status_history = true;
+        self
+    }
+
+    /// Makes the testkit retain snapshots of the blockchain state for the specified number
+    /// of latest heights, so that they can be retrieved with [`TestKit::snapshot_at`].
+    /// By default, snapshots are not retained.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `heights` is zero.
+    ///
+    /// [`TestKit::snapshot_at`]: struct.TestKit.html#method.snapshot_at
+    pub fn with_snapshot_history(mut self, heights: usize) -> Self {
+        assert!(heights > 0, "Number of retained snapshots must be positive");
+        self.snapshot_history = Some(heights);
+        self
+    }
+
+    /// Makes the testkit panic if any transaction in a created or applied block fails,
+    /// e.g., in [`TestKit::create_block`]. The panic message contains the hash of the failed
+    /// transaction and the execution error. By default, the failure is only recorded
+    /// in the transaction status. The block is committed before the panic.
+    ///
+    /// This option allows to catch unintended transaction failures early in strict tests.
+    ///
+    /// [`TestKit::create_block`]: struct.TestKit.html#method.create_block
+    pub fn panic_on_tx_error(mut self) -> Self {
+        self.panic_on_tx_error = true;
+        self
+    }
+
+    /// Makes the testkit panic if the payload of any transaction in a created or applied block
+    /// cannot be decoded into the arguments of the called method. By default, such transactions
+    /// only fail with the `MalformedArguments` error recorded in the transaction status.
+    /// The block is committed before the panic.
+    ///
+    /// Unlike [`panic_on_tx_error`], this option does not affect transactions failing
+    /// for other reasons, so it can be used to catch serialization mismatches between
+    /// transactions and services in tests checking failed transactions.
+    ///
+    /// [`panic_on_tx_error`]: #method.panic_on_tx_error
+    pub fn with_strict_decoding(mut self) -> Self {
+        self.strict_decoding = true;
+        self
+    }
+
+    /// Makes the testkit read the time from `clock` instead of the system clock. The time is
+    /// used to timestamp precommits for the created blocks, so injecting a controllable clock
+    /// makes block times deterministic. The clock is retained if the testkit is stopped
+    /// and resumed.
+    ///
+    /// Note that this does not affect the time read by services themselves; e.g., the time
+    /// oracle service should be supplied with its own mock time provider.
+    pub fn with_clock(mut self, clock: Box<dyn Fn() -> SystemTime + Send + Sync>) -> Self {
+        self.clock = Some(Clock(clock));
+        self
+    }
+
     /// Adds a deploy spec to this builder. The spec may contain artifacts and service instances
     /// to deploy at the blockchain start.
     pub fn with(mut self, spec: impl Deploy) -> Self {
@@ -118,6 +285,32 @@ impl TestKitBuilder {
         self
     }
 
+    /// Deploys the artifact and starts a service instance from it in the genesis block,
+    /// passing `constructor` to the service initialization logic. The artifact is deployed
+    /// without a deploy spec; if it is already added to the builder, it is not deployed again.
+    ///
+    /// Unlike [`with`], this method does not register any service factories, so it can be used
+    /// to start several instances of the same artifact. The runtime must be able to deploy
+    /// the artifact; e.g., for Rust artifacts, the service factory should be added to the builder
+    /// separately, for example, with a [`JustFactory`] spec.
+    ///
+    /// [`with`]: #method.with
+    /// [`JustFactory`]: https://docs.rs/exonum-rust-runtime/latest/exonum_rust_runtime/spec/struct.JustFactory.html
+    pub fn with_instance(
+        mut self,
+        artifact: impl Into<ArtifactId>,
+        id: InstanceId,
+        name: impl Into<String>,
+        constructor: impl BinaryValue,
+    ) -> Self {
+        let artifact = artifact.into();
+        let instance = InstanceInitParams::new(id, name, artifact.clone(), constructor);
+        self.genesis_config = mem::take(&mut self.genesis_config)
+            .with_artifact(artifact)
+            .with_instance(instance);
+        self
+    }
+
     /// Adds a node plugin to the testkit.
     ///
     /// This method is only available if the crate is compiled with the `exonum-node` feature,
@@ -155,6 +348,14 @@ impl TestKitBuilder {
     }
 
     /// Creates the testkit.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the database provided with [`with_database`] contains the genesis block,
+    ///   and the validator keys from the genesis configuration recorded in the database
+    ///   differ from the keys of the test network.
+    ///
+    /// [`with_database`]: #method.with_database
     pub fn build(mut self) -> TestKit {
         if self.logger {
             exonum::helpers::init_logger().ok();
@@ -169,30 +370,47 @@ impl TestKitBuilder {
         let rust_runtime = self.rust_runtime.build(self.api_notifier_channel.0.clone());
         self.additional_runtimes.push(rust_runtime.into());
         let mut genesis_config = self.genesis_config.build();
-        genesis_config.consensus_config = network.consensus_config();
+        let deferred_instances = if self.genesis_services {
+            vec![]
+        } else {
+            mem::take(&mut genesis_config.builtin_instances)
+        };
+        genesis_config.consensus_config = match self.consensus_config {
+            Some(config) => config.with_validator_keys(network.consensus_config().validator_keys),
+            None => network.consensus_config(),
+        };
+
+        let database = match self.database {
+            Some(database) => {
+                check_database(&database, &network);
+                database
+            }
+            None => TemporaryDB::new(),
+        };
 
+        let mut testkit = TestKit::assemble(
+            database,
+            network,
+            self.is_passive,
+            Some(genesis_config),
+            self.additional_runtimes,
+            self.api_notifier_channel,
+        );
         #[cfg(feature = "exonum-node")]
-        {
-            let mut testkit = TestKit::assemble(
-                TemporaryDB::new(),
-                network,
-                Some(genesis_config),
-                self.additional_runtimes,
-                self.api_notifier_channel,
-            );
-            testkit.set_plugins(self.plugins);
-            testkit
+        testkit.set_plugins(self.plugins);
+        testkit.max_block_transactions = self.max_block_transactions;
+        testkit.tx_ordering = self.tx_ordering;
+        testkit.deferred_instances = deferred_instances;
+        if self.status_history {
+            testkit.status_history = Some(HashMap::new());
         }
-        #[cfg(not(feature = "exonum-node"))]
-        {
-            TestKit::assemble(
-                TemporaryDB::new(),
-                network,
-                Some(genesis_config),
-                self.additional_runtimes,
-                self.api_notifier_channel,
-            )
+        if let Some(heights) = self.snapshot_history {
+            testkit.enable_snapshot_history(heights);
         }
+        testkit.clock = self.clock;
+        testkit.panic_on_tx_error = self.panic_on_tx_error;
+        testkit.strict_decoding = self.strict_decoding;
+        testkit
     }
 
     /// Starts a testkit web server, which listens to public and private APIs exposed by
@@ -206,7 +424,33 @@ impl TestKitBuilder {
     /// See [`server` module](server/index.html) for the description of testkit server API.
     pub async fn serve(self, public_api_address: SocketAddr, private_api_address: SocketAddr) {
         let testkit = self.build();
-        testkit.run(public_api_address, private_api_address).await
+        testkit
+            .run(public_api_address, private_api_address, None)
+            .await
+    }
+
+    /// Starts a testkit web server with TLS enabled for both public and private APIs.
+    /// Otherwise, the server behaves the same as the one started with [`serve`](#method.serve).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TLS certificate or private key cannot be loaded. The error
+    /// is returned before the testkit is built.
+    ///
+    /// This method is only available with the `tls` crate feature.
+    #[cfg(feature = "tls")]
+    pub async fn serve_tls(
+        self,
+        public_api_address: SocketAddr,
+        private_api_address: SocketAddr,
+        tls_config: TlsConfig,
+    ) -> io::Result<()> {
+        tls_config.load()?;
+        let testkit = self.build();
+        testkit
+            .run(public_api_address, private_api_address, Some(tls_config))
+            .await;
+        Ok(())
     }
 
     // Creates testkit for validator or auditor node.
@@ -215,6 +459,7 @@ impl TestKitBuilder {
         Self {
             test_network: None,
             our_validator_id: validator_id,
+            is_passive: false,
             logger: false,
             rust_runtime: RustRuntimeBuilder::new(),
             api_notifier_channel,
@@ -222,6 +467,52 @@ impl TestKitBuilder {
             #[cfg(feature = "exonum-node")]
             plugins: vec![],
             genesis_config: GenesisConfigBuilder::default(),
+            consensus_config: None,
+            max_block_transactions: None,
+            tx_ordering: TxOrdering::default(),
+            database: None,
+            genesis_services: true,
+            status_history: false,
+            snapshot_history: None,
+            clock: None,
+            panic_on_tx_error: false,
+            strict_decoding: false,
+        }
+    }
+}
+
+/// Checks that the validators of the test network are the validators from the genesis
+/// configuration of the database.
+fn check_database(database: &TemporaryDB, network: &TestNetwork) {
+    const KEYS_MISMATCH: &str =
+        "Validator keys in the provided database do not match the test network";
+
+    let snapshot = database.snapshot();
+    let schema = Schema::new(&snapshot);
+    if schema.block_hashes_by_height().is_empty() {
+        return;
+    }
+
+    if let Some(block_proof) = schema.block_and_precommits(Height(1)) {
+        // The consensus configuration cannot change before the block at height 1 is committed,
+        // so this block is approved by the validators from the genesis configuration.
+        for precommit in &block_proof.precommits {
+            let validator = precommit.payload().validator;
+            let consensus_key = network
+                .validators()
+                .get(validator.0 as usize)
+                .map(|node| node.consensus_keypair().public_key());
+            assert_eq!(consensus_key, Some(precommit.author()), "{}", KEYS_MISMATCH);
         }
+    } else {
+        // The database contains only the genesis block, so the consensus configuration
+        // is the genesis one.
+        let validator_keys = schema.consensus_config().validator_keys;
+        assert_eq!(
+            validator_keys,
+            network.consensus_config().validator_keys,
+            "{}",
+            KEYS_MISMATCH
+        );
     }
 }
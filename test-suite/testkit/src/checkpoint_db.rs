@@ -80,6 +80,22 @@ impl<T: Database> Database for CheckpointDb<T> {
     fn merge_sync(&self, patch: Patch) -> StorageResult<()> {
         self.merge(patch)
     }
+
+    fn flush(&self) -> StorageResult<()> {
+        self.inner
+            .read()
+            .expect("Cannot lock CheckpointDb for flush")
+            .db
+            .flush()
+    }
+
+    fn approximate_num_keys(&self) -> usize {
+        self.inner
+            .read()
+            .expect("Cannot lock CheckpointDb for approximate_num_keys")
+            .db
+            .approximate_num_keys()
+    }
 }
 
 #[allow(clippy::use_self)] // false positive
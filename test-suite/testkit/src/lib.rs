@@ -119,19 +119,27 @@ pub use crate::{
     builder::TestKitBuilder,
     network::{TestNetwork, TestNode},
 };
+#[cfg(feature = "tls")]
+pub use exonum_api::TlsConfig;
 pub use exonum_explorer as explorer;
 pub use exonum_rust_runtime::spec::Spec;
 
 use exonum::{
     blockchain::{
-        config::GenesisConfig, ApiSender, BlockParams, Blockchain, BlockchainBuilder,
-        BlockchainMut, ConsensusConfig,
+        config::{GenesisConfig, InstanceInitParams},
+        ApiSender, Block, BlockExecutionSummary, BlockParams, BlockPatch, Blockchain,
+        BlockchainBuilder, BlockchainMut, ConsensusConfig, ProposerId, TxLocation,
     },
     crypto::{self, Hash},
     helpers::{byzantine_quorum, Height, ValidatorId},
-    merkledb::{BinaryValue, Database, ObjectHash, Snapshot, TemporaryDB},
+    merkledb::{
+        BinaryValue, Database, Fork, Iter, ObjectHash, ResolvedAddress, Snapshot, TemporaryDB,
+    },
     messages::{AnyTx, Verified},
-    runtime::{InstanceId, RuntimeInstance, SnapshotExt},
+    runtime::{
+        CallInfo, CommonError, ExecutionError, ExecutionFail, InstanceId, MethodId,
+        RuntimeInstance, SnapshotExt,
+    },
 };
 use exonum_api::{
     ApiAccess, ApiAggregator, ApiManager, ApiManagerConfig, UpdateEndpoints, WebServerConfig,
@@ -149,10 +157,11 @@ use futures::{
 use exonum_node::{ExternalMessage, NodePlugin, PluginApiContext, SharedNodeState};
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet, VecDeque},
     fmt, iter, mem,
     net::SocketAddr,
     sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
 };
 
 use crate::{
@@ -209,9 +218,35 @@ pub struct TestKit {
     events_stream: BoxStream<'static, ()>,
     processing_lock: Arc<Mutex<()>>,
     network: TestNetwork,
+    is_passive: bool,
     api_sender: ApiSender,
     api_notifier_channel: ApiNotifierChannel,
     api_aggregator: ApiAggregator,
+    /// Snapshots of the blockchain state after the recently committed blocks.
+    snapshot_history: VecDeque<(Height, Arc<dyn Snapshot>)>,
+    /// Maximum number of heights for which snapshots are retained. Zero if snapshots
+    /// are not retained.
+    snapshot_history_len: usize,
+    /// Maximum number of transactions taken from the pool into a block by `create_block`.
+    max_block_transactions: Option<usize>,
+    tx_ordering: TxOrdering,
+    /// Timing of the latest block created by the testkit.
+    last_block_timing: Option<BlockTiming>,
+    /// Hashes of the pooled transactions in the order of their arrival. May contain hashes
+    /// of transactions already removed from the pool.
+    pool_arrivals: Arc<Mutex<Vec<Hash>>>,
+    /// Service instances skipped in the genesis block, which can be started with `start_service`.
+    deferred_instances: Vec<InstanceInitParams>,
+    /// Statuses of committed transactions keyed by the block height and the transaction hash.
+    /// Unlike the blockchain state, the history is retained on rollbacks. `None` if the history
+    /// is not recorded.
+    status_history: Option<HashMap<(Height, Hash), Result<(), ExecutionError>>>,
+    /// Clock used to timestamp precommits. `None` if the system clock is used.
+    clock: Option<Clock>,
+    /// Should the testkit panic if a transaction in a committed block fails?
+    panic_on_tx_error: bool,
+    /// Should the testkit panic if a transaction in a committed block has malformed arguments?
+    strict_decoding: bool,
     #[cfg(feature = "exonum-node")]
     plugins: Vec<Box<dyn NodePlugin>>,
     #[cfg(feature = "exonum-node")]
@@ -245,6 +280,7 @@ impl TestKit {
     fn assemble(
         database: impl Into<CheckpointDb<TemporaryDB>>,
         network: TestNetwork,
+        is_passive: bool,
         genesis_config: Option<GenesisConfig>,
         runtimes: Vec<RuntimeInstance>,
         api_notifier_channel: ApiNotifierChannel,
@@ -272,6 +308,9 @@ impl TestKit {
         let processing_lock = Arc::new(Mutex::new(()));
         let processing_lock_ = Arc::clone(&processing_lock);
 
+        let pool_arrivals = Arc::new(Mutex::new(vec![]));
+        let pool_arrivals_ = Arc::clone(&pool_arrivals);
+
         let events_stream = api_channel.1.map(move |transaction| {
             let _guard = processing_lock_.lock().unwrap();
             let snapshot = db.snapshot();
@@ -282,7 +321,9 @@ impl TestKit {
                     error
                 );
             } else {
+                let tx_hash = transaction.object_hash();
                 BlockchainMut::add_transactions_into_db_pool(db.as_ref(), iter::once(transaction));
+                pool_arrivals_.lock().unwrap().push(tx_hash);
             }
         });
 
@@ -293,8 +334,20 @@ impl TestKit {
             events_stream: events_stream.boxed(),
             processing_lock,
             network,
+            is_passive,
             api_notifier_channel,
             api_aggregator: ApiAggregator::new(),
+            snapshot_history: VecDeque::new(),
+            snapshot_history_len: 0,
+            max_block_transactions: None,
+            tx_ordering: TxOrdering::default(),
+            last_block_timing: None,
+            pool_arrivals,
+            deferred_instances: vec![],
+            status_history: None,
+            clock: None,
+            panic_on_tx_error: false,
+            strict_decoding: false,
             #[cfg(feature = "exonum-node")]
             plugins: vec![],
             #[cfg(feature = "exonum-node")]
@@ -376,11 +429,207 @@ impl TestKit {
         self.blockchain.snapshot()
     }
 
+    /// Returns a snapshot of the blockchain state right after the block at the specified
+    /// `height` was committed.
+    ///
+    /// Snapshots for the past heights are retained only if the testkit was built
+    /// with [`TestKitBuilder::with_snapshot_history`], and only for the number of latest
+    /// heights specified there; snapshots are not retained when the testkit is
+    /// [stopped](#method.stop). Returns `None` if the `height` is greater than the current
+    /// blockchain height, or if the snapshot for the `height` is no longer retained.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the `height` is lower than the current blockchain height, and the testkit
+    ///   was built without [`TestKitBuilder::with_snapshot_history`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use exonum::{helpers::Height, runtime::SnapshotExt};
+    /// # use exonum_testkit::TestKitBuilder;
+    /// let mut testkit = TestKitBuilder::validator()
+    ///     .with_snapshot_history(16)
+    ///     .build();
+    /// testkit.create_blocks_until(Height(3));
+    /// let snapshot = testkit.snapshot_at(Height(1)).unwrap();
+    /// let block = snapshot.for_core().last_block();
+    /// assert_eq!(block.height, Height(1));
+    /// assert!(testkit.snapshot_at(Height(4)).is_none());
+    /// ```
+    ///
+    /// [`TestKitBuilder::with_snapshot_history`]: struct.TestKitBuilder.html#method.with_snapshot_history
+    pub fn snapshot_at(&self, height: Height) -> Option<Box<dyn Snapshot>> {
+        if height > self.height() {
+            return None;
+        } else if height == self.height() {
+            return Some(self.snapshot());
+        }
+        assert!(
+            self.snapshot_history_len > 0,
+            "Snapshot history is not recorded; use `TestKitBuilder::with_snapshot_history` \
+             to enable it"
+        );
+
+        self.snapshot_history
+            .iter()
+            .find(|(snapshot_height, _)| *snapshot_height == height)
+            .map(|(_, snapshot)| {
+                Box::new(SharedSnapshot(Arc::clone(snapshot))) as Box<dyn Snapshot>
+            })
+    }
+
+    /// Makes the testkit retain snapshots for `snapshot_at` for the specified number
+    /// of latest heights.
+    pub(crate) fn enable_snapshot_history(&mut self, heights: usize) {
+        self.snapshot_history_len = heights;
+        self.record_snapshot();
+    }
+
+    /// Remembers the snapshot of the current blockchain state for `snapshot_at`
+    /// if the snapshot history is enabled.
+    fn record_snapshot(&mut self) {
+        if self.snapshot_history_len == 0 {
+            return;
+        }
+        if self.snapshot_history.len() == self.snapshot_history_len {
+            self.snapshot_history.pop_front();
+        }
+        let snapshot: Arc<dyn Snapshot> = Arc::from(self.snapshot());
+        self.snapshot_history.push_back((self.height(), snapshot));
+    }
+
     /// Returns a blockchain used by the testkit.
     pub fn blockchain(&self) -> Blockchain {
         self.blockchain.as_ref().to_owned()
     }
 
+    /// Limits the number of latest blocks for which execution errors are stored, pruning
+    /// errors in the earlier blocks. This prevents unbounded storage growth in long test runs.
+    /// See [`BlockchainMut::set_status_retention`] for details.
+    ///
+    /// The setting is not preserved if the testkit is [stopped](#method.stop) and resumed.
+    ///
+    /// [`BlockchainMut::set_status_retention`]: https://docs.rs/exonum/latest/exonum/blockchain/struct.BlockchainMut.html#method.set_status_retention
+    pub fn set_status_retention(&mut self, blocks: u64) {
+        self.blockchain.set_status_retention(blocks);
+    }
+
+    /// Creates a fork of the current blockchain state. The fork can be used to modify
+    /// the storage directly, without creating a block; the changes can then be applied
+    /// with [`merge_fork`](#method.merge_fork).
+    ///
+    /// Direct storage modifications bypass transaction execution and may thus violate
+    /// service invariants. They are intended to craft the state for testing the read logic
+    /// of services, e.g., their HTTP API.
+    pub fn fork(&self) -> Fork {
+        self.blockchain.fork()
+    }
+
+    /// Applies changes from the `fork` created with [`fork`](#method.fork) to the storage.
+    /// The changes are visible in the subsequent snapshots; the blockchain height
+    /// is not changed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the changes cannot be merged into the database.
+    pub fn merge_fork(&mut self, fork: Fork) {
+        self.blockchain
+            .merge(fork.into_patch())
+            .expect("Cannot merge fork into the database");
+    }
+
+    /// Starts a service instance skipped in the genesis block because the testkit was built
+    /// with [`TestKitBuilder::without_genesis_services`]. The instance is added in a new block,
+    /// which also includes all correct transactions in the pool, similar to [`create_block`].
+    /// Like instances started by the supervisor, the instance becomes active after this block
+    /// is committed, so it can process transactions starting from the next block.
+    ///
+    /// Adding the instance is not recorded in the block, thus, other nodes (e.g., auditors
+    /// applying the block via [`apply_block`]) cannot reproduce it.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there is no skipped instance with the specified name.
+    /// - Panics if the instance is already started.
+    /// - Panics if the service fails to initialize.
+    ///
+    /// [`TestKitBuilder::without_genesis_services`]: struct.TestKitBuilder.html#method.without_genesis_services
+    /// [`create_block`]: #method.create_block
+    /// [`apply_block`]: #method.apply_block
+    pub fn start_service(&mut self, instance_name: &str) -> BlockWithTransactions {
+        assert!(
+            !self.is_passive,
+            "Passive auditor cannot create blocks; use `TestKit::apply_block` to apply \
+             blocks produced by validators"
+        );
+        let instance = self
+            .deferred_instances
+            .iter()
+            .find(|instance| instance.instance_spec.name == instance_name)
+            .cloned()
+            .unwrap_or_else(|| {
+                panic!(
+                    "Service instance `{}` was not skipped in the genesis block",
+                    instance_name
+                )
+            });
+        let is_started = self
+            .snapshot()
+            .for_dispatcher()
+            .get_instance(instance_name)
+            .is_some();
+        assert!(
+            !is_started,
+            "Service instance `{}` is already started",
+            instance_name
+        );
+
+        let tx_hashes = self.block_candidates();
+        let validator_id = self.leader().validator_id().unwrap();
+        let mut start_result = None;
+        let block = self.do_commit_block_with(
+            validator_id,
+            &tx_hashes,
+            None,
+            None,
+            |blockchain, block_params| {
+                let (patch, res) =
+                    blockchain.create_patch_with_new_service(block_params, &(), instance);
+                start_result = Some(res);
+                patch
+            },
+        );
+        let start_result = start_result.expect("BUG: block patch was not created");
+        if let Err(err) = start_result {
+            panic!("Cannot start service `{}`: {}", instance_name, err);
+        }
+        block
+    }
+
+    /// Returns the status of a transaction committed at the specified height. Unlike
+    /// the statuses stored in the blockchain, the returned status remains available after
+    /// the block with the transaction is [rolled back](#method.rollback). This can be used
+    /// to check that re-executing transactions yields the same results.
+    ///
+    /// Returns `None` if the testkit has not committed the transaction at the specified height.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the testkit was built without [`TestKitBuilder::with_status_history`].
+    ///
+    /// [`TestKitBuilder::with_status_history`]: struct.TestKitBuilder.html#method.with_status_history
+    pub fn historical_status(
+        &self,
+        height: Height,
+        tx_hash: Hash,
+    ) -> Option<Result<(), ExecutionError>> {
+        let status_history = self.status_history.as_ref().expect(
+            "Status history is not recorded; use `TestKitBuilder::with_status_history` to enable it",
+        );
+        status_history.get(&(height, tx_hash)).cloned()
+    }
+
     /// Sets a checkpoint for a future [`rollback`](#method.rollback).
     pub fn checkpoint(&mut self) {
         self.db_handler.checkpoint()
@@ -451,32 +700,114 @@ impl TestKit {
     /// testkit.rollback();
     /// ```
     pub fn rollback(&mut self) {
-        self.db_handler.rollback()
+        self.db_handler.rollback();
+        let height = self.height();
+        while let Some((snapshot_height, _)) = self.snapshot_history.back() {
+            if *snapshot_height <= height {
+                break;
+            }
+            self.snapshot_history.pop_back();
+        }
+    }
+
+    /// Creates a block with the specified transaction hashes. If `time` is not specified,
+    /// precommits for the block are timestamped using the testkit clock.
+    fn do_create_block(
+        &mut self,
+        tx_hashes: &[Hash],
+        time: Option<SystemTime>,
+    ) -> BlockWithTransactions {
+        assert!(
+            !self.is_passive,
+            "Passive auditor cannot create blocks; use `TestKit::apply_block` to apply \
+             blocks produced by validators"
+        );
+        let validator_id = self.leader().validator_id().unwrap();
+        self.do_commit_block(validator_id, tx_hashes, None, time)
+    }
+
+    /// Executes and commits a block with the specified proposer and transaction hashes.
+    /// If `expected_block` is specified, the executed block is checked to be equal to it
+    /// before committing.
+    fn do_commit_block(
+        &mut self,
+        proposer_id: ValidatorId,
+        tx_hashes: &[Hash],
+        expected_block: Option<&Block>,
+        time: Option<SystemTime>,
+    ) -> BlockWithTransactions {
+        self.do_commit_block_with(
+            proposer_id,
+            tx_hashes,
+            expected_block,
+            time,
+            |blockchain, block_params| blockchain.create_patch(block_params, &()),
+        )
     }
 
-    /// Creates a block with the specified transaction hashes.
-    fn do_create_block(&mut self, tx_hashes: &[Hash]) -> BlockWithTransactions {
+    /// Executes and commits a block similar to `do_commit_block`, but with the block patch
+    /// created by the specified closure.
+    fn do_commit_block_with<F>(
+        &mut self,
+        proposer_id: ValidatorId,
+        tx_hashes: &[Hash],
+        expected_block: Option<&Block>,
+        time: Option<SystemTime>,
+        create_patch: F,
+    ) -> BlockWithTransactions
+    where
+        F: FnOnce(&BlockchainMut, BlockParams<'_>) -> BlockPatch,
+    {
+        let start = Instant::now();
         let new_block_height = self.height().next();
         let saved_consensus_config = self.consensus_config();
-        let validator_id = self.leader().validator_id().unwrap();
 
         let guard = self.processing_lock.lock().unwrap();
-        let block_params = BlockParams::new(validator_id, new_block_height, tx_hashes);
-        let patch = self.blockchain.create_patch(block_params, &());
+        let block_params = BlockParams::new(proposer_id, new_block_height, tx_hashes);
+        let dispatch_start = Instant::now();
+        let patch = create_patch(&self.blockchain, block_params);
+        let dispatch = dispatch_start.elapsed();
         let block_hash = patch.block_hash();
+        if let Some(expected_block) = expected_block {
+            assert_eq!(
+                block_hash,
+                expected_block.object_hash(),
+                "Applied block does not match the block obtained by executing its transactions"
+            );
+        }
 
+        let time =
+            time.unwrap_or_else(|| self.clock.as_ref().map_or_else(SystemTime::now, Clock::now));
         let precommits: Vec<_> = self
             .network()
             .validators()
             .iter()
-            .map(|validator| validator.create_precommit(new_block_height, block_hash))
+            .map(|validator| validator.create_precommit_at(new_block_height, block_hash, time))
             .collect();
 
+        let merge_start = Instant::now();
         self.blockchain
             .commit(patch, precommits.into_iter())
             .unwrap();
+        let merge = merge_start.elapsed();
         drop(guard);
 
+        if let Some(status_history) = &mut self.status_history {
+            record_statuses(
+                &self.blockchain.snapshot(),
+                new_block_height,
+                status_history,
+            );
+        }
+
+        // Forget about the transactions removed from the pool.
+        let snapshot = self.snapshot();
+        let pool = snapshot.for_core().transactions_pool();
+        self.pool_arrivals
+            .lock()
+            .unwrap()
+            .retain(|tx_hash| pool.contains(tx_hash));
+
         // Modify the self configuration.
         let actual_consensus_config = self.consensus_config();
         if actual_consensus_config != saved_consensus_config {
@@ -485,6 +816,7 @@ impl TestKit {
         }
 
         self.poll_events();
+        self.record_snapshot();
         let snapshot = self.snapshot();
 
         #[cfg(feature = "exonum-node")]
@@ -492,13 +824,58 @@ impl TestKit {
             plugin.after_commit(&snapshot);
         }
 
-        BlockchainExplorer::new(&snapshot)
+        let block = BlockchainExplorer::new(&snapshot)
             .block_with_txs(self.height())
-            .unwrap()
+            .unwrap();
+        self.last_block_timing = Some(BlockTiming {
+            dispatch,
+            merge,
+            total: start.elapsed(),
+        });
+
+        if self.strict_decoding {
+            let malformed_arguments = CommonError::MalformedArguments.kind();
+            for tx in &block.transactions {
+                if let Err(err) = tx.status() {
+                    if err.kind() == malformed_arguments {
+                        panic!(
+                            "Payload of transaction {} in block at height {} cannot be decoded: {}",
+                            tx.message().object_hash(),
+                            new_block_height,
+                            err
+                        );
+                    }
+                }
+            }
+        }
+        if self.panic_on_tx_error {
+            for tx in &block.transactions {
+                if let Err(err) = tx.status() {
+                    panic!(
+                        "Transaction {} in block at height {} failed: {}",
+                        tx.message().object_hash(),
+                        new_block_height,
+                        err
+                    );
+                }
+            }
+        }
+        block
+    }
+
+    /// Returns the time spent on the latest block created or applied by the testkit,
+    /// or `None` if no blocks were created since the testkit was built or resumed.
+    ///
+    /// This information may be useful to catch accidental inefficiencies in the service logic,
+    /// e.g., a quadratic dependency of the execution time on the number of transactions.
+    /// Keep in mind that the timings are measured on the wall clock and are thus imprecise.
+    pub fn last_block_timing(&self) -> Option<BlockTiming> {
+        self.last_block_timing
     }
 
     /// Creates a block with the given transactions.
-    /// Transactions that are in the pool will be ignored.
+    /// Transactions that are in the pool will be ignored. If a transaction is specified
+    /// several times, it is included into the block only once.
     ///
     /// # Return value
     ///
@@ -555,7 +932,8 @@ impl TestKit {
     }
 
     /// Creates block with the specified transactions. The transactions must be previously
-    /// sent to the node via API or directly put into the `channel()`.
+    /// sent to the node via API or directly put into the `channel()`. If a transaction hash
+    /// is specified several times, the transaction is included into the block only once.
     ///
     /// # Return value
     ///
@@ -572,9 +950,17 @@ impl TestKit {
     ) -> BlockWithTransactions {
         self.poll_events();
 
+        // The same transaction cannot be executed twice, so repeated hashes are skipped.
+        let mut seen = HashSet::with_capacity(tx_hashes.len());
+        let tx_hashes: Vec<_> = tx_hashes
+            .iter()
+            .copied()
+            .filter(|tx_hash| seen.insert(*tx_hash))
+            .collect();
+
         let snapshot = self.blockchain.snapshot();
         let schema = snapshot.for_core();
-        for hash in tx_hashes {
+        for hash in &tx_hashes {
             assert!(
                 schema.transactions_pool().contains(hash),
                 "Transaction with hash {:?} is not found in the transaction pool",
@@ -592,10 +978,13 @@ impl TestKit {
                 );
             }
         }
-        self.do_create_block(tx_hashes)
+        self.do_create_block(&tx_hashes, None)
     }
 
-    /// Creates a block with all correct transactions in the pool.
+    /// Creates a block with all correct transactions in the pool. If the maximum number
+    /// of transactions in a block is set via [`TestKitBuilder::with_max_block_transactions`],
+    /// at most this number of transactions is included into the block, and the remaining
+    /// transactions stay in the pool.
     ///
     /// Transaction correctness is defined per [`Blockchain::check_tx`] method.
     /// See the [type-level docs](#transaction-checks) for more details.
@@ -605,7 +994,109 @@ impl TestKit {
     /// Returns information about the created block.
     ///
     /// [`Blockchain::check_tx`]: https://docs.rs/exonum/latest/exonum/blockchain/struct.Blockchain.html#method.check_tx
+    /// [`TestKitBuilder::with_max_block_transactions`]: struct.TestKitBuilder.html#method.with_max_block_transactions
     pub fn create_block(&mut self) -> BlockWithTransactions {
+        let tx_hashes = self.block_candidates();
+        self.do_create_block(&tx_hashes, None)
+    }
+
+    /// Creates a block with all correct transactions in the pool, similar to [`create_block`],
+    /// timestamping precommits for the block with the specified `time` instead of
+    /// the testkit clock.
+    ///
+    /// # Errors
+    ///
+    /// Similar to real nodes, block times are required to increase monotonically.
+    /// If `time` is earlier than the time of the latest committed block, an error is returned
+    /// and no block is created.
+    ///
+    /// [`create_block`]: #method.create_block
+    pub fn create_block_with_time(
+        &mut self,
+        time: SystemTime,
+    ) -> Result<BlockWithTransactions, NonMonotonicBlockTime> {
+        if let Some(previous) = self.last_block_time() {
+            if time < previous {
+                return Err(NonMonotonicBlockTime {
+                    previous,
+                    requested: time,
+                });
+            }
+        }
+        let tx_hashes = self.block_candidates();
+        Ok(self.do_create_block(&tx_hashes, Some(time)))
+    }
+
+    /// Calls the method `method_id` of the service `to_instance` on behalf of the service
+    /// `from_instance` and creates a block with the changes made by the call, if it succeeds,
+    /// and all correct transactions in the pool, similar to [`create_block`].
+    /// The called service observes the call as one made by `from_instance`, i.e.,
+    /// with `Caller::Service`, which allows to test inter-service calls and
+    /// their authorization without implementing a calling service method.
+    ///
+    /// The call is performed at the beginning of the block, before `before_transactions`
+    /// hooks. It is not recorded in the block, thus, other nodes (e.g., auditors applying
+    /// the block via [`apply_block`]) cannot reproduce its changes.
+    ///
+    /// # Return value
+    ///
+    /// Returns the result of the call. The block is created regardless of the call outcome;
+    /// changes made by a failed call are rolled back.
+    ///
+    /// [`create_block`]: #method.create_block
+    /// [`apply_block`]: #method.apply_block
+    pub fn call_service(
+        &mut self,
+        from_instance: InstanceId,
+        to_instance: InstanceId,
+        method_id: MethodId,
+        payload: impl BinaryValue,
+    ) -> Result<(), ExecutionError> {
+        assert!(
+            !self.is_passive,
+            "Passive auditor cannot create blocks; use `TestKit::apply_block` to apply \
+             blocks produced by validators"
+        );
+        let tx_hashes = self.block_candidates();
+        let validator_id = self.leader().validator_id().unwrap();
+        let call_info = CallInfo::new(to_instance, method_id);
+        let arguments = payload.into_bytes();
+
+        let mut call_result = None;
+        self.do_commit_block_with(
+            validator_id,
+            &tx_hashes,
+            None,
+            None,
+            |blockchain, block_params| {
+                let (patch, res) = blockchain.create_patch_with_service_call(
+                    block_params,
+                    &(),
+                    from_instance,
+                    &call_info,
+                    &arguments,
+                );
+                call_result = Some(res);
+                patch
+            },
+        );
+        call_result.expect("BUG: block patch was not created")
+    }
+
+    /// Returns the time of the latest committed block, i.e., the latest time among
+    /// the block precommits. Returns `None` for the genesis block, which has no precommits.
+    fn last_block_time(&self) -> Option<SystemTime> {
+        let snapshot = self.snapshot();
+        let precommits = snapshot.for_core().precommits(&self.last_block_hash());
+        let latest_time = precommits
+            .iter()
+            .map(|precommit| precommit.payload().time)
+            .max();
+        latest_time.map(SystemTime::from)
+    }
+
+    /// Returns hashes of correct transactions in the pool to be included into the next block.
+    fn block_candidates(&mut self) -> Vec<Hash> {
         self.poll_events();
         let snapshot = self.snapshot();
         let core_schema = snapshot.for_core();
@@ -627,12 +1118,95 @@ impl TestKit {
             }
         };
 
-        let tx_hashes: Vec<_> = core_schema
-            .transactions_pool()
-            .iter()
+        let max_transactions = self.max_block_transactions.unwrap_or(usize::max_value());
+        self.pooled_tx_hashes(snapshot.as_ref())
+            .into_iter()
             .filter(filter_transactions)
+            .take(max_transactions)
+            .collect()
+    }
+
+    /// Returns hashes of transactions in the pool in the order specified by `tx_ordering`.
+    fn pooled_tx_hashes(&self, snapshot: &dyn Snapshot) -> Vec<Hash> {
+        let pool = snapshot.for_core().transactions_pool();
+        match self.tx_ordering {
+            TxOrdering::ByHash => pool.iter().collect(),
+            TxOrdering::Fifo => {
+                let mut seen = HashSet::new();
+                let arrivals = self.pool_arrivals.lock().unwrap();
+                let mut tx_hashes: Vec<_> = arrivals
+                    .iter()
+                    .copied()
+                    .filter(|tx_hash| pool.contains(tx_hash) && seen.insert(*tx_hash))
+                    .collect();
+                // Transactions with unknown arrival order (e.g., ones restored by a rollback)
+                // go last.
+                tx_hashes.extend(pool.iter().filter(|tx_hash| seen.insert(*tx_hash)));
+                tx_hashes
+            }
+        }
+    }
+
+    /// Applies a block produced elsewhere (e.g., by another testkit acting as a validator)
+    /// together with its transactions. The block is executed locally, and the result
+    /// is checked against the provided block header, similar to how real auditor nodes
+    /// process committed blocks.
+    ///
+    /// This method is mostly useful for [passive auditors], which cannot create blocks
+    /// themselves.
+    ///
+    /// # Return value
+    ///
+    /// Returns information about the applied block.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the block does not directly follow the latest committed block.
+    /// - Panics if transactions do not match the block header, or if the block execution
+    ///   leads to a different block hash.
+    ///
+    /// [passive auditors]: struct.TestKitBuilder.html#method.passive_auditor
+    pub fn apply_block<I>(&mut self, block: &Block, transactions: I) -> BlockWithTransactions
+    where
+        I: IntoIterator<Item = Verified<AnyTx>>,
+    {
+        assert_eq!(
+            block.height,
+            self.height().next(),
+            "Applied block does not follow the latest committed block"
+        );
+        assert_eq!(
+            block.prev_hash,
+            self.last_block_hash(),
+            "Applied block does not reference the latest committed block"
+        );
+        let proposer_id = block
+            .get_header::<ProposerId>()
+            .ok()
+            .flatten()
+            .expect("Applied block does not specify its proposer");
+
+        let snapshot = self.snapshot();
+        let schema = snapshot.for_core();
+        let mut unknown_transactions = vec![];
+        let tx_hashes: Vec<_> = transactions
+            .into_iter()
+            .map(|tx| {
+                let tx_id = tx.object_hash();
+                if !schema.transactions().contains(&tx_id) {
+                    unknown_transactions.push(tx);
+                }
+                tx_id
+            })
             .collect();
-        self.do_create_block(&tx_hashes)
+        assert_eq!(
+            tx_hashes.len(),
+            block.tx_count as usize,
+            "Number of transactions does not match the block header"
+        );
+        self.blockchain
+            .add_transactions_into_pool(unknown_transactions);
+        self.do_commit_block(proposer_id, &tx_hashes, Some(block), None)
     }
 
     /// Adds a transaction into the persistent pool.
@@ -648,8 +1222,10 @@ impl TestKit {
                 error
             );
         }
+        let tx_hash = transaction.object_hash();
         self.blockchain
             .add_transactions_into_pool(iter::once(transaction));
+        self.pool_arrivals.lock().unwrap().push(tx_hash);
     }
 
     /// Checks if a transaction with the specified hash is found in the transaction pool.
@@ -660,6 +1236,59 @@ impl TestKit {
             .contains(tx_hash)
     }
 
+    /// Asserts that a transaction with the specified hash is in the transaction pool
+    /// and is not committed yet.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the transaction is committed or is unknown to the blockchain.
+    pub fn assert_pooled(&self, tx_hash: Hash) {
+        let snapshot = self.snapshot();
+        let schema = snapshot.for_core();
+        if schema.transactions_pool().contains(&tx_hash) {
+            return;
+        }
+        if let Some(location) = schema.transactions_locations().get(&tx_hash) {
+            panic!(
+                "Transaction {:?} is expected to be in the pool, but it is committed \
+                 at height {} (position {})",
+                tx_hash,
+                location.block_height(),
+                location.position_in_block()
+            );
+        } else {
+            panic!(
+                "Transaction {:?} is expected to be in the pool, but it is unknown",
+                tx_hash
+            );
+        }
+    }
+
+    /// Asserts that a transaction with the specified hash is committed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the transaction is only in the transaction pool or is unknown
+    ///   to the blockchain.
+    pub fn assert_committed(&self, tx_hash: Hash) {
+        let snapshot = self.snapshot();
+        let schema = snapshot.for_core();
+        if schema.transactions_locations().contains(&tx_hash) {
+            return;
+        }
+        if schema.transactions_pool().contains(&tx_hash) {
+            panic!(
+                "Transaction {:?} is expected to be committed, but it is only in the pool",
+                tx_hash
+            );
+        } else {
+            panic!(
+                "Transaction {:?} is expected to be committed, but it is unknown",
+                tx_hash
+            );
+        }
+    }
+
     /// Creates a chain of blocks until a given height.
     ///
     /// # Example
@@ -679,6 +1308,43 @@ impl TestKit {
         }
     }
 
+    /// Exports blocks in the inclusive range `from..=to` together with their precommits,
+    /// transactions and execution statuses. Blocks are returned in the order of increasing
+    /// height.
+    ///
+    /// The exported blocks are serializable, so they can be used to replay the blockchain
+    /// into an external system, e.g., an indexer tested against the testkit.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `to` exceeds the current blockchain height.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use exonum::helpers::Height;
+    /// # use exonum_testkit::TestKitBuilder;
+    /// let mut testkit = TestKitBuilder::validator().build();
+    /// testkit.create_blocks_until(Height(5));
+    /// let blocks = testkit.export_blocks(Height(2), Height(4));
+    /// assert_eq!(blocks.len(), 3);
+    /// assert_eq!(blocks[0].header.height, Height(2));
+    /// ```
+    pub fn export_blocks(&self, from: Height, to: Height) -> Vec<BlockWithTransactions> {
+        assert!(
+            to <= self.height(),
+            "Cannot export blocks up to height {}, blockchain height is {}",
+            to,
+            self.height()
+        );
+
+        let snapshot = self.snapshot();
+        let explorer = BlockchainExplorer::new(&snapshot);
+        (from.0..=to.0)
+            .map(|height| explorer.block_with_txs(Height(height)).unwrap())
+            .collect()
+    }
+
     /// Returns the hash of latest committed block.
     pub fn last_block_hash(&self) -> crypto::Hash {
         self.blockchain.as_ref().last_hash()
@@ -689,6 +1355,19 @@ impl TestKit {
         self.blockchain.as_ref().last_block().height
     }
 
+    /// Returns the hash of the blockchain state after the latest committed block.
+    pub fn state_hash(&self) -> crypto::Hash {
+        self.blockchain.as_ref().last_block().state_hash
+    }
+
+    /// Returns the aggregated results of transaction execution in the latest committed block.
+    pub fn last_block_summary(&self) -> BlockExecutionSummary {
+        self.snapshot()
+            .for_core()
+            .block_execution_summary(self.height())
+            .expect("Latest committed block has no call records")
+    }
+
     /// Returns an actual blockchain configuration.
     pub fn consensus_config(&self) -> ConsensusConfig {
         self.snapshot().for_core().consensus_config()
@@ -703,6 +1382,16 @@ impl TestKit {
         self.network.validators()[id.0 as usize].clone()
     }
 
+    /// Signs a transaction with the service keypair of the validator with the given identifier.
+    /// The returned transaction can be passed to `create_block_with_transaction(s)`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if validator with the given ID is absent in the test network.
+    pub fn sign_with_validator(&self, id: ValidatorId, tx: AnyTx) -> Verified<AnyTx> {
+        self.validator(id).sign(tx)
+    }
+
     /// Returns sufficient number of validators for the Byzantine Fault Tolerance consensus.
     pub fn majority_count(&self) -> usize {
         byzantine_quorum(self.network().validators().len())
@@ -724,7 +1413,12 @@ impl TestKit {
     }
 
     #[allow(clippy::mut_mut, clippy::unused_unit)] // occurs withing `select!` macro
-    async fn run(mut self, public_api_address: SocketAddr, private_api_address: SocketAddr) {
+    async fn run(
+        mut self,
+        public_api_address: SocketAddr,
+        private_api_address: SocketAddr,
+        tls_config: Option<exonum_api::TlsConfig>,
+    ) {
         let events_task = self.remove_events_stream().fuse();
         futures::pin_mut!(events_task);
 
@@ -732,12 +1426,16 @@ impl TestKit {
         let (api_aggregator, actor_task) = TestKitActor::spawn(self).await;
         let mut actor_task = actor_task.fuse();
 
+        let server_config = |address| {
+            let config = WebServerConfig::new(address);
+            match tls_config.clone() {
+                Some(tls) => config.with_tls(tls),
+                None => config,
+            }
+        };
         let mut servers = HashMap::new();
-        servers.insert(ApiAccess::Public, WebServerConfig::new(public_api_address));
-        servers.insert(
-            ApiAccess::Private,
-            WebServerConfig::new(private_api_address),
-        );
+        servers.insert(ApiAccess::Public, server_config(public_api_address));
+        servers.insert(ApiAccess::Private, server_config(private_api_address));
         let api_manager_config = ApiManagerConfig::new(servers, api_aggregator);
         let manager_task = ApiManager::new(api_manager_config).run(endpoints_rx).fuse();
         futures::pin_mut!(manager_task);
@@ -769,6 +1467,12 @@ impl TestKit {
         self.network().us().clone()
     }
 
+    /// Returns the identifier of the validator emulated by the testkit, or `None` if
+    /// the testkit emulates an auditor node.
+    pub fn our_validator_id(&self) -> Option<ValidatorId> {
+        self.network().us().validator_id()
+    }
+
     /// Emulates stopping the node. The stopped node can then be `restart()`ed.
     ///
     /// See [`StoppedTestKit`] documentation for more details how to use this method.
@@ -777,18 +1481,64 @@ impl TestKit {
     pub fn stop(self) -> StoppedTestKit {
         let db = self.db_handler.into_inner();
         let network = self.network;
+        let is_passive = self.is_passive;
         let api_notifier_channel = self.api_notifier_channel;
+        let max_block_transactions = self.max_block_transactions;
+        let tx_ordering = self.tx_ordering;
+        let snapshot_history_len = self.snapshot_history_len;
+        let clock = self.clock;
+        let panic_on_tx_error = self.panic_on_tx_error;
+        let strict_decoding = self.strict_decoding;
         #[cfg(feature = "exonum-node")]
         let plugins = self.plugins;
 
         StoppedTestKit {
             network,
+            is_passive,
             db,
             api_notifier_channel,
+            max_block_transactions,
+            tx_ordering,
+            snapshot_history_len,
+            clock,
+            panic_on_tx_error,
+            strict_decoding,
             #[cfg(feature = "exonum-node")]
             plugins,
         }
     }
+
+    /// Emulates restarting the node: stops the testkit and immediately resumes it with
+    /// the specified Rust runtime. This is a shortcut for [`stop`] followed by
+    /// [`StoppedTestKit::resume`].
+    ///
+    /// The database state (including the transaction pool) and the network configuration
+    /// are retained, while the services are re-created from the factories in `rust_runtime`
+    /// and resumed from the persisted state. Since the testkit does not own the service
+    /// factories, they need to be provided by the caller.
+    ///
+    /// [`stop`]: #method.stop
+    /// [`StoppedTestKit::resume`]: struct.StoppedTestKit.html#method.resume
+    pub fn restart(self, rust_runtime: RustRuntimeBuilder) -> Self {
+        self.stop().resume(rust_runtime)
+    }
+}
+
+/// Records statuses of transactions in the block at the specified height.
+fn record_statuses(
+    snapshot: &dyn Snapshot,
+    height: Height,
+    status_history: &mut HashMap<(Height, Hash), Result<(), ExecutionError>>,
+) {
+    let schema = snapshot.for_core();
+    for (position, tx_hash) in schema.block_transactions(height).iter().enumerate() {
+        let location = TxLocation::new(height, position as u32);
+        let status = schema
+            .transaction_result(location)
+            .expect("BUG: no status for a committed transaction");
+        // A transaction repeated within the block is only executed at its first position.
+        status_history.entry((height, tx_hash)).or_insert(status);
+    }
 }
 
 /// Persistent state of an Exonum node allowing to emulate node restart.
@@ -871,7 +1621,14 @@ pub struct StoppedTestKit {
     #[cfg(feature = "exonum-node")]
     plugins: Vec<Box<dyn NodePlugin>>,
     network: TestNetwork,
+    is_passive: bool,
     api_notifier_channel: ApiNotifierChannel,
+    max_block_transactions: Option<usize>,
+    tx_ordering: TxOrdering,
+    snapshot_history_len: usize,
+    clock: Option<Clock>,
+    panic_on_tx_error: bool,
+    strict_decoding: bool,
 }
 
 impl fmt::Debug for StoppedTestKit {
@@ -900,6 +1657,23 @@ impl StoppedTestKit {
         &self.network
     }
 
+    /// Converts the stopped testkit into the underlying database. The database can be used
+    /// to start another testkit with [`TestKitBuilder::with_database`].
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the database is still referenced elsewhere, e.g., by a `TestKitApi` instance
+    ///   created before the testkit was stopped.
+    ///
+    /// [`TestKitBuilder::with_database`]: struct.TestKitBuilder.html#method.with_database
+    pub fn into_database(self) -> TemporaryDB {
+        let handler = self.db.handler();
+        drop(self.db);
+        handler
+            .try_unwrap()
+            .unwrap_or_else(|_| panic!("Testkit database is still in use"))
+    }
+
     /// Resumes the operation of the testkit with the Rust runtime.
     ///
     /// Note that services in the Rust runtime may differ from the initially passed to the `TestKit`
@@ -920,31 +1694,128 @@ impl StoppedTestKit {
         self.do_resume(runtimes)
     }
 
-    #[cfg(feature = "exonum-node")]
     fn do_resume(self, runtimes: Vec<RuntimeInstance>) -> TestKit {
         let mut testkit = TestKit::assemble(
             self.db,
             self.network,
+            self.is_passive,
             None,
             runtimes,
             self.api_notifier_channel,
         );
+        #[cfg(feature = "exonum-node")]
         testkit.set_plugins(self.plugins);
+        testkit.max_block_transactions = self.max_block_transactions;
+        testkit.tx_ordering = self.tx_ordering;
+        if self.snapshot_history_len > 0 {
+            testkit.enable_snapshot_history(self.snapshot_history_len);
+        }
+        testkit.clock = self.clock;
+        testkit.panic_on_tx_error = self.panic_on_tx_error;
+        testkit.strict_decoding = self.strict_decoding;
         testkit
     }
+}
 
-    #[cfg(not(feature = "exonum-node"))]
-    fn do_resume(self, runtimes: Vec<RuntimeInstance>) -> TestKit {
-        TestKit::assemble(
-            self.db,
-            self.network,
-            None,
-            runtimes,
-            self.api_notifier_channel,
+/// Time spent by the testkit on creating a block.
+///
+/// See [`TestKit::last_block_timing`] for details.
+///
+/// [`TestKit::last_block_timing`]: struct.TestKit.html#method.last_block_timing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BlockTiming {
+    /// Time spent on executing transactions and other block logic of services.
+    pub dispatch: Duration,
+    /// Time spent on merging the block changes into the database.
+    pub merge: Duration,
+    /// Total time spent on the block, including the time spent by the testkit itself.
+    pub total: Duration,
+}
+
+/// Error returned by [`TestKit::create_block_with_time`] if the requested block time
+/// is earlier than the time of the latest committed block.
+///
+/// [`TestKit::create_block_with_time`]: struct.TestKit.html#method.create_block_with_time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NonMonotonicBlockTime {
+    /// Time of the latest committed block.
+    pub previous: SystemTime,
+    /// Requested time of the new block.
+    pub requested: SystemTime,
+}
+
+impl fmt::Display for NonMonotonicBlockTime {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "Block time {:?} is earlier than the time of the previous block {:?}",
+            self.requested, self.previous
         )
     }
 }
 
+impl std::error::Error for NonMonotonicBlockTime {}
+
+/// Order in which transactions from the transaction pool are included into a block
+/// by [`TestKit::create_block`].
+///
+/// [`TestKit::create_block`]: struct.TestKit.html#method.create_block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TxOrdering {
+    /// Transactions are ordered by their hashes. This is the default ordering.
+    ByHash,
+    /// Transactions are ordered by the time they were added to the pool of the testkit,
+    /// either via [`TestKit::add_tx`] or via the API.
+    ///
+    /// [`TestKit::add_tx`]: struct.TestKit.html#method.add_tx
+    Fifo,
+}
+
+impl Default for TxOrdering {
+    fn default() -> Self {
+        Self::ByHash
+    }
+}
+
+/// Clock injected into the testkit with `TestKitBuilder::with_clock`.
+pub(crate) struct Clock(Box<dyn Fn() -> SystemTime + Send + Sync>);
+
+impl Clock {
+    fn now(&self) -> SystemTime {
+        (self.0)()
+    }
+}
+
+impl fmt::Debug for Clock {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_tuple("Clock").finish()
+    }
+}
+
+/// Snapshot shared among several owners.
+struct SharedSnapshot(Arc<dyn Snapshot>);
+
+impl Snapshot for SharedSnapshot {
+    fn get(&self, name: &ResolvedAddress, key: &[u8]) -> Option<Vec<u8>> {
+        self.0.get(name, key)
+    }
+
+    fn contains(&self, name: &ResolvedAddress, key: &[u8]) -> bool {
+        self.0.contains(name, key)
+    }
+
+    fn iter(&self, name: &ResolvedAddress, from: &[u8]) -> Iter<'_> {
+        self.0.iter(name, from)
+    }
+
+    fn count_prefix(&self, name: &ResolvedAddress, prefix: &[u8]) -> usize {
+        self.0.count_prefix(name, prefix)
+    }
+}
+
 #[test]
 fn test_create_block_heights() {
     let mut testkit = TestKitBuilder::validator().build();
@@ -991,6 +1862,31 @@ fn test_multiple_spec_of_validators_in_builder() {
     drop(testkit);
 }
 
+#[test]
+fn test_custom_consensus_config_in_builder() {
+    use exonum::blockchain::ConsensusConfigBuilder;
+
+    let consensus_config = ConsensusConfigBuilder::new()
+        .min_propose_timeout(50)
+        .max_propose_timeout(500)
+        .first_round_timeout(5_000)
+        .build();
+    let mut testkit = TestKitBuilder::validator()
+        .with_validators(3)
+        .with_consensus_config(consensus_config)
+        .build();
+    testkit.create_block();
+
+    let actual_config = testkit.consensus_config();
+    assert_eq!(actual_config.min_propose_timeout, 50);
+    assert_eq!(actual_config.max_propose_timeout, 500);
+    assert_eq!(actual_config.first_round_timeout, 5_000);
+    assert_eq!(
+        actual_config.validator_keys,
+        testkit.network().consensus_config().validator_keys
+    );
+}
+
 #[test]
 fn test_stop() {
     let testkit = TestKitBuilder::validator().with_logger().build();
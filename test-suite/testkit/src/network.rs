@@ -19,9 +19,11 @@ use exonum::{
     crypto::{self, Hash, KeyPair, PublicKey},
     helpers::{Height, Round, ValidatorId},
     keys::Keys,
-    messages::{Precommit, Verified},
+    messages::{AnyTx, Precommit, Verified},
 };
 
+use std::time::SystemTime;
+
 // TODO Refactor TestNetwork and TestkitBuilder [ECR-3222]
 
 /// Emulated test network.
@@ -51,10 +53,7 @@ impl TestNetwork {
         let mut nodes = keys
             .into_iter()
             .enumerate()
-            .map(|(n, keys)| TestNode {
-                keys,
-                validator_id: Some(ValidatorId(n as u16)),
-            })
+            .map(|(n, keys)| TestNode::from_keys(ValidatorId(n as u16), keys))
             .collect::<Vec<_>>();
 
         assert!(
@@ -204,14 +203,32 @@ impl TestNode {
         }
     }
 
+    /// Creates a validator with the given id from the specified keys.
+    ///
+    /// This allows to build nodes sharing keys with validators of another testkit.
+    pub fn from_keys(validator_id: ValidatorId, keys: Keys) -> Self {
+        Self {
+            keys,
+            validator_id: Some(validator_id),
+        }
+    }
+
     /// Creates a `Precommit` message signed by this validator.
     pub fn create_precommit(
         &self,
         height: Height,
         block_hash: crypto::Hash,
     ) -> Verified<Precommit> {
-        use std::time::SystemTime;
+        self.create_precommit_at(height, block_hash, SystemTime::now())
+    }
 
+    /// Creates a `Precommit` message with the specified time signed by this validator.
+    pub(crate) fn create_precommit_at(
+        &self,
+        height: Height,
+        block_hash: crypto::Hash,
+        time: SystemTime,
+    ) -> Verified<Precommit> {
         Verified::from_value(
             Precommit::new(
                 self.validator_id
@@ -220,7 +237,7 @@ impl TestNode {
                 Round::first(),
                 Hash::zero(),
                 block_hash,
-                SystemTime::now().into(),
+                time.into(),
             ),
             self.keys.consensus_pk(),
             self.keys.consensus_sk(),
@@ -251,6 +268,13 @@ impl TestNode {
     pub fn consensus_keypair(&self) -> KeyPair {
         self.keys.consensus.clone()
     }
+
+    /// Signs a transaction with the service keypair of the node.
+    ///
+    /// Unsigned transactions can be obtained from the service interface via `TxStub`.
+    pub fn sign(&self, tx: AnyTx) -> Verified<AnyTx> {
+        Verified::from_value(tx, self.keys.service_pk(), self.keys.service_sk())
+    }
 }
 
 impl From<TestNode> for ValidatorKeys {
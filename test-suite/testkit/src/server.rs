@@ -14,10 +14,10 @@
 
 //! Types used by the testkit server.
 //!
-//! The server may be created via [`serve`] method in `TestKitBuilder`. Testkit-specific
-//! server endpoints are documented below. Other endpoints exposed by the server are the same
-//! as for an Exonum node or [`TestKitApi`]; that is, the server exposes HTTP API of Exonum
-//! services and node plugins.
+//! The server may be created via [`serve`] or [`serve_tls`] methods in `TestKitBuilder`.
+//! Testkit-specific server endpoints are documented below. Other endpoints exposed
+//! by the server are the same as for an Exonum node or [`TestKitApi`]; that is,
+//! the server exposes HTTP API of Exonum services and node plugins.
 //!
 //! # HTTP endpoints
 //!
@@ -40,6 +40,68 @@
 //!
 //! [`TestKitStatus`]: struct.TestKitStatus.html
 //!
+//! ## Blockchain height
+//!
+//! | Property    | Value |
+//! |-------------|-------|
+//! | Path        | `/api/testkit/v1/height` |
+//! | Method      | GET   |
+//! | Query type  | - |
+//! | Return type | `Height` |
+//!
+//! Returns the current blockchain height as an integer. This is a lightweight alternative
+//! to the [status](#testkit-status) endpoint, e.g., for polling.
+//!
+//! ## Block with transaction statuses
+//!
+//! | Property    | Value |
+//! |-------------|-------|
+//! | Path        | `/api/testkit/v1/block` |
+//! | Method      | GET   |
+//! | Query type  | [`BlockQuery`] |
+//! | Return type | [`BlockWithStatuses`] |
+//!
+//! Returns the header of the block at the specified height together with hashes
+//! of the block transactions in the order of their execution and the execution status
+//! of each transaction. Returns a `404 Not Found` error if the block does not exist.
+//!
+//! [`BlockQuery`]: https://docs.rs/exonum-explorer/latest/exonum_explorer/api/struct.BlockQuery.html
+//! [`BlockWithStatuses`]: struct.BlockWithStatuses.html
+//!
+//! ## Transaction status
+//!
+//! | Property    | Value |
+//! |-------------|-------|
+//! | Path        | `/api/testkit/v1/transaction` |
+//! | Method      | GET   |
+//! | Query type  | [`TransactionQuery`] |
+//! | Return type | [`TransactionOutcome`] |
+//!
+//! Returns the location of a committed transaction together with its execution error,
+//! if any. Unlike the explorer API, the error is serialized as a flat object with
+//! the `kind`, `code` and `description` fields (see [`ErrorDetails`]), so that clients
+//! do not need to know the full set of error types to handle a service-defined error.
+//! Returns a `404 Not Found` error if the transaction is unknown or is not committed yet.
+//!
+//! [`TransactionQuery`]: https://docs.rs/exonum-explorer/latest/exonum_explorer/api/struct.TransactionQuery.html
+//! [`TransactionOutcome`]: struct.TransactionOutcome.html
+//! [`ErrorDetails`]: struct.ErrorDetails.html
+//!
+//! ## Service instances
+//!
+//! | Property    | Value |
+//! |-------------|-------|
+//! | Path        | `/api/testkit/v1/services` |
+//! | Method      | GET   |
+//! | Query type  | - |
+//! | Return type | `Vec<`[`ServiceInstance`]`>` |
+//!
+//! Returns active service instances in the testkit blockchain. The instances are read
+//! from the dispatcher registry, so the list includes both the instances started
+//! in the genesis block and the ones started afterwards.
+//!
+//! [`ServiceInstance`]: struct.ServiceInstance.html
+//!
 //! ## Create block
 //!
 //! | Property    | Value |
@@ -71,30 +133,143 @@
 //! Acts as a rough [`rollback`] equivalent. The blocks are rolled back up and including the block
 //! at the specified in JSON body `height` value (a positive integer), so that after the request
 //! the blockchain height is equal to `height - 1`. If the specified height is greater than the
-//! blockchain height, the request performs no action.
+//! blockchain height, the request performs no action. Only blocks created via the server
+//! can be rolled back; the request fails if `height` does not exceed the blockchain height
+//! at the server start.
 //!
 //! Returns the latest block from the blockchain on success.
 //!
+//! ## Reset
+//!
+//! | Property    | Value |
+//! |-------------|-------|
+//! | Path        | `/api/testkit/v1/reset` |
+//! | Method      | POST  |
+//! | Body type   | - |
+//! | Return type | `BlockWithTransactions` |
+//!
+//! Rolls back all blocks created via the server, so that the blockchain state (including
+//! the state of services and the transaction pool) is returned to the state at the server
+//! start. This is cheaper than restarting the server between independent test scenarios.
+//! Equivalent to the [roll back](#roll-back) request with the height following
+//! the blockchain height at the server start.
+//!
+//! Returns the latest block at the server start (e.g., the genesis block if the server
+//! was started with an empty blockchain) on success.
+//!
+//! ## Batch
+//!
+//! | Property    | Value |
+//! |-------------|-------|
+//! | Path        | `/api/testkit/v1/batch` |
+//! | Method      | POST  |
+//! | Body type   | `Vec<`[`BatchRequest`]`>` |
+//! | Return type | `Vec<`[`BatchResponse`]`>` |
+//!
+//! Processes several requests to the testkit endpoints in a single HTTP call. Each request
+//! specifies the HTTP method, the endpoint relative to `/api/testkit` (e.g., `v1/status`)
+//! and an optional JSON body. Requests are processed sequentially, and the responses
+//! are returned in the same order as requests. An error in one request does not fail
+//! the whole batch; instead, the corresponding response contains the HTTP status code
+//! and the body of the error.
+//!
+//! Requests are dispatched to the testkit endpoints in the same way as separate HTTP
+//! requests. The body of a `GET` request is encoded into the query string; the body
+//! of other requests is sent as JSON, with an omitted body sent as `null`. Batches
+//! cannot be nested: requests to this endpoint within a batch, as well as requests
+//! to unknown endpoints, result in a `404 Not Found` response.
+//!
+//! [`BatchRequest`]: struct.BatchRequest.html
+//! [`BatchResponse`]: struct.BatchResponse.html
+//!
+//! # WebSocket endpoints
+//!
+//! ## Block subscription
+//!
+//! | Property    | Value |
+//! |-------------|-------|
+//! | Path        | `/api/testkit/v1/blocks/subscribe` |
+//! | Method      | GET (WebSocket handshake) |
+//! | Message type | `Notification::Block` |
+//!
+//! Establishes a WebSocket connection, over which the server pushes a JSON-encoded
+//! [`Notification`] with the block header each time a block is created via the
+//! [create block](#create-block) endpoint. The notification format is the same as for
+//! the block subscription in the explorer service.
+//!
+//! [`Notification`]: https://docs.rs/exonum-explorer/latest/exonum_explorer/api/websocket/enum.Notification.html
 //! [`serve`]: ../struct.TestKitBuilder.html#method.serve
+//! [`serve_tls`]: ../struct.TestKitBuilder.html#method.serve_tls
 //! [`TestKitApi`]: ../struct.TestKitApi.html
 //! [`create_block`]: ../struct.TestKit.html#method.create_block
 //! [`create_block_with_tx_hashes`]: ../struct.TestKit.html#method.create_block_with_tx_hashes
 //! [`rollback`]: ../struct.TestKit.html#method.rollback
 
 use actix::prelude::*;
-use exonum::{blockchain::ConsensusConfig, crypto::Hash, helpers::Height};
-use exonum_api::{self as api, ApiAggregator, ApiBuilder};
-use exonum_explorer::{BlockWithTransactions, BlockchainExplorer};
-use futures::FutureExt;
+use actix_web::{
+    http,
+    test::{self, TestRequest},
+    web::{self, Payload},
+    App,
+};
+use actix_web_actors::ws;
+use exonum::{
+    blockchain::{Block, ConsensusConfig, TxLocation},
+    crypto::Hash,
+    helpers::Height,
+    merkledb::ObjectHash,
+    runtime::{
+        ArtifactId, ErrorKind, ExecutionError, ExecutionStatus, InstanceId, InstanceStatus,
+        SnapshotExt,
+    },
+};
+use exonum_api::{
+    self as api,
+    backends::actix::{HttpRequest, RawHandler, RequestHandler},
+    ApiAggregator, ApiBackend, ApiBuilder, ApiScope,
+};
+use exonum_explorer::{
+    api::{websocket::Notification, BlockQuery, TransactionQuery},
+    BlockWithTransactions, BlockchainExplorer, TransactionInfo,
+};
+use futures::{future, FutureExt};
 use serde::{Deserialize, Serialize};
 use tokio::task::LocalSet;
 
+use std::{collections::HashMap, fmt, sync::Arc};
+
 use crate::{TestKit, TestNode};
 
-#[derive(Debug)]
-pub(crate) struct TestKitActor(TestKit);
+pub(crate) struct TestKitActor {
+    testkit: TestKit,
+    /// Blockchain height at the actor start. Blocks up to this height have no checkpoints
+    /// and cannot be rolled back.
+    initial_height: Height,
+    block_subscribers: HashMap<u64, Recipient<BlockNotification>>,
+    next_subscriber_id: u64,
+}
+
+impl fmt::Debug for TestKitActor {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("TestKitActor")
+            .field("testkit", &self.testkit)
+            .field("initial_height", &self.initial_height)
+            .field("block_subscribers", &self.block_subscribers.len())
+            .finish()
+    }
+}
 
 impl TestKitActor {
+    fn new(testkit: TestKit) -> Self {
+        Self {
+            initial_height: testkit.height(),
+            testkit,
+            block_subscribers: HashMap::new(),
+            next_subscriber_id: 0,
+        }
+    }
+
     pub(crate) async fn spawn(mut testkit: TestKit) -> (ApiAggregator, LocalSet) {
         let mut api_aggregator = testkit.update_aggregator();
 
@@ -102,7 +277,9 @@ impl TestKitActor {
         // `System` should be spawn before the testkit actor is added to it.
         local_set.spawn_local(System::run_in_tokio("testkit", &local_set));
         // Add the testkit actor to the system and retrieve a handle to it.
-        let testkit = local_set.run_until(async { Self(testkit).start() }).await;
+        let testkit = local_set
+            .run_until(async { Self::new(testkit).start() })
+            .await;
 
         api_aggregator.insert("testkit", Self::api(testkit));
         (api_aggregator, local_set)
@@ -117,16 +294,68 @@ impl TestKitActor {
             addr_.send(GetStatus).map(flatten_err)
         });
 
+        let addr_ = addr.clone();
+        api_scope.endpoint("v1/height", move |()| {
+            addr_.send(GetHeight).map(flatten_err)
+        });
+
+        let addr_ = addr.clone();
+        api_scope.endpoint("v1/block", move |query: BlockQuery| {
+            addr_.send(GetBlock(query.height)).map(flatten_err)
+        });
+
+        let addr_ = addr.clone();
+        api_scope.endpoint("v1/transaction", move |query: TransactionQuery| {
+            addr_.send(GetTransaction(query.hash)).map(flatten_err)
+        });
+
+        let addr_ = addr.clone();
+        api_scope.endpoint("v1/services", move |()| {
+            addr_.send(GetServices).map(flatten_err)
+        });
+
         let addr_ = addr.clone();
         api_scope.endpoint_mut("v1/blocks/rollback", move |height| {
             addr_.send(RollBack(height)).map(flatten_err)
         });
 
+        let addr_ = addr.clone();
+        api_scope.endpoint_mut("v1/reset", move |()| addr_.send(Reset).map(flatten_err));
+
+        let addr_ = addr.clone();
         api_scope.endpoint_mut("v1/blocks/create", move |query: CreateBlock| {
-            addr.send(query).map(flatten_err)
+            addr_.send(query).map(flatten_err)
+        });
+
+        let ws_handler = move |request: HttpRequest, stream: Payload| {
+            let session = BlockSubscription::new(addr.clone());
+            future::ready(ws::start(session, &request, stream)).boxed_local()
+        };
+        api_scope.web_backend().raw_handler(RequestHandler {
+            name: "v1/blocks/subscribe".to_owned(),
+            method: http::Method::GET,
+            inner: Arc::new(ws_handler) as Arc<RawHandler>,
+        });
+
+        // Batched requests are dispatched to all endpoints declared above.
+        let endpoints = api_scope.clone();
+        api_scope.endpoint_mut("v1/batch", move |requests: Vec<BatchRequest>| {
+            process_batch(endpoints.clone(), requests)
         });
         builder
     }
+
+    /// Sends a notification about the created block to all block subscribers,
+    /// removing subscribers which have disconnected.
+    fn notify_subscribers(&mut self, block: &Block) {
+        let notification = Notification::Block(block.to_owned());
+        let serialized = serde_json::to_string(&notification).unwrap();
+        self.block_subscribers.retain(|_, recipient| {
+            recipient
+                .do_send(BlockNotification(serialized.clone()))
+                .is_ok()
+        });
+    }
 }
 
 impl Actor for TestKitActor {
@@ -165,13 +394,214 @@ impl Handler<GetStatus> for TestKitActor {
 
     fn handle(&mut self, _msg: GetStatus, _ctx: &mut Self::Context) -> Self::Result {
         Ok(TestKitStatus {
-            height: self.0.height(),
-            configuration: self.0.consensus_config(),
-            nodes: self.0.network.nodes().to_vec(),
+            height: self.testkit.height(),
+            configuration: self.testkit.consensus_config(),
+            nodes: self.testkit.network.nodes().to_vec(),
         })
     }
 }
 
+#[derive(Debug)]
+struct GetHeight;
+
+impl Message for GetHeight {
+    type Result = api::Result<Height>;
+}
+
+impl Handler<GetHeight> for TestKitActor {
+    type Result = api::Result<Height>;
+
+    fn handle(&mut self, _msg: GetHeight, _ctx: &mut Self::Context) -> Self::Result {
+        Ok(self.testkit.height())
+    }
+}
+
+#[derive(Debug)]
+struct GetBlock(Height);
+
+impl Message for GetBlock {
+    type Result = api::Result<BlockWithStatuses>;
+}
+
+/// Block header together with the transactions of the block and their execution statuses,
+/// returned by the corresponding API endpoint.
+#[derive(Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct BlockWithStatuses {
+    /// Block header.
+    pub header: Block,
+    /// Transactions of the block in the order of their execution.
+    pub transactions: Vec<TransactionStatus>,
+}
+
+/// Hash and execution status of a committed transaction.
+#[derive(Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct TransactionStatus {
+    /// Transaction hash.
+    pub tx_hash: Hash,
+    /// Result of the transaction execution.
+    pub status: ExecutionStatus,
+}
+
+impl Handler<GetBlock> for TestKitActor {
+    type Result = api::Result<BlockWithStatuses>;
+
+    fn handle(&mut self, GetBlock(height): GetBlock, _ctx: &mut Self::Context) -> Self::Result {
+        let snapshot = self.testkit.snapshot();
+        let explorer = BlockchainExplorer::new(snapshot.as_ref());
+        let block = explorer.block_with_txs(height).ok_or_else(|| {
+            api::Error::not_found()
+                .title("Block not found")
+                .detail(format!("Block at height {} does not exist", height))
+        })?;
+
+        let transactions = block
+            .transactions
+            .iter()
+            .map(|tx| TransactionStatus {
+                tx_hash: tx.message().object_hash(),
+                status: ExecutionStatus(tx.status().map_err(Clone::clone)),
+            })
+            .collect();
+        Ok(BlockWithStatuses {
+            header: block.header,
+            transactions,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct GetTransaction(Hash);
+
+impl Message for GetTransaction {
+    type Result = api::Result<TransactionOutcome>;
+}
+
+/// Location and execution outcome of a committed transaction, returned by the corresponding
+/// API endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct TransactionOutcome {
+    /// Transaction hash.
+    pub tx_hash: Hash,
+    /// Location of the transaction in the blockchain.
+    pub location: TxLocation,
+    /// Execution error, or `None` if the transaction was executed successfully.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorDetails>,
+}
+
+/// Source of an execution error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ErrorSource {
+    /// Error raised outside of the service code, e.g., by the dispatcher when the called
+    /// service or method does not exist, or by the runtime.
+    Dispatcher,
+    /// Error raised by the service code. Unexpected errors (i.e., panics) in the service code
+    /// are attributed to the service as well, but have no error code.
+    Service,
+}
+
+/// Flat representation of an `ExecutionError` returned by the testkit API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ErrorDetails {
+    /// Source of the error.
+    pub kind: ErrorSource,
+    /// Error code. The meaning of the code depends on the error source; for service errors,
+    /// it is defined by the service. `None` for unexpected errors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<u8>,
+    /// Human-readable error description.
+    pub description: String,
+}
+
+impl From<&ExecutionError> for ErrorDetails {
+    fn from(err: &ExecutionError) -> Self {
+        let (kind, code) = match err.kind() {
+            ErrorKind::Service { code } => (ErrorSource::Service, Some(code)),
+            ErrorKind::Unexpected => (ErrorSource::Service, None),
+            ErrorKind::Common { code } | ErrorKind::Core { code } | ErrorKind::Runtime { code } => {
+                (ErrorSource::Dispatcher, Some(code))
+            }
+            _ => (ErrorSource::Dispatcher, None),
+        };
+        Self {
+            kind,
+            code,
+            description: err.description().to_owned(),
+        }
+    }
+}
+
+impl Handler<GetTransaction> for TestKitActor {
+    type Result = api::Result<TransactionOutcome>;
+
+    fn handle(
+        &mut self,
+        GetTransaction(hash): GetTransaction,
+        _ctx: &mut Self::Context,
+    ) -> Self::Result {
+        let snapshot = self.testkit.snapshot();
+        let explorer = BlockchainExplorer::new(snapshot.as_ref());
+        match explorer.transaction(&hash) {
+            Some(TransactionInfo::Committed(tx)) => Ok(TransactionOutcome {
+                tx_hash: hash,
+                location: *tx.location(),
+                error: tx.status().err().map(ErrorDetails::from),
+            }),
+            Some(TransactionInfo::InPool { .. }) => Err(api::Error::not_found()
+                .title("Transaction not committed")
+                .detail(format!("Transaction {:?} is in the pool", hash))),
+            _ => Err(api::Error::not_found()
+                .title("Transaction not found")
+                .detail(format!("Transaction {:?} is unknown", hash))),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct GetServices;
+
+impl Message for GetServices {
+    type Result = api::Result<Vec<ServiceInstance>>;
+}
+
+/// Information about a service instance, returned by the corresponding API endpoint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ServiceInstance {
+    /// Numeric identifier of the instance.
+    pub instance_id: InstanceId,
+    /// Name of the instance.
+    pub name: String,
+    /// Artifact from which the instance is created.
+    pub artifact: ArtifactId,
+}
+
+impl Handler<GetServices> for TestKitActor {
+    type Result = api::Result<Vec<ServiceInstance>>;
+
+    fn handle(&mut self, _msg: GetServices, _ctx: &mut Self::Context) -> Self::Result {
+        let snapshot = self.testkit.snapshot();
+        let services = snapshot
+            .for_dispatcher()
+            .service_instances()
+            .values()
+            .filter(|state| state.status == Some(InstanceStatus::Active))
+            .map(|state| ServiceInstance {
+                instance_id: state.spec.id,
+                name: state.spec.name,
+                artifact: state.spec.artifact,
+            })
+            .collect();
+        Ok(services)
+    }
+}
+
 /// Block creation parameters for the testkit server.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -208,7 +638,7 @@ impl Handler<CreateBlock> for TestKitActor {
 
     fn handle(&mut self, msg: CreateBlock, _ctx: &mut Self::Context) -> Self::Result {
         let block_info = if let Some(tx_hashes) = msg.tx_hashes {
-            let maybe_missing_tx = tx_hashes.iter().find(|h| !self.0.is_tx_in_pool(h));
+            let maybe_missing_tx = tx_hashes.iter().find(|h| !self.testkit.is_tx_in_pool(h));
             if let Some(missing_tx) = maybe_missing_tx {
                 return Err(api::Error::bad_request()
                     .title("Creating block failed")
@@ -219,12 +649,13 @@ impl Handler<CreateBlock> for TestKitActor {
             }
 
             // NB: checkpoints must correspond 1-to-1 to blocks.
-            self.0.checkpoint();
-            self.0.create_block_with_tx_hashes(&tx_hashes)
+            self.testkit.checkpoint();
+            self.testkit.create_block_with_tx_hashes(&tx_hashes)
         } else {
-            self.0.checkpoint();
-            self.0.create_block()
+            self.testkit.checkpoint();
+            self.testkit.create_block()
         };
+        self.notify_subscribers(&block_info.header);
         Ok(block_info)
     }
 }
@@ -243,17 +674,256 @@ impl Handler<RollBack> for TestKitActor {
         if height == Height(0) {
             return Err(api::Error::bad_request().title("Cannot rollback past genesis block"));
         }
+        if height <= self.initial_height {
+            return Err(api::Error::bad_request()
+                .title("Cannot rollback blocks created before the server start")
+                .detail(format!(
+                    "Blocks up to height {} were created before the server start",
+                    self.initial_height
+                )));
+        }
 
-        if self.0.height() >= height {
-            let rollback_blocks = (self.0.height().0 - height.0 + 1) as usize;
+        if self.testkit.height() >= height {
+            let rollback_blocks = (self.testkit.height().0 - height.0 + 1) as usize;
             for _ in 0..rollback_blocks {
-                self.0.rollback();
+                self.testkit.rollback();
             }
         }
 
-        let snapshot = self.0.snapshot();
+        let snapshot = self.testkit.snapshot();
         let explorer = BlockchainExplorer::new(snapshot.as_ref());
-        Ok(explorer.block_with_txs(self.0.height()))
+        Ok(explorer.block_with_txs(self.testkit.height()))
+    }
+}
+
+#[derive(Debug)]
+struct Reset;
+
+impl Message for Reset {
+    type Result = api::Result<BlockWithTransactions>;
+}
+
+impl Handler<Reset> for TestKitActor {
+    type Result = api::Result<BlockWithTransactions>;
+
+    fn handle(&mut self, _msg: Reset, ctx: &mut Self::Context) -> Self::Result {
+        let height = self.initial_height.next();
+        let block = Handler::<RollBack>::handle(self, RollBack(height), ctx)?;
+        Ok(block.expect("Initial block is missing"))
+    }
+}
+
+/// Request within a batch processed by the testkit server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct BatchRequest {
+    /// HTTP method of the request, such as `GET` or `POST`.
+    pub method: String,
+    /// Testkit endpoint relative to `/api/testkit`, such as `v1/status`.
+    pub endpoint: String,
+    /// JSON body of the request. May be omitted for `GET` requests.
+    #[serde(default)]
+    pub body: serde_json::Value,
+}
+
+impl BatchRequest {
+    /// Creates a `GET` request to the specified endpoint.
+    pub fn get(endpoint: impl Into<String>) -> Self {
+        Self {
+            method: "GET".to_owned(),
+            endpoint: endpoint.into(),
+            body: serde_json::Value::Null,
+        }
+    }
+
+    /// Creates a `POST` request to the specified endpoint with the specified body.
+    pub fn post<T: Serialize>(endpoint: impl Into<String>, body: &T) -> Self {
+        Self {
+            method: "POST".to_owned(),
+            endpoint: endpoint.into(),
+            body: serde_json::to_value(body).expect("Cannot serialize request body"),
+        }
+    }
+
+    /// Converts this request into an HTTP request to the testkit endpoints.
+    /// For `GET` requests, the body is encoded into the query string.
+    fn to_http_request(&self) -> api::Result<TestRequest> {
+        let method =
+            http::Method::from_bytes(self.method.to_uppercase().as_bytes()).map_err(|e| {
+                api::Error::bad_request()
+                    .title("Invalid request method")
+                    .detail(e.to_string())
+            })?;
+        let mut uri = format!("/{}", self.endpoint.trim_start_matches('/'));
+
+        let request = if method == http::Method::GET {
+            if !self.body.is_null() {
+                let query = serde_urlencoded::to_string(&self.body).map_err(|e| {
+                    api::Error::bad_request()
+                        .title("Invalid request body")
+                        .detail(e.to_string())
+                })?;
+                uri = format!("{}?{}", uri, query);
+            }
+            TestRequest::get()
+        } else {
+            TestRequest::default().method(method).set_json(&self.body)
+        };
+        Ok(request.uri(&uri))
+    }
+}
+
+/// Response to a request within a batch processed by the testkit server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct BatchResponse {
+    /// HTTP status code of the response.
+    pub status: u16,
+    /// JSON body of the response. For failed requests, the body has the same format
+    /// as the body of an erroneous server response.
+    pub body: serde_json::Value,
+}
+
+impl BatchResponse {
+    fn new<T: Serialize>(result: api::Result<T>) -> Self {
+        let (status, body) = match result {
+            Ok(value) => (api::HttpStatusCode::OK, serde_json::to_value(value)),
+            Err(err) => (err.http_code, serde_json::to_value(err.body)),
+        };
+        Self {
+            status: status.as_u16(),
+            body: body.expect("Cannot serialize response body"),
+        }
+    }
+}
+
+/// Processes requests from a batch sequentially by dispatching them to the endpoints
+/// of the `scope` in the same way as requests sent over HTTP.
+async fn process_batch(
+    mut scope: ApiScope,
+    requests: Vec<BatchRequest>,
+) -> api::Result<Vec<BatchResponse>> {
+    let endpoints = scope.web_backend().wire(web::scope(""));
+    let mut app = test::init_service(App::new().service(endpoints)).await;
+
+    let mut responses = Vec::with_capacity(requests.len());
+    for request in &requests {
+        let request = match request.to_http_request() {
+            Ok(request) => request.to_request(),
+            Err(err) => {
+                responses.push(BatchResponse::new::<()>(Err(err)));
+                continue;
+            }
+        };
+        let response = test::call_service(&mut app, request).await;
+        let status = response.status().as_u16();
+        let body = test::read_body(response).await;
+        let body = if body.is_empty() {
+            serde_json::Value::Null
+        } else {
+            serde_json::from_slice(&body).unwrap_or_else(|_| {
+                serde_json::Value::String(String::from_utf8_lossy(&body).into_owned())
+            })
+        };
+        responses.push(BatchResponse { status, body });
+    }
+    Ok(responses)
+}
+
+/// Serialized notification about a created block, which is sent to a WebSocket session.
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+struct BlockNotification(String);
+
+struct SubscribeToBlocks(Recipient<BlockNotification>);
+
+impl fmt::Debug for SubscribeToBlocks {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_tuple("SubscribeToBlocks").finish()
+    }
+}
+
+impl Message for SubscribeToBlocks {
+    type Result = u64;
+}
+
+impl Handler<SubscribeToBlocks> for TestKitActor {
+    type Result = u64;
+
+    fn handle(&mut self, msg: SubscribeToBlocks, _ctx: &mut Self::Context) -> Self::Result {
+        let id = self.next_subscriber_id;
+        self.next_subscriber_id += 1;
+        self.block_subscribers.insert(id, msg.0);
+        id
+    }
+}
+
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+struct UnsubscribeFromBlocks(u64);
+
+impl Handler<UnsubscribeFromBlocks> for TestKitActor {
+    type Result = ();
+
+    fn handle(&mut self, msg: UnsubscribeFromBlocks, _ctx: &mut Self::Context) {
+        self.block_subscribers.remove(&msg.0);
+    }
+}
+
+/// WebSocket session subscribed to blocks created by the testkit.
+#[derive(Debug)]
+struct BlockSubscription {
+    id: Option<u64>,
+    testkit: Addr<TestKitActor>,
+}
+
+impl BlockSubscription {
+    fn new(testkit: Addr<TestKitActor>) -> Self {
+        Self { id: None, testkit }
+    }
+}
+
+impl Actor for BlockSubscription {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        let recipient = ctx.address().recipient();
+        self.testkit
+            .send(SubscribeToBlocks(recipient))
+            .into_actor(self)
+            .then(|response, actor, context| {
+                match response {
+                    Ok(id) => actor.id = Some(id),
+                    Err(_) => context.stop(),
+                }
+                actix::fut::ready(())
+            })
+            .wait(ctx);
+    }
+
+    fn stopping(&mut self, _ctx: &mut Self::Context) -> Running {
+        if let Some(id) = self.id {
+            self.testkit.do_send(UnsubscribeFromBlocks(id));
+        }
+        Running::Stop
+    }
+}
+
+impl Handler<BlockNotification> for BlockSubscription {
+    type Result = ();
+
+    fn handle(&mut self, msg: BlockNotification, ctx: &mut Self::Context) {
+        ctx.text(msg.0);
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for BlockSubscription {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        match msg {
+            Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
+            Ok(ws::Message::Close(_)) | Err(_) => ctx.stop(),
+            Ok(_) => {}
+        }
     }
 }
 
@@ -263,22 +933,32 @@ mod tests {
         crypto::{gen_keypair, Hash},
         helpers::{Height, ValidatorId},
         messages::{AnyTx, Verified},
-        runtime::{ExecutionContext, ExecutionError},
+        runtime::{ExecutionContext, ExecutionError, ExecutionFail},
+    };
+    use exonum_derive::{exonum_interface, ExecutionFail, ServiceDispatcher, ServiceFactory};
+    use exonum_explorer::{
+        api::{websocket::Notification, BlockQuery},
+        BlockWithTransactions,
     };
-    use exonum_derive::{exonum_interface, ServiceDispatcher, ServiceFactory};
-    use exonum_explorer::BlockWithTransactions;
     use exonum_merkledb::ObjectHash;
-    use exonum_rust_runtime::{api, spec::Spec, Service};
+    use exonum_rust_runtime::{api, spec::Spec, Service, ServiceFactory};
     use pretty_assertions::assert_eq;
     use tokio::time::delay_for;
+    use websocket::{ClientBuilder, OwnedMessage};
 
     use std::time::Duration;
 
-    use super::{CreateBlock, LocalSet, TestKitActor, TestKitStatus};
+    use super::{
+        BatchRequest, BatchResponse, BlockWithStatuses, CreateBlock, ErrorDetails, ErrorSource,
+        LocalSet, ServiceInstance, TestKitActor, TestKitStatus, TransactionOutcome,
+        TransactionQuery,
+    };
     use crate::{TestKitApi, TestKitBuilder};
 
     const TIMESTAMP_SERVICE_ID: u32 = 2;
     const TIMESTAMP_SERVICE_NAME: &str = "sample";
+    const OTHER_SERVICE_ID: u32 = 3;
+    const OTHER_SERVICE_NAME: &str = "other-sample";
 
     fn timestamp(s: &str) -> Verified<AnyTx> {
         gen_keypair().timestamp(TIMESTAMP_SERVICE_ID, s.to_owned())
@@ -295,11 +975,21 @@ mod tests {
         fn timestamp(&self, ctx: Ctx, arg: String) -> Self::Output;
     }
 
+    #[derive(Debug, ExecutionFail)]
+    enum SampleError {
+        /// Timestamp cannot be empty.
+        EmptyTimestamp = 7,
+    }
+
     impl SampleInterface<ExecutionContext<'_>> for SampleService {
         type Output = Result<(), ExecutionError>;
 
-        fn timestamp(&self, _ctx: ExecutionContext<'_>, _arg: String) -> Self::Output {
-            Ok(())
+        fn timestamp(&self, _ctx: ExecutionContext<'_>, arg: String) -> Self::Output {
+            if arg.is_empty() {
+                Err(SampleError::EmptyTimestamp.into())
+            } else {
+                Ok(())
+            }
         }
     }
 
@@ -309,11 +999,11 @@ mod tests {
     /// of empty blocks in the testkit blockchain.
     async fn init_handler(height: Height) -> (TestKitApi, LocalSet) {
         let mut testkit = TestKitBuilder::validator()
-            .with(Spec::new(SampleService).with_instance(
-                TIMESTAMP_SERVICE_ID,
-                TIMESTAMP_SERVICE_NAME,
-                (),
-            ))
+            .with(
+                Spec::new(SampleService)
+                    .with_instance(TIMESTAMP_SERVICE_ID, TIMESTAMP_SERVICE_NAME, ())
+                    .with_instance(OTHER_SERVICE_ID, OTHER_SERVICE_NAME, ()),
+            )
             .build();
         testkit.create_blocks_until(height);
 
@@ -349,6 +1039,27 @@ mod tests {
         local_set.run_until(test_status(api)).await;
     }
 
+    async fn test_height(api: TestKitApi) {
+        let height: Height = api.private("api/testkit").get("v1/height").await.unwrap();
+        assert_eq!(height, Height(3));
+
+        api.private("api/testkit")
+            .query(&CreateBlock::with_all_transactions())
+            .post::<BlockWithTransactions>("v1/blocks/create")
+            .await
+            .unwrap();
+        let height: Height = api.private("api/testkit").get("v1/height").await.unwrap();
+        let status: TestKitStatus = api.private("api/testkit").get("v1/status").await.unwrap();
+        assert_eq!(height, Height(4));
+        assert_eq!(height, status.height);
+    }
+
+    #[tokio::test]
+    async fn height() {
+        let (api, local_set) = init_handler(Height(3)).await;
+        local_set.run_until(test_height(api)).await;
+    }
+
     async fn test_create_block_with_empty_body(api: TestKitApi) {
         let tx = timestamp("foo");
         api.send(tx.clone()).await;
@@ -512,6 +1223,81 @@ mod tests {
         local_set.run_until(test_rollback_normal(api)).await;
     }
 
+    async fn test_reset(api: TestKitApi, initial_height: Height) {
+        let tx = timestamp("foo");
+        api.send(tx.clone()).await;
+        sleep().await;
+        for i in 0..3 {
+            let block: BlockWithTransactions = api
+                .private("api/testkit")
+                .query(&CreateBlock { tx_hashes: None })
+                .post("v1/blocks/create")
+                .await
+                .unwrap();
+            assert_eq!(block.height(), Height(initial_height.0 + i + 1));
+        }
+
+        let block: BlockWithTransactions = api
+            .private("api/testkit")
+            .query(&())
+            .post("v1/reset")
+            .await
+            .unwrap();
+        assert_eq!(block.header.height, initial_height);
+        let status: TestKitStatus = api.private("api/testkit").get("v1/status").await.unwrap();
+        assert_eq!(status.height, initial_height);
+
+        // The committed transaction is forgotten, so it can be included into a block again.
+        api.send(tx.clone()).await;
+        sleep().await;
+        let block: BlockWithTransactions = api
+            .private("api/testkit")
+            .query(&CreateBlock { tx_hashes: None })
+            .post("v1/blocks/create")
+            .await
+            .unwrap();
+        assert_eq!(block.header.height, initial_height.next());
+        assert_eq!(block.transactions.len(), 1);
+        assert_eq!(block.transactions[0].message(), &tx);
+    }
+
+    #[tokio::test]
+    async fn reset() {
+        let (api, local_set) = init_handler(Height(0)).await;
+        local_set.run_until(test_reset(api, Height(0))).await;
+    }
+
+    #[tokio::test]
+    async fn reset_with_existing_blocks() {
+        let (api, local_set) = init_handler(Height(3)).await;
+        local_set.run_until(test_reset(api, Height(3))).await;
+    }
+
+    async fn test_rollback_before_server_start(api: TestKitApi) {
+        let err = api
+            .private("api/testkit")
+            .query(&Height(3))
+            .post::<BlockWithTransactions>("v1/blocks/rollback")
+            .await
+            .unwrap_err();
+        assert_eq!(err.http_code, api::HttpStatusCode::BAD_REQUEST);
+        assert_eq!(
+            err.body.title,
+            "Cannot rollback blocks created before the server start"
+        );
+
+        let status: TestKitStatus = api.private("api/testkit").get("v1/status").await.unwrap();
+        assert_eq!(status.height, Height(3));
+    }
+
+    #[tokio::test]
+    async fn rollback_before_server_start() {
+        let (api, local_set) = init_handler(Height(3)).await;
+        local_set
+            .run_until(test_rollback_before_server_start(api))
+            .await;
+    }
+
     async fn test_rollback_past_genesis(api: TestKitApi) {
         let err = api
             .private("api/testkit")
@@ -529,4 +1315,266 @@ mod tests {
         let (api, local_set) = init_handler(Height(4)).await;
         local_set.run_until(test_rollback_past_genesis(api)).await;
     }
+
+    async fn test_get_block(api: TestKitApi) {
+        let tx_foo = timestamp("foo");
+        let tx_bar = timestamp("bar");
+        api.send(tx_foo.clone()).await;
+        api.send(tx_bar.clone()).await;
+        sleep().await;
+
+        let created_block: BlockWithTransactions = api
+            .private("api/testkit")
+            .query(&CreateBlock::with_all_transactions())
+            .post("v1/blocks/create")
+            .await
+            .unwrap();
+        assert_eq!(created_block.transactions.len(), 2);
+
+        let block: BlockWithStatuses = api
+            .private("api/testkit")
+            .query(&BlockQuery::new(Height(1)))
+            .get("v1/block")
+            .await
+            .unwrap();
+        assert_eq!(block.header, created_block.header);
+        let tx_hashes: Vec<_> = block.transactions.iter().map(|tx| tx.tx_hash).collect();
+        let expected_hashes: Vec<_> = created_block
+            .transactions
+            .iter()
+            .map(|tx| tx.message().object_hash())
+            .collect();
+        assert_eq!(tx_hashes, expected_hashes);
+        assert!(tx_hashes.contains(&tx_foo.object_hash()));
+        assert!(tx_hashes.contains(&tx_bar.object_hash()));
+        for tx in &block.transactions {
+            tx.status.0.as_ref().unwrap();
+        }
+
+        let err = api
+            .private("api/testkit")
+            .query(&BlockQuery::new(Height(2)))
+            .get::<BlockWithStatuses>("v1/block")
+            .await
+            .unwrap_err();
+        assert_eq!(err.http_code, api::HttpStatusCode::NOT_FOUND);
+        assert_eq!(err.body.title, "Block not found");
+    }
+
+    #[tokio::test]
+    async fn get_block() {
+        let (api, local_set) = init_handler(Height(0)).await;
+        local_set.run_until(test_get_block(api)).await;
+    }
+
+    async fn test_get_transaction(api: TestKitApi) {
+        let tx_ok = timestamp("foo");
+        let tx_err = timestamp("");
+        api.send(tx_ok.clone()).await;
+        api.send(tx_err.clone()).await;
+        sleep().await;
+
+        let err = api
+            .private("api/testkit")
+            .query(&TransactionQuery::new(tx_err.object_hash()))
+            .get::<TransactionOutcome>("v1/transaction")
+            .await
+            .unwrap_err();
+        assert_eq!(err.http_code, api::HttpStatusCode::NOT_FOUND);
+        assert_eq!(err.body.title, "Transaction not committed");
+
+        api.private("api/testkit")
+            .query(&CreateBlock::with_all_transactions())
+            .post::<BlockWithTransactions>("v1/blocks/create")
+            .await
+            .unwrap();
+
+        let outcome: TransactionOutcome = api
+            .private("api/testkit")
+            .query(&TransactionQuery::new(tx_ok.object_hash()))
+            .get("v1/transaction")
+            .await
+            .unwrap();
+        assert_eq!(outcome.tx_hash, tx_ok.object_hash());
+        assert_eq!(outcome.location.block_height(), Height(1));
+        assert_eq!(outcome.error, None);
+
+        let outcome: serde_json::Value = api
+            .private("api/testkit")
+            .query(&TransactionQuery::new(tx_err.object_hash()))
+            .get("v1/transaction")
+            .await
+            .unwrap();
+        let description = SampleError::EmptyTimestamp.description();
+        assert_eq!(
+            outcome["error"],
+            serde_json::json!({
+                "kind": "service",
+                "code": 7,
+                "description": description,
+            })
+        );
+        let outcome: TransactionOutcome = serde_json::from_value(outcome).unwrap();
+        assert_eq!(
+            outcome.error,
+            Some(ErrorDetails {
+                kind: ErrorSource::Service,
+                code: Some(7),
+                description,
+            })
+        );
+
+        let err = api
+            .private("api/testkit")
+            .query(&TransactionQuery::new(Hash::zero()))
+            .get::<TransactionOutcome>("v1/transaction")
+            .await
+            .unwrap_err();
+        assert_eq!(err.http_code, api::HttpStatusCode::NOT_FOUND);
+        assert_eq!(err.body.title, "Transaction not found");
+    }
+
+    #[tokio::test]
+    async fn get_transaction() {
+        let (api, local_set) = init_handler(Height(0)).await;
+        local_set.run_until(test_get_transaction(api)).await;
+    }
+
+    async fn test_services(api: TestKitApi) {
+        let mut services: Vec<ServiceInstance> =
+            api.private("api/testkit").get("v1/services").await.unwrap();
+        services.sort_by_key(|service| service.instance_id);
+
+        let artifact = SampleService.artifact_id();
+        assert_eq!(
+            services,
+            vec![
+                ServiceInstance {
+                    instance_id: TIMESTAMP_SERVICE_ID,
+                    name: TIMESTAMP_SERVICE_NAME.to_owned(),
+                    artifact: artifact.clone(),
+                },
+                ServiceInstance {
+                    instance_id: OTHER_SERVICE_ID,
+                    name: OTHER_SERVICE_NAME.to_owned(),
+                    artifact,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn services() {
+        let (api, local_set) = init_handler(Height(0)).await;
+        local_set.run_until(test_services(api)).await;
+    }
+
+    async fn test_batch(api: TestKitApi) {
+        let tx = timestamp("foo");
+        api.send(tx.clone()).await;
+        sleep().await;
+
+        let requests = vec![
+            BatchRequest::get("v1/status"),
+            BatchRequest::post("v1/blocks/create", &CreateBlock::with_all_transactions()),
+            BatchRequest::post(
+                "v1/blocks/create",
+                &CreateBlock::with_tx_hashes(vec![Hash::zero()]),
+            ),
+            BatchRequest::get("v1/unknown"),
+            BatchRequest::get("v1/status"),
+            BatchRequest {
+                body: serde_json::json!({ "height": 1 }),
+                ..BatchRequest::get("v1/block")
+            },
+            BatchRequest::post("v1/batch", &Vec::<BatchRequest>::new()),
+        ];
+        let responses: Vec<BatchResponse> = api
+            .private("api/testkit")
+            .query(&requests)
+            .post("v1/batch")
+            .await
+            .unwrap();
+        assert_eq!(responses.len(), requests.len());
+
+        assert_eq!(responses[0].status, 200);
+        let status: TestKitStatus = serde_json::from_value(responses[0].body.clone()).unwrap();
+        assert_eq!(status.height, Height(0));
+
+        assert_eq!(responses[1].status, 200);
+        let block_info: BlockWithTransactions =
+            serde_json::from_value(responses[1].body.clone()).unwrap();
+        assert_eq!(block_info.header.height, Height(1));
+        assert_eq!(block_info.transactions.len(), 1);
+        assert_eq!(block_info.transactions[0].message(), &tx);
+
+        // Errors in sub-requests do not fail the whole batch.
+        assert_eq!(responses[2].status, 400);
+        assert_eq!(responses[2].body["title"], "Creating block failed");
+        assert_eq!(responses[3].status, 404);
+
+        // Requests are processed in order.
+        assert_eq!(responses[4].status, 200);
+        let status: TestKitStatus = serde_json::from_value(responses[4].body.clone()).unwrap();
+        assert_eq!(status.height, Height(1));
+        // Request bodies of `GET` requests are passed as query parameters.
+        assert_eq!(responses[5].status, 200);
+        let block: BlockWithStatuses = serde_json::from_value(responses[5].body.clone()).unwrap();
+        assert_eq!(block.header.height, Height(1));
+        assert_eq!(block.transactions.len(), 1);
+        // Batches cannot be nested.
+        assert_eq!(responses[6].status, 404);
+    }
+
+    #[tokio::test]
+    async fn batch() {
+        let (api, local_set) = init_handler(Height(0)).await;
+        local_set.run_until(test_batch(api)).await;
+    }
+
+    async fn test_blocks_subscription(api: TestKitApi) {
+        let url = api
+            .private_url("api/testkit/v1/blocks/subscribe")
+            .replace("http://", "ws://");
+        let mut client = ClientBuilder::new(&url)
+            .unwrap()
+            .connect_insecure()
+            .expect("Cannot connect to WebSocket endpoint");
+        client
+            .stream_ref()
+            .set_read_timeout(Some(Duration::from_secs(1)))
+            .unwrap();
+        // Give the session time to subscribe.
+        sleep().await;
+
+        let mut blocks = vec![];
+        for _ in 0..2 {
+            let block: BlockWithTransactions = api
+                .private("api/testkit")
+                .query(&CreateBlock { tx_hashes: None })
+                .post("v1/blocks/create")
+                .await
+                .unwrap();
+            blocks.push(block.header);
+        }
+
+        for expected_block in blocks {
+            let message = client.recv_message().expect("Cannot receive notification");
+            let notification: Notification = match message {
+                OwnedMessage::Text(ref text) => serde_json::from_str(text).unwrap(),
+                other => panic!("Unexpected WebSocket message: {:?}", other),
+            };
+            match notification {
+                Notification::Block(block) => assert_eq!(block, expected_block),
+                other => panic!("Unexpected notification: {:?}", other),
+            }
+        }
+        client.shutdown().ok();
+    }
+
+    #[tokio::test]
+    async fn blocks_subscription() {
+        let (api, local_set) = init_handler(Height(0)).await;
+        local_set.run_until(test_blocks_subscription(api)).await;
+    }
 }
@@ -13,21 +13,31 @@
 // limitations under the License.
 
 use exonum::{
-    blockchain::CallInBlock,
+    blockchain::{Blockchain, CallInBlock},
     crypto::{Hash, KeyPair, PublicKey},
-    helpers::Height,
+    helpers::{Height, ValidatorId},
+    keys::Keys,
     messages::{AnyTx, Verified},
-    runtime::SnapshotExt,
+    runtime::{CallInfo, CommonError, CoreError, ErrorMatch, SnapshotExt},
 };
 use exonum_explorer::{api::TransactionResponse, BlockchainExplorer};
-use exonum_merkledb::{access::Access, HashTag, ObjectHash, Snapshot};
-use exonum_testkit::{ApiKind, Spec, TestKit, TestKitApi, TestKitBuilder};
+use exonum_merkledb::{
+    access::{Access, CopyAccessExt},
+    HashTag, ObjectHash, Snapshot, SystemSchema,
+};
+use exonum_rust_runtime::RustRuntime;
+use exonum_testkit::{ApiKind, Spec, TestKit, TestKitApi, TestKitBuilder, TestNode, TxOrdering};
 use pretty_assertions::assert_eq;
 
-use std::collections::HashSet;
+use std::{
+    cmp::Reverse,
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
 
 use crate::counter::{
-    CounterSchema, CounterService, CounterServiceInterface, CounterWithProof, SERVICE_ID,
+    CounterSchema, CounterService, CounterServiceInterface, CounterWithProof, Error, SERVICE_ID,
     SERVICE_NAME,
 };
 
@@ -70,6 +80,11 @@ fn gen_inc_incorrect_tx(by: u64) -> Verified<AnyTx> {
     KeyPair::random().increment(SERVICE_ID + 1, by)
 }
 
+/// Generates a call to `increment` with the payload which cannot be decoded as `u64`.
+fn gen_malformed_inc_tx() -> Verified<AnyTx> {
+    AnyTx::new(CallInfo::new(SERVICE_ID, 0), vec![1, 2, 3]).sign_with_keypair(&KeyPair::random())
+}
+
 #[test]
 fn test_inc_add_tx() {
     let (mut testkit, _) = init_testkit();
@@ -78,6 +93,35 @@ fn test_inc_add_tx() {
     assert!(testkit.is_tx_in_pool(&tx.object_hash()));
 }
 
+#[test]
+fn test_assert_pooled_and_committed() {
+    let (mut testkit, _) = init_testkit();
+    let tx = gen_inc_tx(5);
+    testkit.add_tx(tx.clone());
+    testkit.assert_pooled(tx.object_hash());
+
+    testkit.create_block();
+    testkit.assert_committed(tx.object_hash());
+}
+
+#[test]
+#[should_panic(expected = "is expected to be in the pool, but it is committed at height 1")]
+fn test_assert_pooled_with_committed_transaction() {
+    let (mut testkit, _) = init_testkit();
+    let tx = gen_inc_tx(5);
+    testkit.create_block_with_transaction(tx.clone());
+    testkit.assert_pooled(tx.object_hash());
+}
+
+#[test]
+#[should_panic(expected = "is expected to be committed, but it is only in the pool")]
+fn test_assert_committed_with_pooled_transaction() {
+    let (mut testkit, _) = init_testkit();
+    let tx = gen_inc_tx(5);
+    testkit.add_tx(tx.clone());
+    testkit.assert_committed(tx.object_hash());
+}
+
 #[test]
 #[should_panic(expected = "Attempt to add incorrect transaction in the pool")]
 fn test_inc_add_tx_incorrect_transaction() {
@@ -86,6 +130,547 @@ fn test_inc_add_tx_incorrect_transaction() {
     testkit.add_tx(incorrect_tx);
 }
 
+#[test]
+fn test_our_validator_id() {
+    let testkit = TestKitBuilder::validator().with_validators(2).build();
+    assert_eq!(testkit.our_validator_id(), Some(ValidatorId(0)));
+
+    let testkit = TestKitBuilder::auditor().with_validators(2).build();
+    assert_eq!(testkit.our_validator_id(), None);
+}
+
+#[test]
+fn test_passive_auditor_applies_blocks() {
+    let mut validator = TestKitBuilder::validator()
+        .with_validators(2)
+        .with(Spec::new(CounterService).with_instance(SERVICE_ID, SERVICE_NAME, ()))
+        .build();
+    let validator_keys = validator
+        .network()
+        .validators()
+        .iter()
+        .map(|node| Keys::from_keys(node.consensus_keypair(), node.service_keypair()));
+    let mut auditor = TestKitBuilder::passive_auditor()
+        .with_keys(validator_keys)
+        .with(Spec::new(CounterService).with_instance(SERVICE_ID, SERVICE_NAME, ()))
+        .build();
+    assert_eq!(auditor.last_block_hash(), validator.last_block_hash());
+
+    for txs in vec![
+        vec![gen_inc_tx(5)],
+        vec![],
+        vec![gen_inc_tx(3), gen_inc_tx(4)],
+    ] {
+        let block = validator.create_block_with_transactions(txs);
+        let transactions = block.transactions.iter().map(|tx| tx.message().clone());
+        auditor.apply_block(&block.header, transactions);
+        assert_eq!(auditor.last_block_hash(), validator.last_block_hash());
+    }
+
+    assert_eq!(
+        auditor.blockchain().last_block(),
+        validator.blockchain().last_block()
+    );
+    let snapshot = auditor.snapshot();
+    assert_eq!(get_schema(&snapshot).counter.get(), Some(12));
+}
+
+#[test]
+fn test_testkits_with_shared_validator_keys() {
+    let mut first = TestKitBuilder::validator()
+        .with_validators(2)
+        .with(Spec::new(CounterService).with_instance(SERVICE_ID, SERVICE_NAME, ()))
+        .build();
+    let keys: Vec<_> = first
+        .network()
+        .validators()
+        .iter()
+        .map(|node| Keys::from_keys(node.consensus_keypair(), node.service_keypair()))
+        .collect();
+
+    let node = TestNode::from_keys(ValidatorId(1), keys[1].clone());
+    assert_eq!(node.validator_id(), Some(ValidatorId(1)));
+    assert_eq!(
+        node.public_keys(),
+        first.network().validators()[1].public_keys()
+    );
+
+    let second = TestKitBuilder::validator()
+        .with_keys(keys)
+        .with(Spec::new(CounterService).with_instance(SERVICE_ID, SERVICE_NAME, ()))
+        .build();
+    assert_eq!(get_validator_keys(&second), get_validator_keys(&first));
+
+    first.create_block_with_transaction(gen_inc_tx(5));
+    let block_proof = first
+        .snapshot()
+        .for_core()
+        .block_and_precommits(Height(1))
+        .unwrap();
+    block_proof.verify(&get_validator_keys(&second)).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "Passive auditor cannot create blocks")]
+fn test_passive_auditor_cannot_create_blocks() {
+    let mut auditor = TestKitBuilder::passive_auditor()
+        .with(Spec::new(CounterService).with_instance(SERVICE_ID, SERVICE_NAME, ()))
+        .build();
+    auditor.create_block_with_transactions(vec![gen_inc_tx(5)]);
+}
+
+#[test]
+fn test_fork_and_merge() {
+    let (mut testkit, _) = init_testkit();
+    testkit.create_block_with_transaction(gen_inc_tx(5));
+
+    let fork = testkit.fork();
+    fork.get_entry("custom_entry").set(42_u64);
+    // Overwrite the counter in the service storage.
+    fork.get_proof_entry("counter.counter").set(10_u64);
+    // Changes in the fork are not visible until it is merged.
+    assert_eq!(get_schema(&testkit.snapshot()).counter.get(), Some(5));
+
+    testkit.merge_fork(fork);
+    assert_eq!(testkit.height(), Height(1));
+    let snapshot = testkit.snapshot();
+    assert_eq!(snapshot.get_entry::<_, u64>("custom_entry").get(), Some(42));
+    assert_eq!(get_schema(&snapshot).counter.get(), Some(10));
+}
+
+#[test]
+fn test_start_service_after_genesis() {
+    let mut testkit = TestKitBuilder::validator()
+        .with(Spec::new(CounterService).with_instance(SERVICE_ID, SERVICE_NAME, ()))
+        .without_genesis_services()
+        .build();
+    let snapshot = testkit.snapshot();
+    assert!(snapshot
+        .for_dispatcher()
+        .get_instance(SERVICE_NAME)
+        .is_none());
+
+    // The service is not started, so transactions to it are incorrect.
+    let tx = gen_inc_tx(5);
+    let err = Blockchain::check_tx(&snapshot, &tx).unwrap_err();
+    assert_eq!(
+        err,
+        ErrorMatch::from_fail(&CoreError::IncorrectInstanceId).with_any_description()
+    );
+
+    // The service is started in a new block.
+    let block = testkit.start_service(SERVICE_NAME);
+    assert_eq!(block.height(), Height(1));
+    let block = testkit.create_block_with_transaction(tx);
+    block[0].status().unwrap();
+    assert_eq!(get_schema(&testkit.snapshot()).counter.get(), Some(5));
+}
+
+#[test]
+fn test_artifact_with_multiple_instances() {
+    const OTHER_SERVICE_ID: u32 = SERVICE_ID + 1;
+    const OTHER_SERVICE_NAME: &str = "other-counter";
+
+    let mut testkit = TestKitBuilder::validator()
+        .with(
+            Spec::new(CounterService)
+                .with_instance(SERVICE_ID, SERVICE_NAME, ())
+                .with_instance(OTHER_SERVICE_ID, OTHER_SERVICE_NAME, ()),
+        )
+        .build();
+
+    let block = testkit.create_block_with_transactions(vec![
+        gen_inc_tx(5),
+        KeyPair::random().increment(OTHER_SERVICE_ID, 3),
+        KeyPair::random().increment(OTHER_SERVICE_ID, 4),
+    ]);
+    for tx in block.iter() {
+        tx.status().unwrap();
+    }
+
+    // Both instances are created from the same artifact, but have separate storage.
+    let snapshot = testkit.snapshot();
+    let other_schema: CounterSchema<_> = snapshot.service_schema(OTHER_SERVICE_NAME).unwrap();
+    assert_eq!(get_schema(&snapshot).counter.get(), Some(5));
+    assert_eq!(other_schema.counter.get(), Some(7));
+
+    let dispatcher = snapshot.for_dispatcher();
+    let artifact = |name: &str| dispatcher.get_instance(name).unwrap().spec.artifact;
+    assert_eq!(artifact(SERVICE_NAME), artifact(OTHER_SERVICE_NAME));
+}
+
+#[test]
+fn test_injected_clock() {
+    let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+    let now = Arc::new(Mutex::new(start));
+    let now_ = Arc::clone(&now);
+    let mut testkit = TestKitBuilder::validator()
+        .with_validators(2)
+        .with_clock(Box::new(move || *now_.lock().unwrap()))
+        .build();
+
+    testkit.create_block();
+    *now.lock().unwrap() += Duration::from_secs(10);
+    testkit.create_block();
+    testkit.create_block();
+
+    let snapshot = testkit.snapshot();
+    let block_times = |height: u64| -> Vec<SystemTime> {
+        let proof = snapshot
+            .for_core()
+            .block_and_precommits(Height(height))
+            .unwrap();
+        proof
+            .precommits
+            .iter()
+            .map(|precommit| precommit.payload().time.into())
+            .collect()
+    };
+    let later = start + Duration::from_secs(10);
+    assert_eq!(block_times(1), vec![start; 2]);
+    assert_eq!(block_times(2), vec![later; 2]);
+    assert_eq!(block_times(3), vec![later; 2]);
+}
+
+#[test]
+fn test_snapshot_at_height() {
+    const HISTORY_LEN: usize = 10;
+
+    let mut testkit = TestKitBuilder::validator()
+        .with(Spec::new(CounterService).with_instance(SERVICE_ID, SERVICE_NAME, ()))
+        .with_snapshot_history(HISTORY_LEN)
+        .build();
+    testkit.create_block_with_transaction(gen_inc_tx(5));
+    testkit.create_block();
+    testkit.create_block_with_transaction(gen_inc_tx(3));
+    assert_eq!(testkit.height(), Height(3));
+
+    let counter_at = |height: u64| {
+        let snapshot = testkit.snapshot_at(Height(height)).unwrap();
+        assert_eq!(snapshot.for_core().last_block().height, Height(height));
+        get_schema(&snapshot).counter.get()
+    };
+    assert_eq!(counter_at(0), None);
+    assert_eq!(counter_at(1), Some(5));
+    assert_eq!(counter_at(2), Some(5));
+    assert_eq!(counter_at(3), Some(8));
+    assert!(testkit.snapshot_at(Height(4)).is_none());
+
+    // Snapshots for the heights reverted by a rollback are forgotten.
+    testkit.checkpoint();
+    testkit.create_block_with_transaction(gen_inc_tx(1));
+    testkit.create_block_with_transaction(gen_inc_tx(1));
+    assert_eq!(
+        get_schema(&testkit.snapshot_at(Height(4)).unwrap())
+            .counter
+            .get(),
+        Some(9)
+    );
+    testkit.rollback();
+    assert!(testkit.snapshot_at(Height(4)).is_none());
+    testkit.create_block_with_transaction(gen_inc_tx(2));
+    assert_eq!(
+        get_schema(&testkit.snapshot_at(Height(4)).unwrap())
+            .counter
+            .get(),
+        Some(10)
+    );
+
+    // Old snapshots are eventually pruned.
+    let pruned_height = testkit.height().0 + 1;
+    testkit.create_blocks_until(Height(pruned_height + HISTORY_LEN as u64));
+    assert!(testkit.snapshot_at(Height(pruned_height)).is_none());
+    assert!(testkit.snapshot_at(Height(pruned_height + 1)).is_some());
+}
+
+#[test]
+#[should_panic(expected = "Snapshot history is not recorded")]
+fn test_snapshot_at_without_history() {
+    let (mut testkit, _) = init_testkit();
+    testkit.create_block();
+    // The snapshot for the current height is always available.
+    assert!(testkit.snapshot_at(Height(1)).is_some());
+    testkit.snapshot_at(Height(0));
+}
+
+#[test]
+fn test_historical_status_after_rollback() {
+    let mut testkit = TestKitBuilder::validator()
+        .with(Spec::new(CounterService).with_instance(SERVICE_ID, SERVICE_NAME, ()))
+        .with_status_history()
+        .build();
+    let ok_tx = gen_inc_tx(5);
+    let failed_tx = gen_inc_tx(0);
+    let expected_err = ErrorMatch::from_fail(&Error::AddingZero).for_service(SERVICE_ID);
+
+    testkit.checkpoint();
+    testkit.create_block_with_transactions(vec![ok_tx.clone(), failed_tx.clone()]);
+    let height = testkit.height();
+    testkit.assert_committed(ok_tx.object_hash());
+    testkit
+        .historical_status(height, ok_tx.object_hash())
+        .unwrap()
+        .unwrap();
+
+    testkit.rollback();
+    // The live statuses are gone...
+    let snapshot = testkit.snapshot();
+    let locations = snapshot.for_core().transactions_locations();
+    assert!(!locations.contains(&ok_tx.object_hash()));
+    assert!(!locations.contains(&failed_tx.object_hash()));
+    // ...but the historical ones are retained.
+    testkit
+        .historical_status(height, ok_tx.object_hash())
+        .unwrap()
+        .unwrap();
+    let err = testkit
+        .historical_status(height, failed_tx.object_hash())
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(err, expected_err);
+    assert!(testkit
+        .historical_status(height.next(), ok_tx.object_hash())
+        .is_none());
+}
+
+#[test]
+fn test_max_block_transactions() {
+    let mut testkit = TestKitBuilder::validator()
+        .with(Spec::new(CounterService).with_instance(SERVICE_ID, SERVICE_NAME, ()))
+        .with_max_block_transactions(2)
+        .build();
+    let transactions: Vec<_> = (1..=5).map(gen_inc_tx).collect();
+    for tx in &transactions {
+        testkit.add_tx(tx.clone());
+    }
+
+    let mut block_sizes = vec![];
+    while transactions
+        .iter()
+        .any(|tx| testkit.is_tx_in_pool(&tx.object_hash()))
+    {
+        let block = testkit.create_block();
+        block_sizes.push(block.transactions.len());
+    }
+    assert_eq!(block_sizes, vec![2, 2, 1]);
+    assert_eq!(testkit.height(), Height(3));
+
+    let snapshot = testkit.snapshot();
+    assert_eq!(get_schema(&snapshot).counter.get(), Some(15));
+}
+
+#[test]
+fn test_tx_ordering() {
+    let mut transactions: Vec<_> = (1..=4).map(gen_inc_tx).collect();
+    // Add transactions to the pool in the order different from the order of their hashes.
+    transactions.sort_unstable_by_key(|tx| Reverse(tx.object_hash()));
+
+    for &ordering in &[TxOrdering::ByHash, TxOrdering::Fifo] {
+        let mut testkit = TestKitBuilder::validator()
+            .with(Spec::new(CounterService).with_instance(SERVICE_ID, SERVICE_NAME, ()))
+            .with_tx_ordering(ordering)
+            .build();
+        for tx in &transactions {
+            testkit.add_tx(tx.clone());
+        }
+
+        let block = testkit.create_block();
+        let committed: Vec<_> = block
+            .transactions
+            .iter()
+            .map(|tx| tx.message().object_hash())
+            .collect();
+        let mut expected: Vec<_> = transactions.iter().map(ObjectHash::object_hash).collect();
+        if ordering == TxOrdering::ByHash {
+            expected.sort_unstable();
+        }
+        assert_eq!(committed, expected);
+    }
+}
+
+#[test]
+fn test_restart() {
+    let (mut testkit, _) = init_testkit();
+    testkit.create_block_with_transaction(gen_inc_tx(5));
+    testkit.create_block();
+    testkit.create_block_with_transaction(gen_inc_tx(3));
+    let pending_tx = gen_inc_tx(2);
+    testkit.add_tx(pending_tx.clone());
+    let state_hash = SystemSchema::new(&testkit.snapshot()).state_hash();
+
+    let rust_runtime = RustRuntime::builder().with_factory(CounterService);
+    let mut testkit = testkit.restart(rust_runtime);
+    let snapshot = testkit.snapshot();
+    assert_eq!(testkit.height(), Height(3));
+    assert_eq!(SystemSchema::new(&snapshot).state_hash(), state_hash);
+    assert_eq!(get_schema(&snapshot).counter.get(), Some(8));
+    assert!(testkit.is_tx_in_pool(&pending_tx.object_hash()));
+
+    // The service is operational after the restart.
+    let block = testkit.create_block();
+    assert_eq!(block.len(), 1);
+    block[0].status().unwrap();
+    assert_eq!(get_schema(&testkit.snapshot()).counter.get(), Some(10));
+}
+
+#[test]
+fn test_testkit_from_existing_database() {
+    let mut testkit = TestKit::for_rust_service(CounterService, SERVICE_NAME, SERVICE_ID, ());
+    testkit.create_block_with_transaction(gen_inc_tx(5));
+    testkit.create_block_with_transaction(gen_inc_tx(3));
+    let state_hash = SystemSchema::new(&testkit.snapshot()).state_hash();
+    let keys: Vec<_> = testkit
+        .network()
+        .validators()
+        .iter()
+        .map(|node| Keys::from_keys(node.consensus_keypair(), node.service_keypair()))
+        .collect();
+    let db = testkit.stop().into_database();
+
+    let mut testkit = TestKitBuilder::validator()
+        .with_keys(keys)
+        .with(Spec::new(CounterService).with_instance(SERVICE_ID, SERVICE_NAME, ()))
+        .with_database(db)
+        .build();
+    let snapshot = testkit.snapshot();
+    assert_eq!(testkit.height(), Height(2));
+    assert_eq!(SystemSchema::new(&snapshot).state_hash(), state_hash);
+    assert_eq!(get_schema(&snapshot).counter.get(), Some(8));
+
+    testkit.create_block_with_transaction(gen_inc_tx(2));
+    assert_eq!(get_schema(&testkit.snapshot()).counter.get(), Some(10));
+}
+
+#[test]
+#[should_panic(expected = "Validator keys in the provided database do not match")]
+fn test_testkit_from_database_with_other_validators() {
+    let mut testkit = TestKit::for_rust_service(CounterService, SERVICE_NAME, SERVICE_ID, ());
+    testkit.create_block();
+    let db = testkit.stop().into_database();
+
+    TestKitBuilder::validator()
+        .with(Spec::new(CounterService).with_instance(SERVICE_ID, SERVICE_NAME, ()))
+        .with_database(db)
+        .build();
+}
+
+#[test]
+#[should_panic(expected = "Validator keys in the provided database do not match")]
+fn test_testkit_from_genesis_database_with_other_validators() {
+    let testkit = TestKit::for_rust_service(CounterService, SERVICE_NAME, SERVICE_ID, ());
+    let db = testkit.stop().into_database();
+
+    TestKitBuilder::validator()
+        .with(Spec::new(CounterService).with_instance(SERVICE_ID, SERVICE_NAME, ()))
+        .with_database(db)
+        .build();
+}
+
+#[test]
+fn test_last_block_timing() {
+    let (mut testkit, _) = init_testkit();
+    assert_eq!(testkit.last_block_timing(), None);
+
+    let txs = (1..=5).map(gen_inc_tx);
+    testkit.create_block_with_transactions(txs);
+    let timing = testkit.last_block_timing().unwrap();
+    assert!(timing.dispatch > Duration::from_secs(0));
+    assert!(timing.merge > Duration::from_secs(0));
+    assert!(timing.total >= timing.dispatch + timing.merge);
+}
+
+#[test]
+fn test_failed_transaction_is_recorded_by_default() {
+    let (mut testkit, _) = init_testkit();
+    let block = testkit.create_block_with_transaction(gen_inc_tx(0));
+    let expected_err = ErrorMatch::from_fail(&Error::AddingZero).for_service(SERVICE_ID);
+    assert_eq!(*block[0].status().unwrap_err(), expected_err);
+}
+
+#[test]
+#[should_panic(expected = "in block at height 2 failed")]
+fn test_panic_on_tx_error() {
+    let mut testkit = TestKitBuilder::validator()
+        .with(Spec::new(CounterService).with_instance(SERVICE_ID, SERVICE_NAME, ()))
+        .panic_on_tx_error()
+        .build();
+    // Successful transactions do not cause a panic.
+    testkit.create_block_with_transaction(gen_inc_tx(5));
+    testkit.create_block_with_transaction(gen_inc_tx(0));
+}
+
+#[test]
+fn test_malformed_transaction_is_recorded_by_default() {
+    let (mut testkit, _) = init_testkit();
+    let block = testkit.create_block_with_transaction(gen_malformed_inc_tx());
+    let expected_err = ErrorMatch::from_fail(&CommonError::MalformedArguments)
+        .with_any_description()
+        .for_service(SERVICE_ID);
+    assert_eq!(*block[0].status().unwrap_err(), expected_err);
+}
+
+#[test]
+#[should_panic(expected = "in block at height 2 cannot be decoded")]
+fn test_strict_decoding() {
+    let mut testkit = TestKitBuilder::validator()
+        .with(Spec::new(CounterService).with_instance(SERVICE_ID, SERVICE_NAME, ()))
+        .with_strict_decoding()
+        .build();
+    // Transactions failing for other reasons do not cause a panic.
+    let block = testkit.create_block_with_transaction(gen_inc_tx(0));
+    block[0].status().unwrap_err();
+    testkit.create_block_with_transaction(gen_malformed_inc_tx());
+}
+
+#[test]
+fn test_state_hash() {
+    let (mut testkit, _) = init_testkit();
+    let initial_hash = testkit.state_hash();
+    assert_eq!(initial_hash, testkit.blockchain().last_block().state_hash);
+
+    testkit.create_block_with_transaction(gen_inc_tx(5));
+    assert_eq!(testkit.height(), Height(1));
+    let state_hash = testkit.state_hash();
+    assert_ne!(state_hash, initial_hash);
+    assert_eq!(
+        state_hash,
+        SystemSchema::new(&testkit.snapshot()).state_hash()
+    );
+}
+
+#[test]
+fn test_export_blocks() {
+    let (mut testkit, _) = init_testkit();
+    let txs: Vec<_> = (1..=4).map(gen_inc_tx).collect();
+    for tx in &txs {
+        testkit.create_block_with_transaction(tx.clone());
+    }
+    testkit.create_block();
+
+    let blocks = testkit.export_blocks(Height(1), Height(4));
+    assert_eq!(blocks.len(), 4);
+    for (i, (block, tx)) in blocks.iter().zip(&txs).enumerate() {
+        assert_eq!(block.header.height, Height(i as u64 + 1));
+        assert_eq!(block.header.tx_count, 1);
+        assert_eq!(block.transactions.len(), 1);
+        assert_eq!(
+            block.transactions[0].message().object_hash(),
+            tx.object_hash()
+        );
+        assert!(block.transactions[0].status().is_ok());
+    }
+    assert_eq!(
+        blocks[3].header.object_hash(),
+        testkit.export_blocks(Height(5), Height(5))[0]
+            .header
+            .prev_hash
+    );
+
+    // Exported blocks should be serializable.
+    let json = serde_json::to_value(&blocks).unwrap();
+    assert_eq!(json.as_array().unwrap().len(), 4);
+}
+
 #[tokio::test]
 async fn test_inc_count_create_block() {
     let (mut testkit, api) = init_testkit();
@@ -417,3 +1002,47 @@ async fn submitting_incorrect_tx_via_sender() {
     testkit.poll_events();
     assert!(!testkit.is_tx_in_pool(&tx_hash));
 }
+
+#[test]
+fn test_last_block_summary() {
+    let (mut testkit, _) = init_testkit();
+    let failing_tx = gen_inc_tx(0);
+    testkit.create_block_with_transactions(vec![gen_inc_tx(1), failing_tx.clone(), gen_inc_tx(2)]);
+
+    let summary = testkit.last_block_summary();
+    assert_eq!(summary.total, 3);
+    assert_eq!(summary.succeeded, 2);
+    assert_eq!(summary.failed.len(), 1);
+    let (tx_hash, err) = &summary.failed[0];
+    assert_eq!(*tx_hash, failing_tx.object_hash());
+    let expected_err = ErrorMatch::from_fail(&Error::AddingZero).for_service(SERVICE_ID);
+    assert_eq!(*err, expected_err);
+
+    testkit.create_block();
+    let summary = testkit.last_block_summary();
+    assert_eq!(summary.total, 0);
+    assert_eq!(summary.succeeded, 0);
+    assert!(summary.failed.is_empty());
+}
+
+#[test]
+fn test_create_block_with_time() {
+    let (mut testkit, _) = init_testkit();
+    let time = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+    testkit.create_block_with_time(time).unwrap();
+    assert_eq!(testkit.height(), Height(1));
+    let snapshot = testkit.snapshot();
+    let proof = snapshot.for_core().block_and_precommits(Height(1)).unwrap();
+    let precommit_time: SystemTime = proof.precommits[0].payload().time.into();
+    assert_eq!(precommit_time, time);
+
+    let earlier_time = time - Duration::from_secs(10);
+    let err = testkit.create_block_with_time(earlier_time).unwrap_err();
+    assert_eq!(err.previous, time);
+    assert_eq!(err.requested, earlier_time);
+    assert_eq!(testkit.height(), Height(1));
+
+    // Blocks with the same time are accepted.
+    testkit.create_block_with_time(time).unwrap();
+    assert_eq!(testkit.height(), Height(2));
+}
@@ -20,7 +20,7 @@ use exonum::{
         SnapshotExt,
     },
 };
-use exonum_rust_runtime::DefaultInstance;
+use exonum_rust_runtime::{DefaultInstance, FallthroughAuth};
 use exonum_testkit::{Spec, TestKit, TestKitBuilder};
 use pretty_assertions::assert_eq;
 
@@ -30,7 +30,8 @@ use crate::{
     schema::{Wallet, WalletSchema},
     services::{
         AnyCall, AnyCallService, CallAny, CustomCall, CustomCallInterface, CustomCallService,
-        DepositInterface, DepositService, Issue, TxIssue, WalletInterface, WalletService,
+        DepositInterface, DepositService, Issue, TxIssue, WalletInterface, WalletInterfaceMut,
+        WalletService,
     },
 };
 
@@ -574,3 +575,151 @@ fn custom_call_err_incorrect_instance_id() {
         }]
     );
 }
+
+#[test]
+fn custom_call_writes_to_caller_and_callee() {
+    let (testkit, res) = execute_custom_call(|context| {
+        let author = context.caller().author().unwrap();
+        // Write data on behalf of the calling service.
+        WalletSchema::new(context.service_data()).wallets.put(
+            &author,
+            Wallet {
+                name: "Magic".to_string(),
+                balance: 102,
+            },
+        );
+        // Make the called service write data within the same transaction.
+        FallthroughAuth(context).create_wallet(WalletService::ID, "Alice".to_owned())
+    });
+    res.expect("Service-to-service call failed");
+
+    let snapshot = testkit.snapshot();
+    let caller_schema = WalletSchema::new(
+        snapshot
+            .for_service(CustomCallService::INSTANCE_NAME)
+            .unwrap(),
+    );
+    let caller_wallets = caller_schema.wallets.values().collect::<Vec<_>>();
+    assert_eq!(caller_wallets.len(), 1);
+    assert_eq!(caller_wallets[0].name, "Magic");
+
+    let callee_wallets = WalletService::get_schema(&snapshot)
+        .wallets
+        .values()
+        .collect::<Vec<_>>();
+    assert_eq!(
+        callee_wallets,
+        vec![Wallet {
+            name: "Alice".to_string(),
+            balance: 0,
+        }]
+    );
+}
+
+fn testkit_with_custom_call(f: CustomCall) -> TestKit {
+    TestKitBuilder::validator()
+        .with_logger()
+        .with(Spec::new(WalletService).with_default_instance())
+        .with(Spec::new(DepositService).with_default_instance())
+        .with(Spec::new(CustomCallService::new(f)).with_default_instance())
+        .build()
+}
+
+/// Writes an entry on behalf of the called service and issues tokens to the first wallet
+/// in the wallet service, forwarding the caller of the call.
+fn write_and_issue(mut context: ExecutionContext<'_>) -> Result<(), ExecutionError> {
+    context
+        .caller()
+        .as_service()
+        .ok_or(Error::WrongInterfaceCaller)?;
+    let to = {
+        let wallets = context.data().for_service(WalletService::ID).unwrap();
+        let first_key = WalletSchema::new(wallets).wallets.keys().next();
+        first_key.ok_or(Error::WalletNotFound)?
+    };
+
+    WalletSchema::new(context.service_data()).wallets.put(
+        &to,
+        Wallet {
+            name: "Magic".to_string(),
+            balance: 10,
+        },
+    );
+    FallthroughAuth(context).issue(WalletService::ID, Issue { to, amount: 10 })
+}
+
+#[test]
+fn call_service_writes_to_both_services() {
+    let mut testkit = testkit_with_custom_call(write_and_issue);
+    let keypair = KeyPair::random();
+    execute_transaction(
+        &mut testkit,
+        keypair.create_wallet(WalletService::ID, "Alice".into()),
+    )
+    .expect("Unable to create wallet");
+
+    // `WalletService::issue` only accepts calls from `DepositService`.
+    testkit
+        .call_service(
+            DepositService::ID,
+            CustomCallService::INSTANCE_ID,
+            0,
+            Vec::<u8>::new(),
+        )
+        .expect("Service-to-service call failed");
+
+    let snapshot = testkit.snapshot();
+    let caller_schema = WalletSchema::new(
+        snapshot
+            .for_service(CustomCallService::INSTANCE_NAME)
+            .unwrap(),
+    );
+    let caller_wallet = caller_schema.wallets.get(&keypair.public_key()).unwrap();
+    assert_eq!(caller_wallet.balance, 10);
+    let callee_wallet = WalletService::get_schema(&snapshot)
+        .wallets
+        .get(&keypair.public_key())
+        .unwrap();
+    assert_eq!(callee_wallet.balance, 10);
+}
+
+#[test]
+fn call_service_from_unauthorized_service() {
+    let mut testkit = testkit_with_custom_call(write_and_issue);
+    let keypair = KeyPair::random();
+    execute_transaction(
+        &mut testkit,
+        keypair.create_wallet(WalletService::ID, "Alice".into()),
+    )
+    .expect("Unable to create wallet");
+
+    let err = testkit
+        .call_service(
+            WalletService::ID,
+            CustomCallService::INSTANCE_ID,
+            0,
+            Vec::<u8>::new(),
+        )
+        .unwrap_err();
+    assert_eq!(err, ErrorMatch::from_fail(&Error::UnauthorizedIssuer));
+
+    // Changes made by the failed call are rolled back.
+    let snapshot = testkit.snapshot();
+    let caller_schema = WalletSchema::new(
+        snapshot
+            .for_service(CustomCallService::INSTANCE_NAME)
+            .unwrap(),
+    );
+    assert_eq!(caller_schema.wallets.values().count(), 0);
+    let callee_wallet = WalletService::get_schema(&snapshot)
+        .wallets
+        .get(&keypair.public_key())
+        .unwrap();
+    assert_eq!(callee_wallet.balance, 0);
+
+    // Calls on behalf of inactive services are rejected.
+    let err = testkit
+        .call_service(1_000, CustomCallService::INSTANCE_ID, 0, Vec::<u8>::new())
+        .unwrap_err();
+    assert_eq!(err, ErrorMatch::from_fail(&CoreError::IncorrectInstanceId));
+}
@@ -16,8 +16,8 @@ use exonum::runtime::{
     migrations::{InitMigrationError, MigrationScript},
     oneshot::Receiver,
     versioning::Version,
-    ArtifactId, ExecutionContext, ExecutionError, InstanceState, Mailbox, MethodId, Runtime,
-    WellKnownRuntime,
+    ArtifactId, ExecutionContext, ExecutionError, InstanceState, InstanceStatus, Mailbox, MethodId,
+    Runtime, SnapshotExt, WellKnownRuntime,
 };
 use exonum_merkledb::Snapshot;
 use exonum_rust_runtime::spec::ForeignSpec;
@@ -189,3 +189,28 @@ fn test_runtime_factory() {
     tester.assert_artifact_deployed(artifact, deploy_args);
     tester.assert_constructor_params_passed(constructor);
 }
+
+#[test]
+fn test_instance_from_artifact() {
+    let tester = Arc::new(RuntimeTester::default());
+
+    let artifact =
+        ArtifactId::new(TestRuntime::ID, "artifact-name", Version::new(1, 0, 0)).unwrap();
+    let constructor = b"constructor_params";
+
+    let testkit = TestKitBuilder::validator()
+        .with_additional_runtime(TestRuntime::with_runtime_tester(tester.clone()))
+        .with_instance(artifact.clone(), 1, "test_instance", constructor.to_vec())
+        .build();
+
+    tester.assert_artifact_deployed(artifact.clone(), &[]);
+    tester.assert_constructor_params_passed(constructor);
+    let snapshot = testkit.snapshot();
+    let state = snapshot
+        .for_dispatcher()
+        .get_instance("test_instance")
+        .unwrap();
+    assert_eq!(state.spec.id, 1);
+    assert_eq!(state.spec.artifact, artifact);
+    assert_eq!(state.status, Some(InstanceStatus::Active));
+}
@@ -0,0 +1,100 @@
+// Copyright 2020 The Exonum Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the testkit server with TLS enabled.
+
+use exonum::helpers::Height;
+use exonum_testkit::{server::TestKitStatus, TestKitBuilder, TlsConfig};
+use tempfile::TempDir;
+use tokio::{task::LocalSet, time::delay_for};
+
+use std::{
+    fs,
+    net::{SocketAddr, TcpListener},
+    time::Duration,
+};
+
+fn free_address() -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.local_addr().unwrap()
+}
+
+fn create_self_signed_cert(dir: &TempDir) -> TlsConfig {
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_owned()]).unwrap();
+    let cert_path = dir.path().join("cert.pem");
+    let key_path = dir.path().join("key.pem");
+    fs::write(&cert_path, cert.serialize_pem().unwrap()).unwrap();
+    fs::write(&key_path, cert.serialize_private_key_pem()).unwrap();
+    TlsConfig::new(cert_path, key_path)
+}
+
+#[tokio::test]
+async fn status_over_https() {
+    let dir = TempDir::new().unwrap();
+    let tls_config = create_self_signed_cert(&dir);
+    let public_address = free_address();
+    let private_address = free_address();
+
+    let local_set = LocalSet::new();
+    local_set.spawn_local(async move {
+        TestKitBuilder::validator()
+            .serve_tls(public_address, private_address, tls_config)
+            .await
+            .unwrap();
+    });
+
+    let check_status = async move {
+        let client = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .build()
+            .unwrap();
+        let url = format!(
+            "https://localhost:{}/api/testkit/v1/status",
+            private_address.port()
+        );
+
+        // Wait until the server is started.
+        let mut response = None;
+        for _ in 0..50 {
+            if let Ok(resp) = client.get(&url).send().await {
+                response = Some(resp);
+                break;
+            }
+            delay_for(Duration::from_millis(100)).await;
+        }
+
+        let status: TestKitStatus = response
+            .expect("Cannot connect to testkit server over HTTPS")
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(status.height, Height(0));
+        assert_eq!(status.nodes.len(), 1);
+    };
+    local_set.run_until(check_status).await;
+}
+
+#[tokio::test]
+async fn invalid_tls_config() {
+    let dir = TempDir::new().unwrap();
+    let tls_config = TlsConfig::new(dir.path().join("cert.pem"), dir.path().join("key.pem"));
+
+    let err = TestKitBuilder::validator()
+        .serve_tls(free_address(), free_address(), tls_config)
+        .await
+        .unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("Cannot open TLS file"), "{}", message);
+    assert!(message.contains("cert.pem"), "{}", message);
+}